@@ -1,21 +1,52 @@
-use clap::Parser;
+use crate::config::schema::SchemaKind;
+use crate::core::OutputFormat;
+use clap::{Parser, Subcommand};
 
 #[derive(Parser, Debug)]
 #[command(name = "cubenceline")]
 #[command(version, about = "High-performance Claude Code StatusLine")]
 pub struct Cli {
-    /// Enter TUI configuration mode
-    #[arg(short = 'c', long = "config")]
-    pub config: bool,
+    /// Enter TUI configuration mode, or with `--config=FILE`, use that config
+    /// file instead of config.toml
+    #[arg(
+        short = 'c',
+        long = "config",
+        value_name = "FILE",
+        num_args = 0..=1,
+        require_equals = true,
+        global = true
+    )]
+    pub config: Option<Option<String>>,
+
+    /// Use the named profile (profiles/<NAME>.toml in the config directory)
+    #[arg(long = "profile", value_name = "NAME", global = true)]
+    pub profile: Option<String>,
 
     /// Set theme
-    #[arg(short = 't', long = "theme")]
+    #[arg(short = 't', long = "theme", global = true)]
     pub theme: Option<String>,
 
+    /// Output format for the statusline
+    #[arg(
+        long = "format",
+        value_enum,
+        default_value_t = OutputFormat::Ansi,
+        global = true
+    )]
+    pub format: OutputFormat,
+
+    /// List built-in and user themes
+    #[arg(long = "list-themes")]
+    pub list_themes: bool,
+
     /// Print current configuration
     #[arg(long = "print")]
     pub print: bool,
 
+    /// With --print, show the file each value came from
+    #[arg(long = "show-origin", requires = "print")]
+    pub show_origin: bool,
+
     /// Initialize config file
     #[arg(long = "init")]
     pub init: bool,
@@ -31,10 +62,100 @@ pub struct Cli {
     /// Patch Claude Code cli.js to disable context warnings
     #[arg(long = "patch")]
     pub patch: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Manage themes
+    Theme {
+        #[command(subcommand)]
+        action: ThemeCommand,
+    },
+
+    /// Render the statusline offline from a recorded payload or a named fixture
+    Render {
+        /// Claude Code JSON payload to render
+        #[arg(long = "input", conflicts_with = "fixture")]
+        input: Option<String>,
+
+        /// Directory of recorded network responses (usage.json, cubence.json, ...)
+        #[arg(long = "responses", requires = "input")]
+        responses: Option<String>,
+
+        /// Built-in fixture name or fixture directory
+        #[arg(long = "fixture")]
+        fixture: Option<String>,
+
+        /// List built-in fixtures
+        #[arg(long = "list")]
+        list: bool,
+    },
+
+    /// Print the JSON Schema for config.toml, .ccline.toml, theme files or models.toml
+    Schema {
+        /// Which file the schema describes
+        #[arg(value_enum, default_value_t = SchemaKind::Config)]
+        kind: SchemaKind,
+    },
+
+    /// Upgrade a config file written by an older version, keeping a backup
+    Migrate {
+        /// Print the changes as a diff without writing anything
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ThemeCommand {
+    /// Convert a Starship palette, Oh My Posh theme, base16 or terminal color scheme into a theme
+    Import {
+        /// Color scheme file (.toml, .json or .yaml)
+        file: String,
+
+        /// Theme name (defaults to the file name)
+        #[arg(long = "name")]
+        name: Option<String>,
+
+        /// Overwrite an existing theme file
+        #[arg(long = "force")]
+        force: bool,
+    },
+
+    /// Print a theme as a self-contained TOML file, with `extends` resolved
+    Export {
+        /// Theme name
+        name: String,
+    },
 }
 
 impl Cli {
     pub fn parse_args() -> Self {
         Self::parse()
     }
+
+    /// Config file selected with `--config=<file>` or `--profile <name>`
+    pub fn config_file(&self) -> Result<Option<std::path::PathBuf>, Box<dyn std::error::Error>> {
+        match (&self.config, &self.profile) {
+            (Some(Some(_)), Some(_)) => {
+                Err("--config=<FILE> and --profile cannot be used together".into())
+            }
+            (Some(Some(file)), None) => Ok(Some(file.into())),
+            (_, Some(profile)) => {
+                if profile.is_empty() || profile.contains(['/', '\\', '.']) {
+                    return Err(format!("Invalid profile name '{}'", profile).into());
+                }
+                Ok(Some(crate::config::paths::profile_file(profile)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Whether `--config` was passed without a file, which opens the TUI
+    pub fn open_configurator(&self) -> bool {
+        matches!(self.config, Some(None))
+    }
 }
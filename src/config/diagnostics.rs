@@ -0,0 +1,318 @@
+//! Validation of config files and loaded configurations, reported as a list
+//! of diagnostics instead of a single error.
+
+use super::loader::{ConfigLayer, ConfigLoader};
+use super::options::{option_spec, option_specs};
+use super::types::{Config, StyleMode};
+use std::fmt;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// A file path, or a key such as `segments.git.options.show_sha`
+    pub location: String,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn error(location: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            location: location.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn warning(location: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            location: location.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        // TOML errors span several lines (position, snippet, message)
+        if self.message.contains('\n') {
+            write!(
+                f,
+                "{}: {}\n{}",
+                label,
+                self.location,
+                self.message.trim_end()
+            )
+        } else {
+            write!(f, "{}: {}: {}", label, self.location, self.message)
+        }
+    }
+}
+
+/// The outcome of loading the config files: the configuration when one could
+/// be built, and everything wrong with them
+pub struct ConfigReport {
+    pub config: Option<Config>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ConfigReport {
+    /// Load the global config and the `.ccline.toml` above `project_dir` like
+    /// rendering does, reporting TOML errors with line and column
+    pub fn load(project_dir: Option<&Path>) -> Self {
+        Self::load_with_theme(None, project_dir)
+    }
+
+    /// Like `load`, with a `--theme` taking the place of the global config
+    pub fn load_with_theme(theme: Option<&str>, project_dir: Option<&Path>) -> Self {
+        let mut diagnostics = Vec::new();
+
+        let global_layer = match theme {
+            Some(theme) => {
+                let layer =
+                    crate::ui::themes::ThemePresets::resolve_theme(theme).and_then(|config| {
+                        ConfigLayer::from_config(format!("theme:{}", theme), &config)
+                    });
+                if let Err(e) = &layer {
+                    diagnostics.push(Diagnostic::error("--theme", e.to_string()));
+                }
+                layer
+            }
+            None => Self::global_layer(&mut diagnostics),
+        };
+
+        let project_path = project_dir.and_then(ConfigLoader::find_project_config);
+        let project_layer = project_path.as_deref().map(ConfigLayer::from_file);
+        if let (Some(path), Some(Err(e))) = (&project_path, &project_layer) {
+            diagnostics.push(file_error(path, e.as_ref()));
+        }
+
+        // Warnings (e.g. an unmigrated file) still render the config as written
+        let has_errors = diagnostics.iter().any(Diagnostic::is_error);
+        let (Ok(global_layer), false) = (global_layer, has_errors) else {
+            return Self {
+                config: None,
+                diagnostics,
+            };
+        };
+        let mut layers = vec![global_layer];
+        layers.extend(project_layer.and_then(Result::ok));
+
+        let config = match Config::from_layers(&layers) {
+            Ok(config) => {
+                diagnostics.extend(config.diagnostics());
+                Some(config)
+            }
+            Err(e) => {
+                let origin = layers.last().map(|layer| layer.origin.clone());
+                diagnostics.push(Diagnostic::error(
+                    origin.unwrap_or_default(),
+                    format!("invalid after merging: {}", e),
+                ));
+                None
+            }
+        };
+
+        Self {
+            config,
+            diagnostics,
+        }
+    }
+
+    /// The global config layer, migrated in memory, with errors in the file
+    /// added to `diagnostics`
+    fn global_layer(
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<ConfigLayer, Box<dyn std::error::Error>> {
+        let global_path = Config::get_config_path();
+        let global_layer = Config::global_layer();
+        match &global_layer {
+            Err(e) => diagnostics.push(file_error(&global_path, e.as_ref())),
+            Ok(_) if global_path.exists() => {
+                // Parse the file on its own too: type errors then point into it
+                match std::fs::read_to_string(&global_path) {
+                    Ok(content) => {
+                        let version = toml::from_str(&content)
+                            .map(|value| super::migrate::config_version(&value))
+                            .unwrap_or(super::migrate::CURRENT_VERSION);
+                        if version < super::migrate::CURRENT_VERSION {
+                            diagnostics.push(Diagnostic::warning(
+                                global_path.display().to_string(),
+                                format!(
+                                    "written by config version {}; run `cubenceline migrate` to upgrade the file",
+                                    version
+                                ),
+                            ));
+                        } else if let Err(e) = toml::from_str::<Config>(&content) {
+                            diagnostics.push(Diagnostic::error(
+                                global_path.display().to_string(),
+                                e.to_string(),
+                            ));
+                        }
+                    }
+                    Err(e) => diagnostics.push(file_error(&global_path, &e)),
+                }
+            }
+            Ok(_) => {}
+        }
+        global_layer
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.diagnostics.iter().filter(|d| d.is_error()).count()
+    }
+}
+
+/// An error reading or parsing `path`, without repeating the path in the message
+fn file_error(path: &Path, error: &dyn std::error::Error) -> Diagnostic {
+    let message = error.to_string();
+    let message = message
+        .strip_prefix(&format!("Failed to parse {}: ", path.display()))
+        .unwrap_or(&message)
+        .to_string();
+    Diagnostic::error(path.display().to_string(), message)
+}
+
+impl Config {
+    /// Everything wrong with this configuration: errors make it render
+    /// differently than written, warnings are likely mistakes
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        if self.segments.is_empty() {
+            diagnostics.push(Diagnostic::error("segments", "No segments configured"));
+        }
+
+        let mut seen_ids = std::collections::HashSet::new();
+        for segment in &self.segments {
+            if !seen_ids.insert(segment.id) {
+                diagnostics.push(Diagnostic::error(
+                    "segments",
+                    format!("Duplicate segment ID: {:?}", segment.id),
+                ));
+            }
+        }
+
+        self.check_theme(&mut diagnostics);
+        self.check_keymap(&mut diagnostics);
+        self.check_colors(&mut diagnostics);
+
+        for segment in &self.segments {
+            let id = serde_json::to_value(segment.id)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default();
+            let specs = option_specs(segment.id);
+
+            let mut keys: Vec<&String> = segment.options.keys().collect();
+            keys.sort();
+            for key in keys {
+                let location = format!("segments.{}.options.{}", id, key);
+                match option_spec(segment.id, key) {
+                    Some(spec) => {
+                        if let Err(message) = spec.validate(&segment.options[key]) {
+                            diagnostics.push(Diagnostic::error(location, message));
+                        }
+                    }
+                    None => {
+                        let known: Vec<&str> = specs.iter().map(|spec| spec.key).collect();
+                        let message = if known.is_empty() {
+                            "unknown option; this segment has no options".to_string()
+                        } else {
+                            format!("unknown option (known: {})", known.join(", "))
+                        };
+                        diagnostics.push(Diagnostic::warning(location, message));
+                    }
+                }
+            }
+
+            if segment.enabled {
+                let (icon, mode) = match self.style.mode {
+                    StyleMode::Plain => (&segment.icon.plain, "plain"),
+                    StyleMode::NerdFont | StyleMode::Powerline => {
+                        (&segment.icon.nerd_font, "nerd_font")
+                    }
+                };
+                if icon.trim().is_empty() {
+                    diagnostics.push(Diagnostic::warning(
+                        format!("segments.{}.icon.{}", id, mode),
+                        format!("empty icon in {:?} mode", self.style.mode),
+                    ));
+                }
+            }
+        }
+
+        diagnostics
+    }
+
+    fn check_theme(&self, diagnostics: &mut Vec<Diagnostic>) {
+        use crate::ui::themes::{ThemePresets, BUILTIN_THEMES};
+
+        let theme_file = ThemePresets::get_themes_path().join(format!("{}.toml", self.theme));
+        if !BUILTIN_THEMES.contains(&self.theme.as_str()) && !theme_file.exists() {
+            diagnostics.push(Diagnostic::error(
+                "theme",
+                format!(
+                    "Unknown theme '{}'. Available themes: {}",
+                    self.theme,
+                    ThemePresets::list_available_themes().join(", ")
+                ),
+            ));
+        }
+    }
+
+    fn check_keymap(&self, diagnostics: &mut Vec<Diagnostic>) {
+        use crate::ui::keymap::{validate, Binding};
+        use crate::ui::themes::ThemePresets;
+
+        for (key, message) in validate(&self.keymap) {
+            diagnostics.push(Diagnostic::error(format!("keymap.{}", key), message));
+        }
+
+        let themes = ThemePresets::list_available_themes();
+        let mut keys: Vec<&String> = self.keymap.keys().collect();
+        keys.sort();
+        for key in keys {
+            if let Ok(Binding::Theme(theme)) = Binding::parse(&self.keymap[key]) {
+                if !themes.contains(&theme) {
+                    diagnostics.push(Diagnostic::warning(
+                        format!("keymap.{}", key),
+                        format!("unknown theme '{}'", theme),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn check_colors(&self, diagnostics: &mut Vec<Diagnostic>) {
+        let Ok(value) = toml::Value::try_from(self) else {
+            return;
+        };
+
+        for (key, value) in super::merge::flatten(&value) {
+            if let Some(c16) = value.get("c16").and_then(|v| v.as_integer()) {
+                if c16 > 15 {
+                    diagnostics.push(Diagnostic::error(
+                        key,
+                        format!("c16 must be 0-15, got {} (use c256 for more colors)", c16),
+                    ));
+                }
+            }
+        }
+    }
+}
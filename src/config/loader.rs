@@ -1,7 +1,41 @@
+use super::schema::{with_directive, SchemaKind};
 use super::types::Config;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// Per-project config file, looked up from the workspace directory upwards
+pub const PROJECT_CONFIG_FILE: &str = ".ccline.toml";
+
+/// One source of configuration values, merged in order over the previous ones
+#[derive(Debug, Clone)]
+pub struct ConfigLayer {
+    /// Where the values came from: a file path, a theme, or `default`
+    pub origin: String,
+    pub value: toml::Value,
+}
+
+impl ConfigLayer {
+    pub fn from_file(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = fs::read_to_string(path)?;
+        let value = toml::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+        Ok(Self {
+            origin: path.display().to_string(),
+            value,
+        })
+    }
+
+    pub fn from_config(
+        origin: impl Into<String>,
+        config: &Config,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self {
+            origin: origin.into(),
+            value: toml::Value::try_from(config)?,
+        })
+    }
+}
+
 pub struct ConfigLoader;
 
 impl ConfigLoader {
@@ -11,9 +45,11 @@ impl ConfigLoader {
 
     pub fn load_from_path<P: AsRef<Path>>(path: P) -> Result<Config, Box<dyn std::error::Error>> {
         let content = fs::read_to_string(path)?;
-        let mut config: Config = toml::from_str(&content)?;
-        config.ensure_theme_segments();
-        Ok(config)
+        let value: toml::Value = toml::from_str(&content)?;
+        match super::migrate::migrate(value.clone())? {
+            Some(migrated) => Ok(migrated.value.try_into()?),
+            None => Ok(value.try_into()?),
+        }
     }
 
     /// Initialize themes directory and create built-in theme files
@@ -41,7 +77,8 @@ impl ConfigLoader {
 
             if !theme_path.exists() {
                 let theme_config = crate::ui::themes::ThemePresets::get_theme(theme_name);
-                let content = toml::to_string_pretty(&theme_config)?;
+                let content =
+                    with_directive(SchemaKind::Theme, toml::to_string_pretty(&theme_config)?);
                 fs::write(&theme_path, content)?;
                 println!("Created theme file: {}", theme_path.display());
                 created_any = true;
@@ -55,13 +92,16 @@ impl ConfigLoader {
         Ok(())
     }
 
-    /// Get the themes directory path (~/.claude/ccline/themes/)
+    /// Find the nearest `.ccline.toml` in `dir` or one of its parents
+    pub fn find_project_config(dir: &Path) -> Option<PathBuf> {
+        dir.ancestors()
+            .map(|dir| dir.join(PROJECT_CONFIG_FILE))
+            .find(|path| path.is_file())
+    }
+
+    /// Get the themes directory path (`themes/` in the config directory)
     pub fn get_themes_path() -> PathBuf {
-        if let Some(home) = dirs::home_dir() {
-            home.join(".claude").join("ccline").join("themes")
-        } else {
-            PathBuf::from(".claude/ccline/themes")
-        }
+        super::paths::themes_dir()
     }
 
     /// Ensure themes directory exists and has built-in themes (silent mode)
@@ -94,7 +134,8 @@ impl ConfigLoader {
 
             if !theme_path.exists() {
                 let theme_config = crate::ui::themes::ThemePresets::get_theme(theme_name);
-                let content = toml::to_string_pretty(&theme_config)?;
+                let content =
+                    with_directive(SchemaKind::Theme, toml::to_string_pretty(&theme_config)?);
                 fs::write(&theme_path, content)?;
             }
         }
@@ -106,19 +147,56 @@ impl ConfigLoader {
 impl Config {
     /// Load configuration from default location
     pub fn load() -> Result<Config, Box<dyn std::error::Error>> {
+        Self::from_layers(&[Self::global_layer()?])
+    }
+
+    /// Load configuration with the nearest `.ccline.toml` above `dir` merged
+    /// over the global config
+    pub fn load_for_dir(dir: &Path) -> Result<Config, Box<dyn std::error::Error>> {
+        let mut layers = vec![Self::global_layer()?];
+        layers.extend(Self::project_layer(dir)?);
+
+        Self::from_layers(&layers)
+    }
+
+    /// The global config file, or the defaults when there is none. A file
+    /// written by an older version is migrated in memory; only `migrate`
+    /// rewrites it.
+    pub fn global_layer() -> Result<ConfigLayer, Box<dyn std::error::Error>> {
         // Ensure themes directory exists and has built-in themes
         ConfigLoader::ensure_themes_exist();
 
         let config_path = Self::get_config_path();
-
-        if !config_path.exists() {
-            return Ok(Config::default());
+        if config_path.exists() {
+            let mut layer = ConfigLayer::from_file(&config_path)?;
+            if let Some(migrated) = super::migrate::migrate(layer.value.clone())? {
+                layer.value = migrated.value;
+            }
+            Ok(layer)
+        } else {
+            ConfigLayer::from_config("default", &Config::default())
         }
+    }
+
+    /// The nearest `.ccline.toml` above `dir`, if any
+    pub fn project_layer(dir: &Path) -> Result<Option<ConfigLayer>, Box<dyn std::error::Error>> {
+        ConfigLoader::find_project_config(dir)
+            .map(|path| ConfigLayer::from_file(&path))
+            .transpose()
+    }
 
-        let content = fs::read_to_string(config_path)?;
-        let mut config: Config = toml::from_str(&content)?;
-        config.ensure_theme_segments();
-        Ok(config)
+    /// Deep-merge the layers in order (segments by id, then style and options
+    /// key by key) into a config
+    pub fn from_layers(layers: &[ConfigLayer]) -> Result<Config, Box<dyn std::error::Error>> {
+        let mut layers = layers.iter();
+        let mut value = layers
+            .next()
+            .map(|layer| layer.value.clone())
+            .ok_or("No configuration to load")?;
+        for layer in layers {
+            super::merge::merge_values(&mut value, layer.value.clone());
+        }
+        Ok(value.try_into()?)
     }
 
     /// Save configuration to default location
@@ -130,18 +208,19 @@ impl Config {
             fs::create_dir_all(parent)?;
         }
 
-        let content = toml::to_string_pretty(self)?;
-        fs::write(config_path, content)?;
+        let config = Config {
+            version: super::migrate::CURRENT_VERSION,
+            ..self.clone()
+        };
+        let content = with_directive(SchemaKind::Config, toml::to_string_pretty(&config)?);
+        write_atomic(&config_path, &content)?;
         Ok(())
     }
 
-    /// Get the default config file path (~/.claude/ccline/config.toml)
-    fn get_config_path() -> PathBuf {
-        if let Some(home) = dirs::home_dir() {
-            home.join(".claude").join("ccline").join("config.toml")
-        } else {
-            PathBuf::from(".claude/ccline/config.toml")
-        }
+    /// Get the config file path (`--config`, `--profile`, or config.toml in
+    /// the config directory)
+    pub fn get_config_path() -> PathBuf {
+        super::paths::config_file()
     }
 
     /// Initialize config directory and create default config
@@ -153,9 +232,17 @@ impl Config {
             fs::create_dir_all(parent)?;
         }
 
+        // Schemas first, so the files below get a `#:schema` directive
+        super::schema::write_schemas()?;
+
         // Initialize themes directory and built-in themes
         ConfigLoader::init_themes()?;
 
+        let models_path = super::paths::models_file();
+        if !models_path.exists() {
+            super::models::ModelConfig::create_default_file(&models_path)?;
+        }
+
         // Create default config if it doesn't exist
         if !config_path.exists() {
             let default_config = Config::default();
@@ -168,22 +255,20 @@ impl Config {
         Ok(())
     }
 
-    /// Validate configuration
+    /// Validate configuration, failing on the errors among `diagnostics`
     pub fn check(&self) -> Result<(), Box<dyn std::error::Error>> {
-        // Basic validation
-        if self.segments.is_empty() {
-            return Err("No segments configured".into());
-        }
-
-        // Validate segment IDs are unique
-        let mut seen_ids = std::collections::HashSet::new();
-        for segment in &self.segments {
-            if !seen_ids.insert(segment.id) {
-                return Err(format!("Duplicate segment ID: {:?}", segment.id).into());
-            }
+        let errors: Vec<String> = self
+            .diagnostics()
+            .iter()
+            .filter(|d| d.is_error())
+            .map(|d| d.to_string())
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("\n").into())
         }
-
-        Ok(())
     }
 
     /// Print configuration as TOML
@@ -192,4 +277,60 @@ impl Config {
         println!("{}", content);
         Ok(())
     }
+
+    /// Print every value with the layer it came from, like `git config --show-origin`.
+    /// Values no layer sets are defaults of fields missing from the files.
+    pub fn print_with_origins(
+        &self,
+        layers: &[ConfigLayer],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let layer_keys: Vec<(&str, Vec<String>)> = layers
+            .iter()
+            .map(|layer| {
+                let keys = super::merge::flatten(&layer.value)
+                    .into_iter()
+                    .map(|(key, _)| key)
+                    .collect();
+                (layer.origin.as_str(), keys)
+            })
+            .collect();
+
+        let entries: Vec<(&str, String, toml::Value)> =
+            super::merge::flatten(&toml::Value::try_from(self)?)
+                .into_iter()
+                .map(|(key, value)| {
+                    let origin = layer_keys
+                        .iter()
+                        .rev()
+                        .find(|(_, keys)| keys.contains(&key))
+                        .map(|(origin, _)| *origin)
+                        .unwrap_or("default");
+                    (origin, key, value)
+                })
+                .collect();
+
+        let width = entries
+            .iter()
+            .map(|(origin, _, _)| origin.len())
+            .max()
+            .unwrap_or(0);
+        for (origin, key, value) in entries {
+            println!("{:width$}  {} = {}", origin, key, value, width = width);
+        }
+        Ok(())
+    }
+}
+
+/// Write through a temporary file in the same directory and rename it over
+/// `path`, so a statusline render never reads a half-written config
+pub fn write_atomic(path: &Path, contents: &str) -> std::io::Result<()> {
+    let mut name = std::ffi::OsString::from(".");
+    name.push(path.file_name().unwrap_or_default());
+    name.push(format!(".{}.tmp", std::process::id()));
+    let temp = path.with_file_name(name);
+
+    fs::write(&temp, contents)?;
+    fs::rename(&temp, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp);
+    })
 }
@@ -0,0 +1,101 @@
+// Deep-merge helpers for layered configuration (theme inheritance, overrides)
+
+use toml::Value;
+
+/// Keys that identify an `AnsiColor` table; such tables are replaced, never merged,
+/// so `{ c16 = 1 }` over `{ r = .., g = .., b = .. }` does not yield a mixed color
+const COLOR_KEYS: [&str; 5] = ["c16", "c256", "r", "g", "b"];
+
+/// Merge `overlay` into `base` in place.
+///
+/// Tables are merged key by key, `segments` arrays are merged element-wise by
+/// segment `id` (unknown ids are appended), and every other value in `overlay`
+/// replaces the one in `base`.
+pub fn merge_values(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Table(base_table), Value::Table(overlay_table)) => {
+            for (key, overlay_value) in overlay_table {
+                match base_table.get_mut(&key) {
+                    Some(base_value) if key == "segments" => {
+                        merge_segments(base_value, overlay_value)
+                    }
+                    Some(base_value) if is_mergeable_table(base_value, &overlay_value) => {
+                        merge_values(base_value, overlay_value)
+                    }
+                    _ => {
+                        base_table.insert(key, overlay_value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+fn is_mergeable_table(base: &Value, overlay: &Value) -> bool {
+    match (base, overlay) {
+        (Value::Table(_), Value::Table(overlay_table)) => !overlay_table
+            .keys()
+            .any(|key| COLOR_KEYS.contains(&key.as_str())),
+        _ => false,
+    }
+}
+
+fn merge_segments(base: &mut Value, overlay: Value) {
+    let (Value::Array(base_segments), Value::Array(overlay_segments)) = (&mut *base, &overlay)
+    else {
+        *base = overlay;
+        return;
+    };
+
+    for overlay_segment in overlay_segments.iter().cloned() {
+        let id = overlay_segment.get("id").and_then(|v| v.as_str());
+        let existing = id.and_then(|id| {
+            base_segments
+                .iter_mut()
+                .find(|segment| segment.get("id").and_then(|v| v.as_str()) == Some(id))
+        });
+
+        match existing {
+            Some(base_segment) => merge_values(base_segment, overlay_segment),
+            None => base_segments.push(overlay_segment),
+        }
+    }
+}
+
+/// Flatten a config value into dotted keys and leaf values, in the same shape
+/// `merge_values` merges: segments are keyed by id (`segments.git.options.show_sha`)
+/// and colors are kept whole
+pub fn flatten(value: &Value) -> Vec<(String, Value)> {
+    let mut entries = Vec::new();
+    flatten_into(value, String::new(), &mut entries);
+    entries
+}
+
+fn flatten_into(value: &Value, prefix: String, entries: &mut Vec<(String, Value)>) {
+    let join = |key: &str| {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", prefix, key)
+        }
+    };
+
+    match value {
+        Value::Table(table) if !table.keys().any(|key| COLOR_KEYS.contains(&key.as_str())) => {
+            for (key, value) in table {
+                match value {
+                    Value::Array(segments) if key == "segments" && prefix.is_empty() => {
+                        for segment in segments {
+                            if let Some(id) = segment.get("id").and_then(|v| v.as_str()) {
+                                flatten_into(segment, join(&format!("segments.{}", id)), entries);
+                            }
+                        }
+                    }
+                    value => flatten_into(value, join(key), entries),
+                }
+            }
+        }
+        value => entries.push((prefix, value.clone())),
+    }
+}
@@ -0,0 +1,215 @@
+//! Upgrades of config.toml written by older versions.
+//!
+//! Every change to the file format that old files need to follow gets a step
+//! in `MIGRATIONS`, and `CURRENT_VERSION` moves to its `to` version. Steps work
+//! on the raw TOML so they can read fields the current `Config` no longer has.
+
+use super::types::Config;
+use std::path::{Path, PathBuf};
+use toml::Value;
+
+/// Version written to new config files
+pub const CURRENT_VERSION: u32 = 1;
+
+struct Migration {
+    /// Version of the config after this step
+    to: u32,
+    /// Apply the step, describing each change made
+    apply: fn(&mut Value, &mut Vec<String>),
+}
+
+const MIGRATIONS: &[Migration] = &[Migration {
+    to: 1,
+    apply: explicit_segments,
+}];
+
+/// A config brought up to `CURRENT_VERSION`
+pub struct Migrated {
+    pub from: u32,
+    pub value: Value,
+    pub changes: Vec<String>,
+}
+
+/// The `version` of a config file; files without one predate versioning
+pub fn config_version(value: &Value) -> u32 {
+    value
+        .get("version")
+        .and_then(|v| v.as_integer())
+        .and_then(|v| u32::try_from(v).ok())
+        .unwrap_or(0)
+}
+
+/// Run every step newer than the config's version. Returns `None` when the
+/// config is already current.
+pub fn migrate(mut value: Value) -> Result<Option<Migrated>, Box<dyn std::error::Error>> {
+    let from = config_version(&value);
+    if from > CURRENT_VERSION {
+        return Err(format!(
+            "config version {} is newer than this cubenceline supports ({}); please update",
+            from, CURRENT_VERSION
+        )
+        .into());
+    }
+    if from == CURRENT_VERSION {
+        return Ok(None);
+    }
+
+    let mut changes = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.to > from) {
+        (migration.apply)(&mut value, &mut changes);
+        set_version(&mut value, migration.to);
+    }
+
+    Ok(Some(Migrated {
+        from,
+        value,
+        changes,
+    }))
+}
+
+/// Where the original of a migrated config file is kept, e.g. `config.toml.v0.bak`
+pub fn backup_path(path: &Path, from: u32) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".v{}.bak", from));
+    path.with_file_name(name)
+}
+
+/// The file contents a migrated config is written as
+pub fn render(migrated: &Migrated) -> Result<String, Box<dyn std::error::Error>> {
+    let config: Config = migrated.value.clone().try_into()?;
+    Ok(super::schema::with_directive(
+        super::schema::SchemaKind::Config,
+        toml::to_string_pretty(&config)?,
+    ))
+}
+
+/// Migrate a config file in place, keeping the original next to it. Returns
+/// `None` when the file is already current.
+pub fn migrate_file(path: &Path) -> Result<Option<Migrated>, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)?;
+    let value: Value = toml::from_str(&content)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+
+    let Some(migrated) = migrate(value)? else {
+        return Ok(None);
+    };
+    let new_content = render(&migrated)?;
+
+    // Never overwrite an earlier backup of the same version
+    let backup = backup_path(path, migrated.from);
+    if !backup.exists() {
+        std::fs::write(&backup, &content)?;
+    }
+    super::loader::write_atomic(path, &new_content)?;
+    Ok(Some(migrated))
+}
+
+/// Unified diff between the current file and its migrated contents
+pub fn diff(path: &Path, old: &str, new: &str) -> String {
+    let name = path.display().to_string();
+    similar::TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(3)
+        .header(&name, &format!("{} (migrated)", name))
+        .to_string()
+}
+
+fn set_version(value: &mut Value, version: u32) {
+    if let Some(table) = value.as_table_mut() {
+        table.insert("version".to_string(), Value::Integer(version.into()));
+    }
+}
+
+/// v1: segments are listed explicitly instead of being completed on every load.
+///
+/// - the legacy `[segments]` table of booleans becomes the segment list
+/// - segment IDs added since the file was written are appended
+/// - options added to existing segments since then are filled in
+fn explicit_segments(value: &mut Value, changes: &mut Vec<String>) {
+    let Some(table) = value.as_table_mut() else {
+        return;
+    };
+    let theme = table
+        .get("theme")
+        .and_then(|v| v.as_str())
+        .unwrap_or("default")
+        .to_string();
+    let preset = crate::ui::themes::ThemePresets::get_theme(&theme);
+    let Ok(Value::Array(preset_segments)) = Value::try_from(&preset.segments) else {
+        return;
+    };
+
+    // Oldest configs only switched a few segments on and off
+    if let Some(Value::Table(legacy)) = table.get("segments") {
+        let segments = preset_segments
+            .iter()
+            .cloned()
+            .map(|mut segment| {
+                let id = segment_id(&segment).unwrap_or_default();
+                if let (Some(Value::Boolean(enabled)), Some(segment)) =
+                    (legacy.get(&id), segment.as_table_mut())
+                {
+                    segment.insert("enabled".to_string(), Value::Boolean(*enabled));
+                }
+                segment
+            })
+            .collect();
+        table.insert("segments".to_string(), Value::Array(segments));
+        changes.push("converted the legacy [segments] table into a segment list".to_string());
+        return;
+    }
+
+    let Some(Value::Array(segments)) = table.get_mut("segments") else {
+        return;
+    };
+
+    for preset_segment in &preset_segments {
+        let Some(id) = segment_id(preset_segment) else {
+            continue;
+        };
+
+        match segments
+            .iter_mut()
+            .find(|segment| segment_id(segment).as_deref() == Some(id.as_str()))
+        {
+            Some(segment) => {
+                let Some(preset_options) = preset_segment.get("options").and_then(|o| o.as_table())
+                else {
+                    continue;
+                };
+                let Some(segment) = segment.as_table_mut() else {
+                    continue;
+                };
+                let options = segment
+                    .entry("options")
+                    .or_insert_with(|| Value::Table(Default::default()));
+                let Some(options) = options.as_table_mut() else {
+                    continue;
+                };
+
+                let mut keys: Vec<&String> = preset_options.keys().collect();
+                keys.sort();
+                for key in keys {
+                    if !options.contains_key(key) {
+                        options.insert(key.clone(), preset_options[key].clone());
+                        changes.push(format!(
+                            "added option {}.{} = {}",
+                            id, key, preset_options[key]
+                        ));
+                    }
+                }
+            }
+            None => {
+                segments.push(preset_segment.clone());
+                changes.push(format!("added segment '{}' from theme '{}'", id, theme));
+            }
+        }
+    }
+}
+
+fn segment_id(segment: &Value) -> Option<String> {
+    segment
+        .get("id")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+}
@@ -1,8 +1,15 @@
 pub mod defaults;
+pub mod diagnostics;
 pub mod loader;
+pub mod merge;
+pub mod migrate;
 pub mod models;
+pub mod options;
+pub mod paths;
+pub mod schema;
 pub mod types;
 
-pub use loader::ConfigLoader;
+pub use diagnostics::{ConfigReport, Diagnostic, Severity};
+pub use loader::{ConfigLayer, ConfigLoader, PROJECT_CONFIG_FILE};
 pub use models::*;
 pub use types::*;
@@ -1,14 +1,15 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ModelConfig {
     #[serde(rename = "models")]
     pub model_entries: Vec<ModelEntry>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ModelEntry {
     pub pattern: String,
     pub display_name: String,
@@ -28,16 +29,14 @@ impl ModelConfig {
         let mut model_config = Self::default();
 
         // First, try to create default models.toml if it doesn't exist
-        if let Some(home_dir) = dirs::home_dir() {
-            let user_models_path = home_dir.join(".claude").join("ccline").join("models.toml");
-            if !user_models_path.exists() {
-                let _ = Self::create_default_file(&user_models_path);
-            }
+        let user_models_path = super::paths::models_file();
+        if !user_models_path.exists() {
+            let _ = Self::create_default_file(&user_models_path);
         }
 
         // Try loading from user config directory first, then local
         let config_paths = [
-            dirs::home_dir().map(|d| d.join(".claude").join("ccline").join("models.toml")),
+            Some(user_models_path),
             Some(Path::new("models.toml").to_path_buf()),
         ];
 
@@ -101,7 +100,7 @@ impl ModelConfig {
         let template_content = format!(
             "# CubenceLine Model Configuration\n\
              # This file defines model display names and context limits for different LLM models\n\
-             # File location: ~/.claude/ccline/models.toml\n\
+             # File location: models.toml in the ccline config directory (~/.claude/ccline by default)\n\
              \n\
              {}\n\
              \n\
@@ -122,6 +121,8 @@ impl ModelConfig {
             fs::create_dir_all(parent)?;
         }
 
+        let template_content =
+            super::schema::with_directive(super::schema::SchemaKind::Models, template_content);
         fs::write(path, template_content)?;
         Ok(())
     }
@@ -0,0 +1,167 @@
+//! The options each segment understands, with their types and defaults.
+//! Used to validate `[segments.options]` tables and to document them.
+
+use super::types::SegmentId;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OptionKind {
+    Bool {
+        default: bool,
+    },
+    /// A non-negative integer
+    Integer {
+        default: u64,
+    },
+    String {
+        default: &'static str,
+    },
+    /// One of a fixed set of strings, compared case-insensitively
+    Choice {
+        choices: &'static [&'static str],
+        default: &'static str,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OptionSpec {
+    pub key: &'static str,
+    pub kind: OptionKind,
+    pub description: &'static str,
+}
+
+impl OptionSpec {
+    pub fn default_value(&self) -> serde_json::Value {
+        match self.kind {
+            OptionKind::Bool { default } => default.into(),
+            OptionKind::Integer { default } => default.into(),
+            OptionKind::String { default } | OptionKind::Choice { default, .. } => default.into(),
+        }
+    }
+
+    /// Human-readable type, e.g. for diagnostics
+    pub fn type_name(&self) -> String {
+        match self.kind {
+            OptionKind::Bool { .. } => "a boolean".to_string(),
+            OptionKind::Integer { .. } => "a non-negative integer".to_string(),
+            OptionKind::String { .. } => "a string".to_string(),
+            OptionKind::Choice { choices, .. } => format!("one of {}", choices.join(", ")),
+        }
+    }
+
+    /// Check that a configured value has the expected type
+    pub fn validate(&self, value: &serde_json::Value) -> Result<(), String> {
+        let valid = match self.kind {
+            OptionKind::Bool { .. } => value.is_boolean(),
+            OptionKind::Integer { .. } => value.is_u64(),
+            OptionKind::String { .. } => value.is_string(),
+            OptionKind::Choice { choices, .. } => value
+                .as_str()
+                .is_some_and(|value| choices.iter().any(|c| c.eq_ignore_ascii_case(value))),
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("expected {}, got {}", self.type_name(), value))
+        }
+    }
+}
+
+const CACHE_DURATION_HELP: &str = "Seconds to reuse the last result before refreshing it";
+const TIMEOUT_HELP: &str = "Request timeout in seconds";
+
+const DIRECTORY_OPTIONS: &[OptionSpec] = &[
+    OptionSpec {
+        key: "mode",
+        kind: OptionKind::Choice {
+            choices: &["name", "full", "home", "project", "fish"],
+            default: "name",
+        },
+        description: "How the working directory is shown",
+    },
+    OptionSpec {
+        key: "max_components",
+        kind: OptionKind::Integer { default: 0 },
+        description: "Keep only the last N path components (0 keeps all)",
+    },
+];
+
+const GIT_OPTIONS: &[OptionSpec] = &[OptionSpec {
+    key: "show_sha",
+    kind: OptionKind::Bool { default: false },
+    description: "Show the short commit SHA",
+}];
+
+const USAGE_OPTIONS: &[OptionSpec] = &[
+    OptionSpec {
+        key: "api_base_url",
+        kind: OptionKind::String {
+            default: "https://api.anthropic.com",
+        },
+        description: "Informational; ANTHROPIC_BASE_URL from settings.json is used",
+    },
+    OptionSpec {
+        key: "cache_duration",
+        kind: OptionKind::Integer { default: 300 },
+        description: CACHE_DURATION_HELP,
+    },
+    OptionSpec {
+        key: "timeout",
+        kind: OptionKind::Integer { default: 2 },
+        description: TIMEOUT_HELP,
+    },
+];
+
+const CUBENCE_OPTIONS: &[OptionSpec] = &[
+    OptionSpec {
+        key: "api_url",
+        kind: OptionKind::String {
+            default: "https://cubence.com/api/v1/user/subscription-info",
+        },
+        description: "Informational; ANTHROPIC_BASE_URL from settings.json is used",
+    },
+    OptionSpec {
+        key: "cache_duration",
+        kind: OptionKind::Integer { default: 180 },
+        description: CACHE_DURATION_HELP,
+    },
+    OptionSpec {
+        key: "timeout",
+        kind: OptionKind::Integer { default: 2 },
+        description: TIMEOUT_HELP,
+    },
+];
+
+const LANGUAGE_OPTIONS: &[OptionSpec] = &[
+    OptionSpec {
+        key: "cache_duration",
+        kind: OptionKind::Integer { default: 300 },
+        description: CACHE_DURATION_HELP,
+    },
+    OptionSpec {
+        key: "show_version",
+        kind: OptionKind::Bool { default: true },
+        description: "Show the toolchain version next to the language",
+    },
+];
+
+/// Options understood by a segment
+pub fn option_specs(id: SegmentId) -> &'static [OptionSpec] {
+    match id {
+        SegmentId::Directory => DIRECTORY_OPTIONS,
+        SegmentId::Git => GIT_OPTIONS,
+        SegmentId::Usage => USAGE_OPTIONS,
+        SegmentId::Cubence => CUBENCE_OPTIONS,
+        SegmentId::Language => LANGUAGE_OPTIONS,
+        SegmentId::Model
+        | SegmentId::ContextWindow
+        | SegmentId::Cost
+        | SegmentId::Session
+        | SegmentId::OutputStyle
+        | SegmentId::Update => &[],
+    }
+}
+
+pub fn option_spec(id: SegmentId, key: &str) -> Option<&'static OptionSpec> {
+    option_specs(id).iter().find(|spec| spec.key == key)
+}
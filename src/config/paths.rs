@@ -0,0 +1,79 @@
+//! Where CubenceLine keeps its files.
+//!
+//! The config directory is, in order:
+//! 1. `$CCLINE_CONFIG_DIR`
+//! 2. `$XDG_CONFIG_HOME/ccline`, unless `~/.claude/ccline/config.toml` exists
+//! 3. `~/.claude/ccline`
+//!
+//! Caches and the update state go to `$XDG_CACHE_HOME/ccline` when it is set,
+//! and to the config directory otherwise. `--config=<file>` and
+//! `--profile <name>` only change which config file is read and written.
+
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+static CONFIG_FILE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Use this config file instead of `config.toml` in the config directory.
+/// Only the first call has an effect; `main` makes it before anything is loaded.
+pub fn set_config_file(path: impl Into<PathBuf>) {
+    let _ = CONFIG_FILE.set(path.into());
+}
+
+/// Config file of a named profile (`<config dir>/profiles/<name>.toml`)
+pub fn profile_file(name: &str) -> PathBuf {
+    config_dir().join("profiles").join(format!("{}.toml", name))
+}
+
+/// The config file in use
+pub fn config_file() -> PathBuf {
+    CONFIG_FILE
+        .get()
+        .cloned()
+        .unwrap_or_else(|| config_dir().join("config.toml"))
+}
+
+pub fn config_dir() -> PathBuf {
+    if let Some(dir) = env_dir("CCLINE_CONFIG_DIR") {
+        return dir;
+    }
+
+    let legacy = legacy_dir();
+    if let Some(xdg) = env_dir("XDG_CONFIG_HOME") {
+        // Existing installs keep working until the user moves their files.
+        // The directory alone doesn't count: installers put the binary there.
+        if !legacy.join("config.toml").exists() {
+            return xdg.join("ccline");
+        }
+    }
+    legacy
+}
+
+pub fn cache_dir() -> PathBuf {
+    match env_dir("XDG_CACHE_HOME") {
+        Some(xdg) => xdg.join("ccline"),
+        None => config_dir(),
+    }
+}
+
+pub fn themes_dir() -> PathBuf {
+    config_dir().join("themes")
+}
+
+pub fn models_file() -> PathBuf {
+    config_dir().join("models.toml")
+}
+
+fn legacy_dir() -> PathBuf {
+    match dirs::home_dir() {
+        Some(home) => home.join(".claude").join("ccline"),
+        None => PathBuf::from(".claude/ccline"),
+    }
+}
+
+/// An environment variable holding a non-empty absolute path
+fn env_dir(name: &str) -> Option<PathBuf> {
+    std::env::var_os(name)
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+}
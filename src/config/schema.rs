@@ -0,0 +1,200 @@
+//! JSON Schema for the files CubenceLine reads, for editor completion and
+//! validation (Taplo, Even Better TOML, ...).
+//!
+//! The schemas are derived from the config types; segment options come from
+//! the registry in `options`, one `if`/`then` branch per segment ID.
+
+use super::migrate::CURRENT_VERSION;
+use super::models::ModelConfig;
+use super::options::{option_specs, OptionKind, OptionSpec};
+use super::types::{Config, SegmentId};
+use serde_json::{json, Map, Value};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SchemaKind {
+    /// config.toml and profiles
+    Config,
+    /// Per-project `.ccline.toml` overrides
+    Project,
+    /// Theme files, which may `extends` another theme
+    Theme,
+    /// models.toml
+    Models,
+}
+
+impl SchemaKind {
+    pub const ALL: [SchemaKind; 4] = [
+        SchemaKind::Config,
+        SchemaKind::Project,
+        SchemaKind::Theme,
+        SchemaKind::Models,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SchemaKind::Config => "config",
+            SchemaKind::Project => "project",
+            SchemaKind::Theme => "theme",
+            SchemaKind::Models => "models",
+        }
+    }
+
+    /// Where `--init` writes this schema (`schemas/<kind>.schema.json`)
+    pub fn path(&self) -> PathBuf {
+        super::paths::config_dir()
+            .join("schemas")
+            .join(format!("{}.schema.json", self.as_str()))
+    }
+}
+
+const SEGMENT_IDS: [SegmentId; 11] = [
+    SegmentId::Model,
+    SegmentId::Directory,
+    SegmentId::Git,
+    SegmentId::ContextWindow,
+    SegmentId::Usage,
+    SegmentId::Cubence,
+    SegmentId::Cost,
+    SegmentId::Session,
+    SegmentId::OutputStyle,
+    SegmentId::Update,
+    SegmentId::Language,
+];
+
+pub fn schema(kind: SchemaKind) -> Value {
+    match kind {
+        SchemaKind::Config => config_schema(),
+        SchemaKind::Project => {
+            let mut schema = partial_config_schema();
+            set_title(&mut schema, "CubenceLine project overrides (.ccline.toml)");
+            schema
+        }
+        SchemaKind::Theme => {
+            let mut schema = partial_config_schema();
+            set_title(&mut schema, "CubenceLine theme");
+            schema["properties"]["extends"] = json!({
+                "description": "Theme whose values this file overrides",
+                "type": "string",
+            });
+            schema
+        }
+        SchemaKind::Models => {
+            let mut schema = schemars::schema_for!(ModelConfig).to_value();
+            set_title(&mut schema, "CubenceLine models.toml");
+            schema
+        }
+    }
+}
+
+/// Write every schema to its `path()`
+pub fn write_schemas() -> Result<(), Box<dyn std::error::Error>> {
+    for kind in SchemaKind::ALL {
+        let path = kind.path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(&schema(kind))? + "\n")?;
+    }
+    Ok(())
+}
+
+/// The `#:schema` line for files of this kind, once `--init` has written the
+/// schema; `None` before that
+pub fn directive(kind: SchemaKind) -> Option<String> {
+    let path = kind.path();
+    path.exists()
+        .then(|| format!("#:schema {}\n", path.display()))
+}
+
+/// Prefix `content` with the `#:schema` line for its kind, if there is one
+pub fn with_directive(kind: SchemaKind, content: String) -> String {
+    match directive(kind) {
+        Some(directive) if !content.starts_with("#:schema") => directive + &content,
+        _ => content,
+    }
+}
+
+fn config_schema() -> Value {
+    let mut schema = schemars::schema_for!(Config).to_value();
+    set_title(&mut schema, "CubenceLine config.toml");
+    schema["properties"]["version"]["maximum"] = CURRENT_VERSION.into();
+
+    if let Some(segment) = schema
+        .pointer_mut("/$defs/SegmentConfig")
+        .and_then(Value::as_object_mut)
+    {
+        segment.insert(
+            "allOf".to_string(),
+            SEGMENT_IDS.iter().map(|id| options_branch(*id)).collect(),
+        );
+    }
+    schema
+}
+
+/// `if id == <id> then options follow <id>'s specs`
+fn options_branch(id: SegmentId) -> Value {
+    let properties: Map<String, Value> = option_specs(id)
+        .iter()
+        .map(|spec| (spec.key.to_string(), option_schema(spec)))
+        .collect();
+
+    json!({
+        "if": { "properties": { "id": { "const": id } } },
+        "then": {
+            "properties": {
+                "options": {
+                    "type": "object",
+                    "properties": properties,
+                    "additionalProperties": false,
+                }
+            }
+        }
+    })
+}
+
+fn option_schema(spec: &OptionSpec) -> Value {
+    let mut schema = match spec.kind {
+        OptionKind::Bool { .. } => json!({ "type": "boolean" }),
+        OptionKind::Integer { .. } => json!({ "type": "integer", "minimum": 0 }),
+        OptionKind::String { .. } => json!({ "type": "string" }),
+        OptionKind::Choice { choices, .. } => json!({ "type": "string", "enum": choices }),
+    };
+    schema["description"] = spec.description.into();
+    schema["default"] = spec.default_value();
+    schema
+}
+
+fn set_title(schema: &mut Value, title: &str) {
+    schema["title"] = title.into();
+}
+
+/// The config schema for files that are merged over another config: only
+/// segment IDs are required, as segments are matched by them. Colors are
+/// replaced whole, so they keep their required components.
+fn partial_config_schema() -> Value {
+    let mut schema = config_schema();
+    schema
+        .as_object_mut()
+        .map(|schema| schema.remove("required"));
+    if let Some(defs) = schema.get_mut("$defs").and_then(Value::as_object_mut) {
+        for (name, def) in defs.iter_mut() {
+            if name != "AnsiColor" {
+                strip_required(def);
+            }
+        }
+    }
+    schema["$defs"]["SegmentConfig"]["required"] = json!(["id"]);
+    schema
+}
+
+fn strip_required(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.remove("required");
+            map.values_mut().for_each(strip_required);
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_required),
+        _ => {}
+    }
+}
@@ -1,23 +1,56 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 // Main config structure
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Config {
+    /// Format version of the file; older files are upgraded by `migrate`
+    #[serde(default)]
+    pub version: u32,
     pub style: StyleConfig,
     pub segments: Vec<SegmentConfig>,
     pub theme: String,
+    /// Configurator key bindings over the defaults, e.g. `"ctrl+t" = "next_theme"`
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_sorted"
+    )]
+    pub keymap: HashMap<String, String>,
 }
 
 // Default implementation moved to ui/themes/presets.rs
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct StyleConfig {
     pub mode: StyleMode,
     pub separator: String,
+    #[serde(default)]
+    pub color_support: ColorSupport,
+    /// Glyph drawn before the first segment, colored with its background
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_cap: Option<String>,
+    /// Glyph drawn after the last segment, colored with its background
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_cap: Option<String>,
+    /// Powerline separator between segments sharing a background; derived
+    /// from `separator` when unset (e.g. `\u{e0b1}` for `\u{e0b0}`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thin_separator: Option<String>,
+    /// Character repeated between the left and right segment groups
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fill: Option<String>,
+    /// Statusline width used to right-align segments; detected from `COLUMNS`
+    /// or the terminal when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u16>,
+    /// Make segments with a link target clickable (OSC 8 hyperlinks)
+    #[serde(default)]
+    pub hyperlinks: bool,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum StyleMode {
     Plain,
@@ -25,43 +58,214 @@ pub enum StyleMode {
     Powerline,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Which side of the statusline a segment is drawn on
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SegmentAlign {
+    #[default]
+    Left,
+    /// Pushed to the right edge of the terminal
+    Right,
+}
+
+impl SegmentAlign {
+    pub fn is_left(&self) -> bool {
+        *self == SegmentAlign::Left
+    }
+}
+
+/// Colors the terminal can display; richer colors are downsampled to fit
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorSupport {
+    /// Detect from `NO_COLOR`, `COLORTERM` and `TERM`
+    #[default]
+    Auto,
+    Truecolor,
+    #[serde(rename = "256")]
+    Ansi256,
+    #[serde(rename = "16")]
+    Ansi16,
+    /// Plain text without any escape sequences
+    None,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SegmentConfig {
     pub id: SegmentId,
     pub enabled: bool,
     pub icon: IconConfig,
     pub colors: ColorConfig,
     pub styles: TextStyleConfig,
+    /// Separator drawn after this segment, overriding `style.separator`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub separator: Option<String>,
+    #[serde(default, skip_serializing_if = "SegmentAlign::is_left")]
+    pub align: SegmentAlign,
+    #[serde(serialize_with = "serialize_sorted")]
     pub options: HashMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Write map keys in order so saved files and migration diffs are stable
+fn serialize_sorted<S, V>(map: &HashMap<String, V>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    V: Serialize,
+{
+    serializer.collect_map(map.iter().collect::<std::collections::BTreeMap<_, _>>())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct IconConfig {
     pub plain: String,
     pub nerd_font: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 pub struct ColorConfig {
     pub icon: Option<AnsiColor>,
     pub text: Option<AnsiColor>,
     pub background: Option<AnsiColor>,
+    /// Secondary text color, falling back to `text`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary: Option<AnsiColor>,
+    /// Colors for individual secondary-text tokens, keyed by token prefix (e.g. "+", "✓")
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_sorted"
+    )]
+    pub tokens: HashMap<String, AnsiColor>,
+}
+
+impl ColorConfig {
+    /// Color of a secondary-text token; the longest matching prefix wins
+    pub fn token_color(&self, token: &str) -> Option<&AnsiColor> {
+        self.tokens
+            .iter()
+            .filter(|(prefix, _)| !prefix.is_empty() && token.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, color)| color)
+            .or(self.secondary.as_ref())
+            .or(self.text.as_ref())
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 pub struct TextStyleConfig {
     pub text_bold: bool,
+    /// Attributes for the primary text; `text_bold` is kept for existing configs
+    #[serde(default, skip_serializing_if = "TextAttributes::is_plain")]
+    pub text: TextAttributes,
+    /// Attributes for the secondary text, inheriting the primary text's when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary: Option<TextAttributes>,
+    #[serde(default, skip_serializing_if = "TextAttributes::is_plain")]
+    pub icon: TextAttributes,
+}
+
+impl TextStyleConfig {
+    /// Effective attributes of the primary text
+    pub fn text_attributes(&self) -> TextAttributes {
+        let mut attributes = self.text.clone();
+        attributes.bold |= self.text_bold;
+        attributes
+    }
+
+    /// Effective attributes of the secondary text
+    pub fn secondary_attributes(&self) -> TextAttributes {
+        self.secondary
+            .clone()
+            .unwrap_or_else(|| self.text_attributes())
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(default)]
+pub struct TextAttributes {
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub bold: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub italic: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub dim: bool,
+    #[serde(skip_serializing_if = "UnderlineStyle::is_none")]
+    pub underline: UnderlineStyle,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub underline_color: Option<AnsiColor>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub reverse: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub strikethrough: bool,
+}
+
+impl TextAttributes {
+    pub fn is_plain(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UnderlineStyle {
+    #[default]
+    None,
+    Single,
+    Double,
+    Curly,
+    Dotted,
+    Dashed,
+}
+
+impl UnderlineStyle {
+    pub const ALL: [UnderlineStyle; 6] = [
+        UnderlineStyle::None,
+        UnderlineStyle::Single,
+        UnderlineStyle::Double,
+        UnderlineStyle::Curly,
+        UnderlineStyle::Dotted,
+        UnderlineStyle::Dashed,
+    ];
+
+    pub fn is_none(&self) -> bool {
+        *self == UnderlineStyle::None
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UnderlineStyle::None => "none",
+            UnderlineStyle::Single => "single",
+            UnderlineStyle::Double => "double",
+            UnderlineStyle::Curly => "curly",
+            UnderlineStyle::Dotted => "dotted",
+            UnderlineStyle::Dashed => "dashed",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|s| s == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum AnsiColor {
-    Color16 { c16: u8 },
-    Color256 { c256: u8 },
-    Rgb { r: u8, g: u8, b: u8 },
+    Color16 {
+        #[schemars(range(max = 15))]
+        c16: u8,
+    },
+    Color256 {
+        c256: u8,
+    },
+    Rgb {
+        r: u8,
+        g: u8,
+        b: u8,
+    },
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum SegmentId {
     Model,
@@ -74,15 +278,7 @@ pub enum SegmentId {
     Session,
     OutputStyle,
     Update,
-}
-
-// Legacy compatibility structure
-#[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct SegmentsConfig {
-    pub directory: bool,
-    pub git: bool,
-    pub model: bool,
-    // pub usage: bool,
+    Language,
 }
 
 // Data structures compatible with existing main.rs
@@ -95,6 +291,8 @@ pub struct Model {
 #[derive(Deserialize)]
 pub struct Workspace {
     pub current_dir: String,
+    #[serde(default)]
+    pub project_dir: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -240,40 +438,6 @@ impl NormalizedUsage {
     }
 }
 
-impl Config {
-    pub fn ensure_theme_segments(&mut self) {
-        let theme_preset = crate::ui::themes::ThemePresets::get_theme(&self.theme);
-
-        let mut existing: HashMap<SegmentId, SegmentConfig> = self
-            .segments
-            .drain(..)
-            .map(|segment| (segment.id, segment))
-            .collect();
-
-        let mut normalized = Vec::with_capacity(theme_preset.segments.len());
-
-        for preset_segment in theme_preset.segments {
-            if let Some(mut segment) = existing.remove(&preset_segment.id) {
-                // Backfill any missing options from preset defaults without overwriting user values
-                for (key, value) in preset_segment.options.iter() {
-                    segment
-                        .options
-                        .entry(key.clone())
-                        .or_insert_with(|| value.clone());
-                }
-                normalized.push(segment);
-            } else {
-                normalized.push(preset_segment);
-            }
-        }
-
-        // Preserve any custom segments the user may have added manually
-        normalized.extend(existing.into_values());
-
-        self.segments = normalized;
-    }
-}
-
 impl Config {
     /// Check if current config matches the specified theme preset
     pub fn matches_theme(&self, theme_name: &str) -> bool {
@@ -315,7 +479,13 @@ impl Config {
             && self.color_matches(&current.colors.icon, &preset.colors.icon)
             && self.color_matches(&current.colors.text, &preset.colors.text)
             && self.color_matches(&current.colors.background, &preset.colors.background)
-            && current.styles.text_bold == preset.styles.text_bold
+            && self.color_matches(&current.colors.secondary, &preset.colors.secondary)
+            && current.colors.tokens == preset.colors.tokens
+            && current.separator == preset.separator
+            && current.align == preset.align
+            && current.styles.text_attributes() == preset.styles.text_attributes()
+            && current.styles.secondary == preset.styles.secondary
+            && current.styles.icon == preset.styles.icon
             && current.options == preset.options
     }
 
@@ -0,0 +1,180 @@
+use crate::config::{AnsiColor, ColorSupport};
+
+// xterm's default values for the 16 standard colors
+const ANSI16_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+// Below this chroma a color is treated as grey when picking a 16-color match
+const GREY_CHROMA: u8 = 32;
+
+// Saturated colors at least this bright map to the bright variants (9-14)
+const BRIGHT_VALUE: u8 = 230;
+
+impl ColorSupport {
+    /// Resolve `Auto` from the environment; explicit settings are returned as-is
+    pub fn resolve(self) -> ColorSupport {
+        match self {
+            ColorSupport::Auto => Self::detect(
+                std::env::var("NO_COLOR").ok().as_deref(),
+                std::env::var("COLORTERM").ok().as_deref(),
+                std::env::var("TERM").ok().as_deref(),
+            ),
+            other => other,
+        }
+    }
+
+    /// Detect color support from `NO_COLOR`, `COLORTERM` and `TERM` values
+    pub fn detect(no_color: Option<&str>, colorterm: Option<&str>, term: Option<&str>) -> Self {
+        // https://no-color.org: any non-empty value disables color
+        if no_color.is_some_and(|v| !v.is_empty()) {
+            return ColorSupport::None;
+        }
+
+        if let Some(colorterm) = colorterm {
+            let colorterm = colorterm.to_lowercase();
+            if colorterm == "truecolor" || colorterm == "24bit" {
+                return ColorSupport::Truecolor;
+            }
+        }
+
+        match term {
+            Some("dumb") => ColorSupport::None,
+            Some(term) if term.contains("truecolor") || term.contains("direct") => {
+                ColorSupport::Truecolor
+            }
+            Some(term) if term.contains("256") => ColorSupport::Ansi256,
+            Some(term) if !term.is_empty() => ColorSupport::Ansi16,
+            // No terminal information at all (e.g. Windows): keep colors as configured
+            _ => ColorSupport::Truecolor,
+        }
+    }
+}
+
+/// Convert a color to the closest one the terminal supports, or `None` for no color
+pub fn downsample(color: &AnsiColor, support: ColorSupport) -> Option<AnsiColor> {
+    match (support, color) {
+        (ColorSupport::None, _) => None,
+        (ColorSupport::Auto | ColorSupport::Truecolor, _) => Some(color.clone()),
+        (_, AnsiColor::Color16 { .. }) => Some(color.clone()),
+        (ColorSupport::Ansi256, AnsiColor::Color256 { .. }) => Some(color.clone()),
+        (ColorSupport::Ansi256, AnsiColor::Rgb { r, g, b }) => Some(AnsiColor::Color256 {
+            c256: rgb_to_ansi256(*r, *g, *b),
+        }),
+        (ColorSupport::Ansi16, AnsiColor::Color256 { c256 }) if *c256 < 16 => {
+            Some(AnsiColor::Color16 { c16: *c256 })
+        }
+        (ColorSupport::Ansi16, AnsiColor::Color256 { c256 }) => {
+            let (r, g, b) = ansi256_to_rgb(*c256);
+            Some(AnsiColor::Color16 {
+                c16: rgb_to_ansi16(r, g, b),
+            })
+        }
+        (ColorSupport::Ansi16, AnsiColor::Rgb { r, g, b }) => Some(AnsiColor::Color16 {
+            c16: rgb_to_ansi16(*r, *g, *b),
+        }),
+    }
+}
+
+/// Closest xterm 256-color index, from either the 6x6x6 cube or the grey ramp
+pub fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    let cube_index = |v: u8| {
+        CUBE_LEVELS
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, level)| (**level as i32 - v as i32).abs())
+            .map(|(i, _)| i as u8)
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (cube_index(r), cube_index(g), cube_index(b));
+    let cube = (
+        CUBE_LEVELS[ri as usize],
+        CUBE_LEVELS[gi as usize],
+        CUBE_LEVELS[bi as usize],
+    );
+
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let grey_index = (average.saturating_sub(8) / 10).min(23) as u8;
+    let grey_level = 8 + grey_index * 10;
+
+    if distance((r, g, b), (grey_level, grey_level, grey_level)) < distance((r, g, b), cube) {
+        232 + grey_index
+    } else {
+        16 + 36 * ri + 6 * gi + bi
+    }
+}
+
+/// Closest of the 16 standard colors, matching saturated colors by hue
+pub fn rgb_to_ansi16(r: u8, g: u8, b: u8) -> u8 {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let chroma = max - min;
+
+    if chroma < GREY_CHROMA {
+        return [0u8, 8, 7, 15]
+            .into_iter()
+            .min_by_key(|i| distance((r, g, b), ANSI16_RGB[*i as usize]))
+            .unwrap_or(7);
+    }
+
+    let (rf, gf, bf) = (r as f32, g as f32, b as f32);
+    let delta = chroma as f32;
+    let hue = if max == r {
+        60.0 * ((gf - bf) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((bf - rf) / delta + 2.0)
+    } else {
+        60.0 * ((rf - gf) / delta + 4.0)
+    };
+
+    // red, yellow, green, cyan, blue, magenta sit 60° apart on the hue wheel
+    let base = [1u8, 3, 2, 6, 4, 5][((hue + 30.0) / 60.0) as usize % 6];
+    if max >= BRIGHT_VALUE {
+        base + 8
+    } else {
+        base
+    }
+}
+
+/// RGB value of an xterm 256-color index
+pub fn ansi256_to_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI16_RGB[index as usize],
+        16..=231 => {
+            let i = index - 16;
+            (
+                CUBE_LEVELS[(i / 36) as usize],
+                CUBE_LEVELS[(i / 6 % 6) as usize],
+                CUBE_LEVELS[(i % 6) as usize],
+            )
+        }
+        _ => {
+            let level = 8 + (index - 232) * 10;
+            (level, level, level)
+        }
+    }
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let dr = a.0 as i32 - b.0 as i32;
+    let dg = a.1 as i32 - b.1 as i32;
+    let db = a.2 as i32 - b.2 as i32;
+    (dr * dr + dg * dg + db * db) as u32
+}
@@ -0,0 +1,278 @@
+use crate::config::InputData;
+use std::path::{Path, PathBuf};
+
+/// Names of the fixtures shipped with the binary
+pub const BUILTIN_FIXTURES: [&str; 5] = [
+    "default",
+    "low-context",
+    "near-quota",
+    "api-error",
+    "detached-head",
+];
+
+/// Recorded responses standing in for the network (and git) while rendering.
+/// In offline mode, segments without a recorded response are left out instead
+/// of reaching for the network.
+#[derive(Debug, Clone, Default)]
+pub struct CannedResponses {
+    pub offline: bool,
+    pub usage: Option<serde_json::Value>,
+    pub cubence: Option<serde_json::Value>,
+    pub git: Option<serde_json::Value>,
+    pub update: Option<serde_json::Value>,
+}
+
+impl CannedResponses {
+    /// Read `usage.json`, `cubence.json`, `git.json` and `update.json` from a
+    /// directory; missing files are fine
+    pub fn from_dir(dir: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let read = |name: &str| -> Result<Option<serde_json::Value>, Box<dyn std::error::Error>> {
+            let path = dir.join(name);
+            if !path.exists() {
+                return Ok(None);
+            }
+            let content = std::fs::read_to_string(&path)?;
+            let value =
+                serde_json::from_str(&content).map_err(|e| format!("{}: {}", path.display(), e))?;
+            Ok(Some(value))
+        };
+
+        Ok(Self {
+            offline: true,
+            usage: read("usage.json")?,
+            cubence: read("cubence.json")?,
+            git: read("git.json")?,
+            update: read("update.json")?,
+        })
+    }
+}
+
+impl CannedResponses {
+    /// What the segments last cached, however old, so rendering never waits
+    /// on the network. Git is left to run for real.
+    pub fn from_cache() -> Self {
+        let update_state = crate::config::paths::cache_dir().join(".update_state.json");
+        Self {
+            offline: true,
+            usage: crate::core::segments::UsageSegment::new().cached_response(),
+            cubence: crate::utils::subscription::SubscriptionApiClient::new()
+                .cached_info()
+                .and_then(|info| serde_json::to_value(info).ok()),
+            git: None,
+            update: std::fs::read_to_string(update_state)
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok()),
+        }
+    }
+}
+
+/// A recorded Claude Code payload together with the responses it was rendered against
+pub struct Fixture {
+    pub input: InputData,
+    pub responses: CannedResponses,
+    /// The embedded transcript written out for `input`, removed with the fixture
+    _transcript: Option<TempFile>,
+}
+
+impl Fixture {
+    pub fn new(input: InputData, responses: CannedResponses) -> Self {
+        Self {
+            input,
+            responses,
+            _transcript: None,
+        }
+    }
+
+    /// Load a built-in fixture by name, or a fixture directory by path
+    pub fn load(name_or_dir: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let dir = Path::new(name_or_dir);
+        if dir.is_dir() {
+            return Self::from_dir(dir);
+        }
+
+        Self::builtin(name_or_dir).unwrap_or_else(|| {
+            Err(format!(
+                "Unknown fixture '{}'. Available fixtures: {}",
+                name_or_dir,
+                BUILTIN_FIXTURES.join(", ")
+            )
+            .into())
+        })
+    }
+
+    /// Load `payload.json` and the recorded responses from a fixture directory.
+    /// A relative `transcript_path` is resolved against the directory.
+    pub fn from_dir(dir: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let payload_path = dir.join("payload.json");
+        let payload = std::fs::read_to_string(&payload_path)
+            .map_err(|e| format!("{}: {}", payload_path.display(), e))?;
+        let mut input: InputData = serde_json::from_str(&payload)
+            .map_err(|e| format!("{}: {}", payload_path.display(), e))?;
+
+        if Path::new(&input.transcript_path).is_relative() {
+            input.transcript_path = dir.join(&input.transcript_path).display().to_string();
+        }
+
+        Ok(Self::new(input, CannedResponses::from_dir(dir)?))
+    }
+
+    /// A payload for the current directory and the newest Claude Code
+    /// transcript, rendered against cached API data
+    pub fn live() -> Self {
+        let current_dir = std::env::current_dir()
+            .map(|dir| dir.display().to_string())
+            .unwrap_or_else(|_| ".".to_string());
+        let transcript = newest_transcript();
+        let model_id = transcript.as_deref().and_then(transcript_model);
+
+        let input = InputData {
+            model: crate::config::Model {
+                display_name: model_id.clone().unwrap_or_else(|| "Claude".to_string()),
+                id: model_id.unwrap_or_default(),
+            },
+            workspace: crate::config::Workspace {
+                current_dir,
+                project_dir: None,
+            },
+            transcript_path: transcript
+                .map(|path| path.display().to_string())
+                .unwrap_or_default(),
+            cost: None,
+            output_style: None,
+        };
+
+        Self::new(input, CannedResponses::from_cache())
+    }
+
+    fn builtin(name: &str) -> Option<Result<Self, Box<dyn std::error::Error>>> {
+        let files = builtin_files(name)?;
+        Some(Self::from_embedded(name, &files))
+    }
+
+    fn from_embedded(
+        name: &str,
+        files: &EmbeddedFixture,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut input: InputData = serde_json::from_str(files.payload)?;
+
+        // Transcripts are read from disk, so give the embedded one a home
+        let transcript = write_transcript(name, files.transcript)?;
+        input.transcript_path = transcript.0.display().to_string();
+
+        Ok(Self {
+            input,
+            responses: CannedResponses {
+                offline: true,
+                usage: Some(serde_json::from_str(files.usage)?),
+                cubence: Some(serde_json::from_str(files.cubence)?),
+                git: Some(serde_json::from_str(files.git)?),
+                update: files.update.map(serde_json::from_str).transpose()?,
+            },
+            _transcript: Some(transcript),
+        })
+    }
+}
+
+struct EmbeddedFixture {
+    payload: &'static str,
+    transcript: &'static str,
+    usage: &'static str,
+    cubence: &'static str,
+    git: &'static str,
+    update: Option<&'static str>,
+}
+
+/// A file deleted when dropped
+struct TempFile(PathBuf);
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Write to a fresh file in the temp dir. `create_new` refuses existing files
+/// and symlinks, so another user can't plant one at a name we'd pick.
+fn write_transcript(name: &str, transcript: &str) -> std::io::Result<TempFile> {
+    use std::hash::{BuildHasher, Hasher};
+    use std::io::Write;
+
+    loop {
+        let suffix = std::collections::hash_map::RandomState::new()
+            .build_hasher()
+            .finish();
+        let path = std::env::temp_dir().join(format!(
+            "cubenceline-fixture-{}-{}-{:016x}.jsonl",
+            name,
+            std::process::id(),
+            suffix
+        ));
+        match std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+        {
+            Ok(mut file) => {
+                let temp = TempFile(path);
+                file.write_all(transcript.as_bytes())?;
+                return Ok(temp);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+macro_rules! embedded_fixture {
+    ($dir:literal) => {
+        EmbeddedFixture {
+            payload: include_str!(concat!("../../fixtures/", $dir, "/payload.json")),
+            transcript: include_str!(concat!("../../fixtures/", $dir, "/transcript.jsonl")),
+            usage: include_str!(concat!("../../fixtures/", $dir, "/usage.json")),
+            cubence: include_str!(concat!("../../fixtures/", $dir, "/cubence.json")),
+            git: include_str!(concat!("../../fixtures/", $dir, "/git.json")),
+            update: None,
+        }
+    };
+}
+
+fn builtin_files(name: &str) -> Option<EmbeddedFixture> {
+    let files = match name {
+        "default" => EmbeddedFixture {
+            update: Some(include_str!("../../fixtures/default/update.json")),
+            ..embedded_fixture!("default")
+        },
+        "low-context" => embedded_fixture!("low-context"),
+        "near-quota" => embedded_fixture!("near-quota"),
+        "api-error" => embedded_fixture!("api-error"),
+        "detached-head" => embedded_fixture!("detached-head"),
+        _ => return None,
+    };
+    Some(files)
+}
+
+/// The most recently written session under `~/.claude/projects`
+fn newest_transcript() -> Option<PathBuf> {
+    let projects = dirs::home_dir()?.join(".claude").join("projects");
+    std::fs::read_dir(projects)
+        .ok()?
+        .flatten()
+        .filter_map(|project| std::fs::read_dir(project.path()).ok())
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "jsonl"))
+        .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+        .max()
+        .map(|(_, path)| path)
+}
+
+/// The model of the last assistant message in a transcript
+fn transcript_model(path: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    content.lines().rev().find_map(|line| {
+        let entry: serde_json::Value = serde_json::from_str(line).ok()?;
+        let model = entry.get("message")?.get("model")?.as_str()?;
+        // Claude Code marks its own messages, e.g. after an interruption
+        (model != "<synthetic>").then(|| model.to_string())
+    })
+}
@@ -1,4 +1,8 @@
+pub mod color;
+pub mod fixtures;
+pub mod output;
 pub mod segments;
 pub mod statusline;
 
-pub use statusline::{collect_all_segments, StatusLineGenerator};
+pub use output::OutputFormat;
+pub use statusline::{collect_all_segments, collect_segments_with_responses, StatusLineGenerator};
@@ -0,0 +1,223 @@
+use crate::config::SegmentConfig;
+use crate::core::segments::SegmentData;
+use std::collections::BTreeMap;
+
+/// How the statusline is printed
+#[derive(Debug, Clone, Copy, PartialEq, Default, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// ANSI escape sequences, as expected by Claude Code
+    #[default]
+    Ansi,
+    /// Collected segment data as JSON, for scripting
+    Json,
+    /// Text without any escape sequences
+    Plain,
+    /// tmux status line format (`#[fg=…,bg=…]`)
+    Tmux,
+    /// zsh prompt, with escapes wrapped in `%{…%}`
+    Zsh,
+    /// bash prompt, with escapes wrapped in `\[…\]`
+    Bash,
+}
+
+/// A piece of ANSI output: either an escape sequence or visible text
+enum Token<'a> {
+    Escape(&'a str),
+    Text(&'a str),
+}
+
+/// Split ANSI output into escape sequences (CSI and OSC) and visible text
+fn tokenize(ansi: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut text_start = 0;
+    let mut chars = ansi.char_indices().peekable();
+
+    while let Some((start, ch)) = chars.next() {
+        if ch != '\x1b' {
+            continue;
+        }
+        if text_start < start {
+            tokens.push(Token::Text(&ansi[text_start..start]));
+        }
+
+        let mut end = ansi.len();
+        match chars.next() {
+            Some((_, '[')) => {
+                for (i, ch) in chars.by_ref() {
+                    if ch.is_alphabetic() {
+                        end = i + ch.len_utf8();
+                        break;
+                    }
+                }
+            }
+            Some((_, ']')) => {
+                while let Some((i, ch)) = chars.next() {
+                    if ch == '\x07' {
+                        end = i + 1;
+                        break;
+                    }
+                    if ch == '\x1b' {
+                        if let Some((j, '\\')) = chars.peek().copied() {
+                            chars.next();
+                            end = j + 1;
+                            break;
+                        }
+                    }
+                }
+            }
+            Some((i, ch)) => end = i + ch.len_utf8(),
+            None => {}
+        }
+
+        tokens.push(Token::Escape(&ansi[start..end]));
+        text_start = end;
+    }
+
+    if text_start < ansi.len() {
+        tokens.push(Token::Text(&ansi[text_start..]));
+    }
+    tokens
+}
+
+/// Collected segment data as a JSON array, one object per enabled segment
+pub fn to_json(segments: &[(SegmentConfig, SegmentData)]) -> String {
+    let values: Vec<serde_json::Value> = segments
+        .iter()
+        .filter(|(config, _)| config.enabled)
+        .map(|(config, data)| {
+            // Sorted keys keep the output stable between runs
+            let metadata: BTreeMap<&String, &String> = data.metadata.iter().collect();
+            serde_json::json!({
+                "id": config.id,
+                "primary": data.primary,
+                "secondary": data.secondary,
+                "metadata": metadata,
+            })
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&values).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Wrap every escape sequence so zsh doesn't count it towards the prompt width.
+/// `\`, `$` and backticks are escaped so `PROMPT_SUBST` can't run a branch or
+/// directory name as a command.
+pub fn to_zsh(ansi: &str) -> String {
+    tokenize(ansi)
+        .into_iter()
+        .map(|token| match token {
+            Token::Escape(escape) => format!("%{{{}%}}", escape.replace('%', "%%")),
+            Token::Text(text) => text
+                .replace('%', "%%")
+                .replace('\\', "\\\\")
+                .replace('$', "\\$")
+                .replace('`', "\\`"),
+        })
+        .collect()
+}
+
+/// Wrap every escape sequence so bash doesn't count it towards the prompt width.
+/// Text passes through prompt decoding (`\\` → `\`) and then `promptvars`
+/// expansion, so `$` and backticks get two backslashes and a literal `\` four.
+pub fn to_bash(ansi: &str) -> String {
+    tokenize(ansi)
+        .into_iter()
+        .map(|token| match token {
+            Token::Escape(escape) => format!("\\[{}\\]", escape.replace('\\', "\\\\")),
+            Token::Text(text) => text
+                .replace('\\', "\\\\\\\\")
+                .replace('$', "\\\\$")
+                .replace('`', "\\\\`"),
+        })
+        .collect()
+}
+
+/// Translate SGR sequences into tmux style directives; other escapes are dropped
+pub fn to_tmux(ansi: &str) -> String {
+    tokenize(ansi)
+        .into_iter()
+        .map(|token| match token {
+            Token::Escape(escape) => escape
+                .strip_prefix("\x1b[")
+                .and_then(|sgr| sgr.strip_suffix('m'))
+                .map(tmux_style)
+                .unwrap_or_default(),
+            Token::Text(text) => text.replace('#', "##"),
+        })
+        .collect()
+}
+
+fn tmux_style(params: &str) -> String {
+    let params: Vec<&str> = params.split(';').collect();
+    let mut styles = Vec::new();
+    let mut i = 0;
+
+    while i < params.len() {
+        let param = params[i];
+        match param {
+            "" | "0" => styles.push("default".to_string()),
+            "1" => styles.push("bold".to_string()),
+            "2" => styles.push("dim".to_string()),
+            "3" => styles.push("italics".to_string()),
+            "7" => styles.push("reverse".to_string()),
+            "9" => styles.push("strikethrough".to_string()),
+            "39" => styles.push("fg=default".to_string()),
+            "49" => styles.push("bg=default".to_string()),
+            "38" | "48" | "58" => {
+                let target = match param {
+                    "38" => "fg",
+                    "48" => "bg",
+                    _ => "us",
+                };
+                match params.get(i + 1) {
+                    Some(&"5") => {
+                        if let Some(index) = params.get(i + 2) {
+                            styles.push(format!("{}=colour{}", target, index));
+                        }
+                        i += 2;
+                    }
+                    Some(&"2") => {
+                        let rgb: Vec<u8> = params[i + 2..(i + 5).min(params.len())]
+                            .iter()
+                            .filter_map(|v| v.parse().ok())
+                            .collect();
+                        if let [r, g, b] = rgb[..] {
+                            styles.push(format!("{}=#{:02x}{:02x}{:02x}", target, r, g, b));
+                        }
+                        i += 4;
+                    }
+                    _ => {}
+                }
+            }
+            underline if underline == "4" || underline.starts_with("4:") => {
+                let style = match underline {
+                    "4:0" => "nounderscore",
+                    "4:2" => "double-underscore",
+                    "4:3" => "curly-underscore",
+                    "4:4" => "dotted-underscore",
+                    "4:5" => "dashed-underscore",
+                    _ => "underscore",
+                };
+                styles.push(style.to_string());
+            }
+            code => {
+                if let Ok(code) = code.parse::<u8>() {
+                    match code {
+                        30..=37 => styles.push(format!("fg=colour{}", code - 30)),
+                        40..=47 => styles.push(format!("bg=colour{}", code - 40)),
+                        90..=97 => styles.push(format!("fg=colour{}", code - 90 + 8)),
+                        100..=107 => styles.push(format!("bg=colour{}", code - 100 + 8)),
+                        _ => {}
+                    }
+                }
+            }
+        }
+        i += 1;
+    }
+
+    if styles.is_empty() {
+        String::new()
+    } else {
+        format!("#[{}]", styles.join(","))
+    }
+}
@@ -1,39 +1,83 @@
 use super::{Segment, SegmentData};
 use crate::config::{InputData, SegmentId};
-use crate::utils::{
-    credentials,
-    subscription::{SubscriptionApiClient, WindowInfo},
-};
+use crate::utils::subscription::{SubscriptionApiClient, SubscriptionInfo, WindowInfo};
 use std::collections::HashMap;
+use std::path::PathBuf;
 
-#[derive(Default)]
-pub struct CubenceSegment;
+pub struct CubenceSegment {
+    canned: Option<serde_json::Value>,
+    client: SubscriptionApiClient,
+    cache_duration: u64,
+    timeout: u64,
+}
+
+impl Default for CubenceSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl CubenceSegment {
+    const DASHBOARD_URL: &'static str = "https://cubence.com/dashboard";
+
     pub fn new() -> Self {
-        Self
+        Self {
+            canned: None,
+            client: SubscriptionApiClient::new(),
+            cache_duration: 180,
+            timeout: 2,
+        }
     }
 
-    fn get_options() -> (String, u64, u64) {
-        let config = crate::config::Config::load().ok();
-        let segment_config = config
-            .as_ref()
-            .and_then(|cfg| cfg.segments.iter().find(|s| s.id == SegmentId::Cubence));
+    pub fn with_cache_duration(mut self, cache_duration: u64) -> Self {
+        self.cache_duration = cache_duration;
+        self
+    }
 
-        // Always use the URL from settings.json (ANTHROPIC_BASE_URL)
-        let api_url = SubscriptionApiClient::default_url();
+    pub fn with_timeout(mut self, timeout: u64) -> Self {
+        self.timeout = timeout;
+        self
+    }
 
-        let cache_duration = segment_config
-            .and_then(|sc| sc.options.get("cache_duration"))
-            .and_then(|v| v.as_u64())
-            .unwrap_or(180);
+    /// Call this base URL instead of ANTHROPIC_BASE_URL from settings.json
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.client = self.client.with_base_url(base_url);
+        self
+    }
 
-        let timeout = segment_config
-            .and_then(|sc| sc.options.get("timeout"))
-            .and_then(|v| v.as_u64())
-            .unwrap_or(2);
+    /// Read credentials from this settings file instead of ~/.claude/settings.json
+    pub fn with_settings_path(mut self, settings_path: impl Into<PathBuf>) -> Self {
+        self.client = self.client.with_settings_path(settings_path);
+        self
+    }
+
+    /// Keep the response cache in this directory instead of the cache directory
+    pub fn with_cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.client = self.client.with_cache_dir(cache_dir);
+        self
+    }
 
-        (api_url, cache_duration, timeout)
+    /// Use a recorded subscription-info response instead of calling the API.
+    /// `{"error": "..."}` renders as a failed request; an optional `latency_ms`
+    /// stands in for the health check.
+    pub fn with_canned_response(mut self, response: serde_json::Value) -> Self {
+        self.canned = Some(response);
+        self
+    }
+
+    fn canned_results(
+        response: &serde_json::Value,
+    ) -> (Result<SubscriptionInfo, String>, Result<u64, String>) {
+        let info = match response.get("error").and_then(|e| e.as_str()) {
+            Some(error) => Err(error.to_string()),
+            None => serde_json::from_value(response.clone())
+                .map_err(|e| format!("Failed to parse response: {}", e)),
+        };
+        let latency = response
+            .get("latency_ms")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| "Health check failed".to_string());
+        (info, latency)
     }
 
     fn format_window_for_metadata(window: &WindowInfo) -> HashMap<String, String> {
@@ -71,15 +115,30 @@ impl CubenceSegment {
 
 impl Segment for CubenceSegment {
     fn collect(&self, _input: &InputData) -> Option<SegmentData> {
-        let (api_url, cache_duration, timeout) = Self::get_options();
+        let (info_result, latency_result) = match &self.canned {
+            Some(response) => Self::canned_results(response),
+            None => {
+                let info_result = self
+                    .client
+                    .get_with_cache(self.timeout, self.cache_duration);
+
+                // Check health endpoint latency
+                let latency_result = match info_result {
+                    Ok(_) => self.client.check_health_latency(self.timeout),
+                    Err(_) => Err("skipped".to_string()),
+                };
+                (info_result, latency_result)
+            }
+        };
 
-        let info = match SubscriptionApiClient::get_with_cache(&api_url, timeout, cache_duration) {
+        let info = match info_result {
             Ok(info) => info,
             Err(error_msg) => {
                 // Failed to get cubence info, return error segment with detailed error message
                 let mut metadata = HashMap::new();
                 metadata.insert("error".to_string(), "fetch_failed".to_string());
                 metadata.insert("error_message".to_string(), error_msg.clone());
+                metadata.insert("link".to_string(), Self::DASHBOARD_URL.to_string());
                 return Some(SegmentData {
                     primary: format!("Cubence - API Error: {}", error_msg),
                     secondary: String::new(),
@@ -111,11 +170,6 @@ impl Segment for CubenceSegment {
             format!("Cubence - 余额[${:.2}]", balance.amount_dollar)
         };
 
-        // Check health endpoint latency
-        let base_url = credentials::get_anthropic_base_url()
-            .unwrap_or_else(|| "https://cubence.com/api".to_string());
-
-        let latency_result = SubscriptionApiClient::check_health_latency(&base_url, timeout);
         let latency_str = match &latency_result {
             Ok(latency_ms) => format!(" 延迟[{}ms]", latency_ms),
             Err(_) => " 延迟[timeout]".to_string(),
@@ -154,6 +208,8 @@ impl Segment for CubenceSegment {
             metadata.insert(format!("weekly_{}", k), v);
         }
 
+        metadata.insert("link".to_string(), Self::DASHBOARD_URL.to_string());
+
         // Add latency to metadata
         if let Ok(latency_ms) = latency_result {
             metadata.insert("latency_ms".to_string(), latency_ms.to_string());
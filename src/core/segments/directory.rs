@@ -1,13 +1,175 @@
 use super::{Segment, SegmentData};
 use crate::config::{InputData, SegmentId};
 use std::collections::HashMap;
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+
+/// Git work tree of each directory looked up so far. The statusline renders
+/// once per process, and the TUI preview re-renders on every key.
+static GIT_ROOTS: OnceLock<Mutex<HashMap<String, Option<String>>>> = OnceLock::new();
+
+/// How the Directory segment renders the working directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DirectoryMode {
+    /// Last path component only (`crate`)
+    #[default]
+    Name,
+    /// Absolute path (`/home/user/work/project/crate`)
+    Full,
+    /// Absolute path with the home directory replaced by `~`
+    Home,
+    /// Path relative to the git root or Claude Code's `project_dir`
+    Project,
+    /// Fish-style abbreviation (`~/w/p/crate`)
+    Fish,
+}
+
+impl DirectoryMode {
+    pub const ALL: [DirectoryMode; 5] = [
+        DirectoryMode::Name,
+        DirectoryMode::Full,
+        DirectoryMode::Home,
+        DirectoryMode::Project,
+        DirectoryMode::Fish,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DirectoryMode::Name => "name",
+            DirectoryMode::Full => "full",
+            DirectoryMode::Home => "home",
+            DirectoryMode::Project => "project",
+            DirectoryMode::Fish => "fish",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|mode| mode.as_str().eq_ignore_ascii_case(value))
+    }
+
+    /// Next mode in display order, used by the TUI to cycle the option
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|m| m == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// A path split into a leading anchor (`~`, `/`, `C:`) and its components
+struct PathParts {
+    anchor: String,
+    components: Vec<String>,
+    separator: char,
+}
+
+impl PathParts {
+    fn parse(path: &str) -> Self {
+        // Windows paths use backslashes unless the caller already normalized them
+        let separator = if path.contains('\\') && !path.contains('/') {
+            '\\'
+        } else {
+            '/'
+        };
+
+        let mut components: Vec<String> = path
+            .split(['/', '\\'])
+            .filter(|c| !c.is_empty())
+            .map(|c| c.to_string())
+            .collect();
+
+        let anchor = if path.starts_with('/') || path.starts_with('\\') {
+            separator.to_string()
+        } else if components
+            .first()
+            .map(|c| c.len() == 2 && c.ends_with(':'))
+            .unwrap_or(false)
+        {
+            // Drive letter, e.g. C:
+            components.remove(0)
+        } else {
+            String::new()
+        };
+
+        Self {
+            anchor,
+            components,
+            separator,
+        }
+    }
+
+    /// Replace a leading home directory prefix with `~`
+    fn with_home_substituted(mut self, home: Option<&str>) -> Self {
+        let Some(home) = home else {
+            return self;
+        };
+
+        let home_parts = PathParts::parse(home);
+        if home_parts.components.is_empty()
+            || !self.anchor.eq_ignore_ascii_case(&home_parts.anchor)
+            || !self.components.starts_with(&home_parts.components)
+        {
+            return self;
+        }
+
+        self.components.drain(..home_parts.components.len());
+        self.anchor = "~".to_string();
+        self
+    }
+
+    fn abbreviate(&mut self) {
+        let last = self.components.len().saturating_sub(1);
+        for component in self.components.iter_mut().take(last) {
+            // Keep the leading dot of hidden directories, like fish does
+            let take = if component.starts_with('.') { 2 } else { 1 };
+            *component = component.chars().take(take).collect();
+        }
+    }
+
+    fn truncate(&mut self, max_components: usize) {
+        if max_components == 0 || self.components.len() <= max_components {
+            return;
+        }
+
+        let skip = self.components.len() - max_components;
+        self.components.drain(..skip);
+        self.anchor = "…".to_string();
+    }
+
+    fn render(&self) -> String {
+        let sep = self.separator.to_string();
+        let joined = self.components.join(&sep);
+
+        match self.anchor.as_str() {
+            "" => joined,
+            anchor if anchor == sep => format!("{}{}", sep, joined),
+            anchor if joined.is_empty() => anchor.to_string(),
+            anchor => format!("{}{}{}", anchor, sep, joined),
+        }
+    }
+}
 
 #[derive(Default)]
-pub struct DirectorySegment;
+pub struct DirectorySegment {
+    mode: DirectoryMode,
+    max_components: usize,
+}
 
 impl DirectorySegment {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    pub fn with_mode(mut self, mode: DirectoryMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Limit the rendered path to the last `max` components (0 = unlimited)
+    pub fn with_max_components(mut self, max: usize) -> Self {
+        self.max_components = max;
+        self
     }
 
     /// Extract directory name from path, handling both Unix and Windows separators
@@ -34,6 +196,95 @@ impl DirectorySegment {
             result.to_string()
         }
     }
+
+    /// Find the project root: Claude Code's project_dir if it contains the
+    /// current directory, otherwise the enclosing git work tree
+    fn find_project_root(current_dir: &str, project_dir: Option<&str>) -> Option<String> {
+        if let Some(project_dir) = project_dir {
+            let project = PathParts::parse(project_dir);
+            let current = PathParts::parse(current_dir);
+            if project.anchor == current.anchor
+                && current.components.starts_with(&project.components)
+            {
+                return Some(project_dir.to_string());
+            }
+        }
+
+        let roots = GIT_ROOTS.get_or_init(Default::default);
+        if let Some(root) = roots.lock().ok()?.get(current_dir) {
+            return root.clone();
+        }
+
+        let root = Self::git_root(current_dir);
+        if let Ok(mut roots) = roots.lock() {
+            roots.insert(current_dir.to_string(), root.clone());
+        }
+        root
+    }
+
+    fn git_root(current_dir: &str) -> Option<String> {
+        let output = Command::new("git")
+            .args(["rev-parse", "--show-toplevel"])
+            .current_dir(current_dir)
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        let root = String::from_utf8(output.stdout).ok()?.trim().to_string();
+        if root.is_empty() {
+            None
+        } else {
+            Some(root)
+        }
+    }
+
+    /// Render `current_dir` according to the mode and component limit
+    pub fn format_path(&self, current_dir: &str, project_dir: Option<&str>) -> String {
+        let home = dirs::home_dir().map(|h| h.to_string_lossy().to_string());
+
+        let mut parts = match self.mode {
+            DirectoryMode::Name => return Self::extract_directory_name(current_dir),
+            DirectoryMode::Full => PathParts::parse(current_dir),
+            DirectoryMode::Home | DirectoryMode::Fish => {
+                PathParts::parse(current_dir).with_home_substituted(home.as_deref())
+            }
+            DirectoryMode::Project => {
+                let mut current = PathParts::parse(current_dir);
+                let root_parts = Self::find_project_root(current_dir, project_dir)
+                    .map(|root| PathParts::parse(&root))
+                    .filter(|root| {
+                        !root.components.is_empty()
+                            && current.components.starts_with(&root.components)
+                    });
+
+                match root_parts {
+                    Some(root) => {
+                        // Keep the project's own name as the first component
+                        current.components.drain(..root.components.len() - 1);
+                        current.anchor = String::new();
+                        current
+                    }
+                    // Outside a project, fall back to the home-relative path
+                    None => current.with_home_substituted(home.as_deref()),
+                }
+            }
+        };
+
+        if self.mode == DirectoryMode::Fish {
+            parts.abbreviate();
+        }
+        parts.truncate(self.max_components);
+
+        let rendered = parts.render();
+        if rendered.is_empty() {
+            "root".to_string()
+        } else {
+            rendered
+        }
+    }
 }
 
 impl Segment for DirectorySegment {
@@ -41,11 +292,13 @@ impl Segment for DirectorySegment {
         let current_dir = &input.workspace.current_dir;
 
         // Handle cross-platform path separators manually for better compatibility
-        let dir_name = Self::extract_directory_name(current_dir);
+        let dir_name = self.format_path(current_dir, input.workspace.project_dir.as_deref());
 
         // Store the full path in metadata for potential use
         let mut metadata = HashMap::new();
         metadata.insert("full_path".to_string(), current_dir.clone());
+        metadata.insert("mode".to_string(), self.mode.as_str().to_string());
+        metadata.insert("link".to_string(), file_url(current_dir));
 
         Some(SegmentData {
             primary: dir_name,
@@ -58,3 +311,30 @@ impl Segment for DirectorySegment {
         SegmentId::Directory
     }
 }
+
+/// `file://` URL of a local path, percent-encoding anything outside the URL path alphabet
+pub fn file_url(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let mut url = String::from("file://");
+    if !path.starts_with('/') {
+        // Windows drive paths: file:///C:/Users
+        url.push('/');
+    }
+
+    url.push_str(&encode_url_path(&path));
+    url
+}
+
+/// Percent-encode anything outside the URL path alphabet, keeping `/` separators
+pub fn encode_url_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' | b':' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
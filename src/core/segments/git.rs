@@ -1,18 +1,23 @@
+use super::directory::encode_url_path;
 use super::{Segment, SegmentData};
 use crate::config::{InputData, SegmentId};
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::process::Command;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct GitInfo {
     pub branch: String,
     pub status: GitStatus,
+    #[serde(default)]
     pub ahead: u32,
+    #[serde(default)]
     pub behind: u32,
     pub sha: Option<String>,
+    pub remote_url: Option<String>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub enum GitStatus {
     Clean,
     Dirty,
@@ -21,6 +26,8 @@ pub enum GitStatus {
 
 pub struct GitSegment {
     show_sha: bool,
+    remote_url: bool,
+    canned: Option<GitInfo>,
 }
 
 impl Default for GitSegment {
@@ -31,7 +38,11 @@ impl Default for GitSegment {
 
 impl GitSegment {
     pub fn new() -> Self {
-        Self { show_sha: false }
+        Self {
+            show_sha: false,
+            remote_url: false,
+            canned: None,
+        }
     }
 
     pub fn with_sha(mut self, show_sha: bool) -> Self {
@@ -39,6 +50,19 @@ impl GitSegment {
         self
     }
 
+    /// Look up the remote URL, the link target of the segment when hyperlinks
+    /// are on. Off by default to save a git call per render.
+    pub fn with_remote_url(mut self, remote_url: bool) -> Self {
+        self.remote_url = remote_url;
+        self
+    }
+
+    /// Use recorded repository state instead of running git
+    pub fn with_canned_info(mut self, info: GitInfo) -> Self {
+        self.canned = Some(info);
+        self
+    }
+
     fn get_git_info(&self, working_dir: &str) -> Option<GitInfo> {
         if !self.is_git_repository(working_dir) {
             return None;
@@ -55,12 +79,19 @@ impl GitSegment {
             None
         };
 
+        let remote_url = if self.remote_url {
+            self.get_remote_url(working_dir)
+        } else {
+            None
+        };
+
         Some(GitInfo {
             branch,
             status,
             ahead,
             behind,
             sha,
+            remote_url,
         })
     }
 
@@ -151,6 +182,25 @@ impl GitSegment {
         }
     }
 
+    fn get_remote_url(&self, working_dir: &str) -> Option<String> {
+        let output = Command::new("git")
+            .args(["config", "--get", "remote.origin.url"])
+            .current_dir(working_dir)
+            .output()
+            .ok()?;
+
+        if output.status.success() {
+            let url = String::from_utf8(output.stdout).ok()?.trim().to_string();
+            if url.is_empty() {
+                None
+            } else {
+                Some(url)
+            }
+        } else {
+            None
+        }
+    }
+
     fn get_sha(&self, working_dir: &str) -> Option<String> {
         let output = Command::new("git")
             .args(["rev-parse", "--short=7", "HEAD"])
@@ -173,7 +223,16 @@ impl GitSegment {
 
 impl Segment for GitSegment {
     fn collect(&self, input: &InputData) -> Option<SegmentData> {
-        let git_info = self.get_git_info(&input.workspace.current_dir)?;
+        let git_info = match &self.canned {
+            Some(info) => {
+                let mut info = info.clone();
+                if !self.show_sha {
+                    info.sha = None;
+                }
+                info
+            }
+            None => self.get_git_info(&input.workspace.current_dir)?,
+        };
 
         let mut metadata = HashMap::new();
         metadata.insert("branch".to_string(), git_info.branch.clone());
@@ -185,6 +244,14 @@ impl Segment for GitSegment {
             metadata.insert("sha".to_string(), sha.clone());
         }
 
+        if let Some(url) = git_info
+            .remote_url
+            .as_deref()
+            .and_then(|remote| branch_web_url(remote, &git_info.branch))
+        {
+            metadata.insert("link".to_string(), url);
+        }
+
         let primary = git_info.branch;
         let mut status_parts = Vec::new();
 
@@ -216,3 +283,60 @@ impl Segment for GitSegment {
         SegmentId::Git
     }
 }
+
+/// Web page of a remote, e.g. `https://github.com/owner/repo` for
+/// `git@github.com:owner/repo.git`
+pub fn remote_web_url(remote: &str) -> Option<String> {
+    let remote = remote.trim().trim_end_matches('/');
+    let remote = remote.strip_suffix(".git").unwrap_or(remote);
+
+    let (host, path) = if let Some((_, rest)) = remote.split_once("://") {
+        // https://user@host/path, ssh://git@host:22/path
+        let (authority, path) = rest.split_once('/')?;
+        let host = authority.rsplit('@').next()?;
+        let host = if remote.starts_with("http") {
+            host
+        } else {
+            // The SSH port is not the web port
+            host.split(':').next()?
+        };
+        (host, path)
+    } else {
+        // scp-like syntax: git@host:path
+        let (authority, path) = remote.split_once(':')?;
+        (authority.rsplit('@').next()?, path)
+    };
+
+    if host.is_empty() || path.is_empty() {
+        return None;
+    }
+
+    let scheme = if remote.starts_with("http://") {
+        "http"
+    } else {
+        "https"
+    };
+    Some(format!(
+        "{}://{}/{}",
+        scheme,
+        host,
+        path.trim_start_matches('/')
+    ))
+}
+
+/// Web page of a branch on the remote's hosting service
+pub fn branch_web_url(remote: &str, branch: &str) -> Option<String> {
+    let base = remote_web_url(remote)?;
+    if branch == "detached" {
+        return Some(base);
+    }
+
+    let tree = if base.contains("gitlab") {
+        "-/tree"
+    } else if base.contains("bitbucket") {
+        "src"
+    } else {
+        "tree"
+    };
+    Some(format!("{}/{}/{}", base, tree, encode_url_path(branch)))
+}
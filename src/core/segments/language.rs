@@ -0,0 +1,425 @@
+use super::{Segment, SegmentData};
+use crate::config::{IconConfig, InputData, SegmentId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A language recognised by the presence of one of its manifest files
+struct LanguageSpec {
+    name: &'static str,
+    nerd_icon: &'static str,
+    plain_icon: &'static str,
+    manifests: &'static [&'static str],
+    /// Manifest file extensions, for projects named after the directory (e.g. `*.csproj`)
+    extensions: &'static [&'static str],
+    /// Files holding a toolchain version, checked in order
+    version_files: &'static [&'static str],
+    /// Tool name in asdf's `.tool-versions`
+    asdf_tool: Option<&'static str>,
+}
+
+// Order matters: first match wins, so put more specific ecosystems first
+const LANGUAGES: &[LanguageSpec] = &[
+    LanguageSpec {
+        name: "Rust",
+        nerd_icon: "\u{e7a8}",
+        plain_icon: "🦀",
+        manifests: &["Cargo.toml"],
+        extensions: &[],
+        version_files: &["rust-toolchain.toml", "rust-toolchain"],
+        asdf_tool: Some("rust"),
+    },
+    LanguageSpec {
+        name: "Go",
+        nerd_icon: "\u{e627}",
+        plain_icon: "🐹",
+        manifests: &["go.mod"],
+        extensions: &[],
+        version_files: &["go.mod"],
+        asdf_tool: Some("golang"),
+    },
+    LanguageSpec {
+        name: "TypeScript",
+        nerd_icon: "\u{e628}",
+        plain_icon: "🟦",
+        manifests: &["tsconfig.json"],
+        extensions: &[],
+        version_files: &[".nvmrc", ".node-version"],
+        asdf_tool: Some("nodejs"),
+    },
+    LanguageSpec {
+        name: "Deno",
+        nerd_icon: "\u{e7c0}",
+        plain_icon: "🦕",
+        manifests: &["deno.json", "deno.jsonc"],
+        extensions: &[],
+        version_files: &[".dvmrc"],
+        asdf_tool: Some("deno"),
+    },
+    LanguageSpec {
+        name: "Node.js",
+        nerd_icon: "\u{e718}",
+        plain_icon: "⬢",
+        manifests: &["package.json"],
+        extensions: &[],
+        version_files: &[".nvmrc", ".node-version"],
+        asdf_tool: Some("nodejs"),
+    },
+    LanguageSpec {
+        name: "Python",
+        nerd_icon: "\u{e73c}",
+        plain_icon: "🐍",
+        manifests: &[
+            "pyproject.toml",
+            "requirements.txt",
+            "setup.py",
+            "setup.cfg",
+            "Pipfile",
+        ],
+        extensions: &[],
+        version_files: &[".python-version"],
+        asdf_tool: Some("python"),
+    },
+    LanguageSpec {
+        name: "Ruby",
+        nerd_icon: "\u{e739}",
+        plain_icon: "💎",
+        manifests: &["Gemfile"],
+        extensions: &["gemspec"],
+        version_files: &[".ruby-version"],
+        asdf_tool: Some("ruby"),
+    },
+    LanguageSpec {
+        name: "Java",
+        nerd_icon: "\u{e738}",
+        plain_icon: "☕",
+        manifests: &["pom.xml", "build.gradle", "build.gradle.kts"],
+        extensions: &[],
+        version_files: &[".java-version"],
+        asdf_tool: Some("java"),
+    },
+    LanguageSpec {
+        name: "C#",
+        nerd_icon: "\u{f031b}",
+        plain_icon: "#️⃣",
+        manifests: &["global.json"],
+        extensions: &["csproj", "sln"],
+        version_files: &[],
+        asdf_tool: Some("dotnet"),
+    },
+    LanguageSpec {
+        name: "PHP",
+        nerd_icon: "\u{e73d}",
+        plain_icon: "🐘",
+        manifests: &["composer.json"],
+        extensions: &[],
+        version_files: &[".php-version"],
+        asdf_tool: Some("php"),
+    },
+    LanguageSpec {
+        name: "Elixir",
+        nerd_icon: "\u{e62d}",
+        plain_icon: "💧",
+        manifests: &["mix.exs"],
+        extensions: &[],
+        version_files: &[],
+        asdf_tool: Some("elixir"),
+    },
+    LanguageSpec {
+        name: "Swift",
+        nerd_icon: "\u{e755}",
+        plain_icon: "🐦",
+        manifests: &["Package.swift"],
+        extensions: &[],
+        version_files: &[".swift-version"],
+        asdf_tool: Some("swift"),
+    },
+    LanguageSpec {
+        name: "Dart",
+        nerd_icon: "\u{e798}",
+        plain_icon: "🎯",
+        manifests: &["pubspec.yaml"],
+        extensions: &[],
+        version_files: &[],
+        asdf_tool: Some("dart"),
+    },
+    LanguageSpec {
+        name: "Zig",
+        nerd_icon: "\u{e6a9}",
+        plain_icon: "⚡",
+        manifests: &["build.zig"],
+        extensions: &[],
+        version_files: &[],
+        asdf_tool: Some("zig"),
+    },
+    LanguageSpec {
+        name: "C/C++",
+        nerd_icon: "\u{e61d}",
+        plain_icon: "🔧",
+        manifests: &["CMakeLists.txt", "meson.build", "Makefile"],
+        extensions: &[],
+        version_files: &[],
+        asdf_tool: None,
+    },
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LanguageInfo {
+    language: String,
+    nerd_icon: String,
+    plain_icon: String,
+    manifest: String,
+    version: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LanguageCacheEntry {
+    info: Option<LanguageInfo>,
+    cached_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct LanguageCache {
+    directories: HashMap<String, LanguageCacheEntry>,
+}
+
+pub struct LanguageSegment {
+    cache_duration: u64,
+    show_version: bool,
+    dynamic_icon: bool,
+}
+
+impl Default for LanguageSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LanguageSegment {
+    const CACHE_FILE: &'static str = ".language_cache.json";
+    // Keep the cache file from growing without bound across many projects
+    const MAX_CACHE_ENTRIES: usize = 64;
+
+    pub fn new() -> Self {
+        Self {
+            cache_duration: 300,
+            show_version: true,
+            dynamic_icon: true,
+        }
+    }
+
+    pub fn with_cache_duration(mut self, cache_duration: u64) -> Self {
+        self.cache_duration = cache_duration;
+        self
+    }
+
+    pub fn with_version(mut self, show_version: bool) -> Self {
+        self.show_version = show_version;
+        self
+    }
+
+    /// Show the detected language's icon; off when the segment has an icon
+    /// of its own (see `icon_is_configured`)
+    pub fn with_dynamic_icon(mut self, dynamic_icon: bool) -> Self {
+        self.dynamic_icon = dynamic_icon;
+        self
+    }
+
+    /// Whether the user set an icon, which then wins over the language's
+    pub fn icon_is_configured(icon: &IconConfig) -> bool {
+        !icon.plain.is_empty() || !icon.nerd_font.is_empty()
+    }
+
+    fn detect(dir: &Path) -> Option<LanguageInfo> {
+        let entries = fs::read_dir(dir).ok()?;
+        let files: HashSet<String> = entries
+            .flatten()
+            .filter_map(|entry| entry.file_name().to_str().map(|s| s.to_string()))
+            .collect();
+
+        for spec in LANGUAGES {
+            let manifest = spec
+                .manifests
+                .iter()
+                .find(|m| files.contains(**m))
+                .map(|m| m.to_string())
+                .or_else(|| {
+                    files
+                        .iter()
+                        .find(|f| {
+                            Path::new(f)
+                                .extension()
+                                .and_then(|e| e.to_str())
+                                .map(|e| spec.extensions.contains(&e))
+                                .unwrap_or(false)
+                        })
+                        .cloned()
+                });
+
+            if let Some(manifest) = manifest {
+                return Some(LanguageInfo {
+                    language: spec.name.to_string(),
+                    nerd_icon: spec.nerd_icon.to_string(),
+                    plain_icon: spec.plain_icon.to_string(),
+                    manifest,
+                    version: Self::detect_version(dir, spec, &files),
+                });
+            }
+        }
+
+        None
+    }
+
+    /// Read the toolchain version from cheap, local pin files only
+    fn detect_version(dir: &Path, spec: &LanguageSpec, files: &HashSet<String>) -> Option<String> {
+        for file in spec.version_files {
+            if !files.contains(*file) {
+                continue;
+            }
+
+            let Ok(content) = fs::read_to_string(dir.join(file)) else {
+                continue;
+            };
+            let version = match *file {
+                "rust-toolchain.toml" => Self::parse_toml_channel(&content),
+                "go.mod" => content
+                    .lines()
+                    .find_map(|line| line.trim().strip_prefix("go "))
+                    .map(|v| v.trim().to_string()),
+                _ => content
+                    .lines()
+                    .map(|line| line.trim())
+                    .find(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(|line| line.to_string()),
+            };
+
+            if version.is_some() {
+                return version;
+            }
+        }
+
+        // Fall back to asdf/mise .tool-versions
+        let tool = spec.asdf_tool?;
+        if !files.contains(".tool-versions") {
+            return None;
+        }
+        let content = fs::read_to_string(dir.join(".tool-versions")).ok()?;
+        content.lines().find_map(|line| {
+            let mut parts = line.split_whitespace();
+            if parts.next() == Some(tool) {
+                parts.next().map(|v| v.to_string())
+            } else {
+                None
+            }
+        })
+    }
+
+    fn parse_toml_channel(content: &str) -> Option<String> {
+        let value: toml::Value = toml::from_str(content).ok()?;
+        value
+            .get("toolchain")
+            .and_then(|t| t.get("channel"))
+            .and_then(|c| c.as_str())
+            .map(|c| c.to_string())
+    }
+
+    fn get_cache_path() -> PathBuf {
+        crate::config::paths::cache_dir().join(Self::CACHE_FILE)
+    }
+
+    fn load_cache() -> LanguageCache {
+        fs::read_to_string(Self::get_cache_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_cache(cache: &LanguageCache) {
+        let path = Self::get_cache_path();
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(content) = serde_json::to_string_pretty(cache) {
+            let _ = fs::write(path, content);
+        }
+    }
+
+    fn is_entry_valid(&self, entry: &LanguageCacheEntry) -> bool {
+        if self.cache_duration == 0 {
+            return false;
+        }
+
+        if let Ok(cached_at) = DateTime::parse_from_rfc3339(&entry.cached_at) {
+            let elapsed = Utc::now().signed_duration_since(cached_at.with_timezone(&Utc));
+            elapsed.num_seconds() < self.cache_duration as i64
+        } else {
+            false
+        }
+    }
+
+    fn detect_with_cache(&self, dir: &str) -> Option<LanguageInfo> {
+        let mut cache = Self::load_cache();
+
+        if let Some(entry) = cache.directories.get(dir) {
+            if self.is_entry_valid(entry) {
+                return entry.info.clone();
+            }
+        }
+
+        let info = Self::detect(Path::new(dir));
+
+        if self.cache_duration > 0 {
+            // Drop expired entries before adding the fresh one
+            cache
+                .directories
+                .retain(|_, entry| self.is_entry_valid(entry));
+            if cache.directories.len() >= Self::MAX_CACHE_ENTRIES {
+                cache.directories.clear();
+            }
+            cache.directories.insert(
+                dir.to_string(),
+                LanguageCacheEntry {
+                    info: info.clone(),
+                    cached_at: Utc::now().to_rfc3339(),
+                },
+            );
+            Self::save_cache(&cache);
+        }
+
+        info
+    }
+}
+
+impl Segment for LanguageSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let info = self.detect_with_cache(&input.workspace.current_dir)?;
+
+        let primary = match (&info.version, self.show_version) {
+            (Some(version), true) => format!("{} {}", info.language, version),
+            _ => info.language.clone(),
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("language".to_string(), info.language);
+        metadata.insert("manifest".to_string(), info.manifest);
+        if let Some(version) = info.version {
+            metadata.insert("version".to_string(), version);
+        }
+        if self.dynamic_icon {
+            metadata.insert("dynamic_icon".to_string(), info.nerd_icon);
+            metadata.insert("dynamic_icon_plain".to_string(), info.plain_icon);
+        }
+
+        Some(SegmentData {
+            primary,
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Language
+    }
+}
@@ -3,6 +3,7 @@ pub mod cost;
 pub mod cubence;
 pub mod directory;
 pub mod git;
+pub mod language;
 pub mod model;
 pub mod output_style;
 pub mod session;
@@ -29,8 +30,9 @@ pub struct SegmentData {
 pub use context_window::ContextWindowSegment;
 pub use cost::CostSegment;
 pub use cubence::CubenceSegment;
-pub use directory::DirectorySegment;
+pub use directory::{DirectoryMode, DirectorySegment};
 pub use git::GitSegment;
+pub use language::LanguageSegment;
 pub use model::ModelSegment;
 pub use output_style::OutputStyleSegment;
 pub use session::SessionSegment;
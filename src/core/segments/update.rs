@@ -3,23 +3,45 @@ use crate::config::{InputData, SegmentId};
 use crate::updater::UpdateState;
 
 #[derive(Default)]
-pub struct UpdateSegment;
+pub struct UpdateSegment {
+    canned: Option<UpdateState>,
+}
 
 impl UpdateSegment {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Use a recorded update state instead of loading it (and checking GitHub)
+    pub fn with_canned_state(mut self, state: UpdateState) -> Self {
+        self.canned = Some(state);
+        self
     }
 }
 
 impl Segment for UpdateSegment {
     fn collect(&self, _input: &InputData) -> Option<SegmentData> {
         // Load update state and check for update status
-        let update_state = UpdateState::load();
+        let loaded;
+        let update_state = match &self.canned {
+            Some(state) => state,
+            None => {
+                loaded = UpdateState::load();
+                &loaded
+            }
+        };
+
+        update_state.status_text().map(|status_text| {
+            let mut metadata = std::collections::HashMap::new();
+            if let Some(url) = &update_state.release_url {
+                metadata.insert("link".to_string(), url.clone());
+            }
 
-        update_state.status_text().map(|status_text| SegmentData {
-            primary: status_text,
-            secondary: String::new(),
-            metadata: std::collections::HashMap::new(),
+            SegmentData {
+                primary: status_text,
+                secondary: String::new(),
+                metadata,
+            }
         })
     }
 
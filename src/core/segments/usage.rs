@@ -4,6 +4,7 @@ use crate::utils::credentials;
 use chrono::{DateTime, Datelike, Duration, Local, Timelike, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 #[derive(Debug, Deserialize)]
 struct ApiUsageResponse {
@@ -25,12 +26,128 @@ struct ApiUsageCache {
     cached_at: String,
 }
 
-#[derive(Default)]
-pub struct UsageSegment;
+pub struct UsageSegment {
+    canned: Option<serde_json::Value>,
+    base_url: Option<String>,
+    settings_path: Option<PathBuf>,
+    cache_dir: Option<PathBuf>,
+    user_agent: Option<String>,
+    cache_duration: u64,
+    timeout: u64,
+}
+
+impl Default for UsageSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl UsageSegment {
+    const DEFAULT_BASE_URL: &'static str = "https://api.anthropic.com";
+    const CACHE_FILE: &'static str = ".api_usage_cache.json";
+
     pub fn new() -> Self {
-        Self
+        Self {
+            canned: None,
+            base_url: None,
+            settings_path: None,
+            cache_dir: None,
+            user_agent: None,
+            cache_duration: 300,
+            timeout: 2,
+        }
+    }
+
+    pub fn with_cache_duration(mut self, cache_duration: u64) -> Self {
+        self.cache_duration = cache_duration;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: u64) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Call this base URL instead of ANTHROPIC_BASE_URL from settings.json
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Read credentials from this settings file instead of ~/.claude/settings.json
+    pub fn with_settings_path(mut self, settings_path: impl Into<PathBuf>) -> Self {
+        self.settings_path = Some(settings_path.into());
+        self
+    }
+
+    /// Keep the response cache in this directory instead of the cache directory
+    pub fn with_cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(cache_dir.into());
+        self
+    }
+
+    /// Send this User-Agent instead of asking npm for the Claude Code version
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Use a recorded `/api/oauth/usage` response instead of calling the API.
+    /// Anything that doesn't parse as a usage response renders as an API error.
+    pub fn with_canned_response(mut self, response: serde_json::Value) -> Self {
+        self.canned = Some(response);
+        self
+    }
+
+    /// The last cached usage, however old, shaped like an `/api/oauth/usage`
+    /// response for `with_canned_response`
+    pub fn cached_response(&self) -> Option<serde_json::Value> {
+        let cache = self.load_cache()?;
+        Some(serde_json::json!({
+            "five_hour": { "utilization": cache.five_hour_utilization, "resets_at": null },
+            "seven_day": {
+                "utilization": cache.seven_day_utilization,
+                "resets_at": cache.resets_at,
+            },
+        }))
+    }
+
+    fn api_error() -> SegmentData {
+        let mut metadata = HashMap::new();
+        metadata.insert("error".to_string(), "api_error".to_string());
+        SegmentData {
+            primary: "API Error".to_string(),
+            secondary: String::new(),
+            metadata,
+        }
+    }
+
+    fn usage_data(
+        five_hour_util: f64,
+        seven_day_util: f64,
+        resets_at: Option<String>,
+    ) -> SegmentData {
+        let dynamic_icon = Self::get_circle_icon(seven_day_util / 100.0);
+        let five_hour_percent = five_hour_util.round() as u8;
+        let primary = format!("{}%", five_hour_percent);
+        let secondary = format!("· {}", Self::format_reset_time(resets_at.as_deref()));
+
+        let mut metadata = HashMap::new();
+        metadata.insert("dynamic_icon".to_string(), dynamic_icon);
+        metadata.insert(
+            "five_hour_utilization".to_string(),
+            five_hour_util.to_string(),
+        );
+        metadata.insert(
+            "seven_day_utilization".to_string(),
+            seven_day_util.to_string(),
+        );
+
+        SegmentData {
+            primary,
+            secondary,
+            metadata,
+        }
     }
 
     fn get_circle_icon(utilization: f64) -> String {
@@ -65,17 +182,22 @@ impl UsageSegment {
         "?".to_string()
     }
 
-    fn get_cache_path() -> Option<std::path::PathBuf> {
-        let home = dirs::home_dir()?;
-        Some(
-            home.join(".claude")
-                .join("ccline")
-                .join(".api_usage_cache.json"),
-        )
+    fn settings_path(&self) -> Option<PathBuf> {
+        self.settings_path
+            .clone()
+            .or_else(credentials::get_settings_path)
+    }
+
+    fn get_cache_path(&self) -> Option<PathBuf> {
+        let dir = self
+            .cache_dir
+            .clone()
+            .unwrap_or_else(crate::config::paths::cache_dir);
+        Some(dir.join(Self::CACHE_FILE))
     }
 
     fn load_cache(&self) -> Option<ApiUsageCache> {
-        let cache_path = Self::get_cache_path()?;
+        let cache_path = self.get_cache_path()?;
         if !cache_path.exists() {
             return None;
         }
@@ -85,7 +207,7 @@ impl UsageSegment {
     }
 
     fn save_cache(&self, cache: &ApiUsageCache) {
-        if let Some(cache_path) = Self::get_cache_path() {
+        if let Some(cache_path) = self.get_cache_path() {
             if let Some(parent) = cache_path.parent() {
                 let _ = std::fs::create_dir_all(parent);
             }
@@ -125,32 +247,6 @@ impl UsageSegment {
         "claude-code".to_string()
     }
 
-    fn get_proxy_from_settings() -> Option<String> {
-        // First try to read from Claude settings file
-        if let Ok(home) = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE")) {
-            let settings_path = format!("{}/.claude/settings.json", home);
-            if let Ok(content) = std::fs::read_to_string(&settings_path) {
-                if let Ok(settings) = serde_json::from_str::<serde_json::Value>(&content) {
-                    // Try HTTPS_PROXY first, then HTTP_PROXY from settings file
-                    if let Some(proxy) = settings
-                        .get("env")
-                        .and_then(|env| env.get("HTTPS_PROXY"))
-                        .or_else(|| settings.get("env").and_then(|env| env.get("HTTP_PROXY")))
-                        .and_then(|v| v.as_str())
-                        .map(|s| s.to_string())
-                    {
-                        return Some(proxy);
-                    }
-                }
-            }
-        }
-
-        // Fallback to environment variables if not found in settings file
-        std::env::var("HTTPS_PROXY")
-            .or_else(|_| std::env::var("HTTP_PROXY"))
-            .ok()
-    }
-
     fn fetch_api_usage(
         &self,
         api_base_url: &str,
@@ -158,12 +254,18 @@ impl UsageSegment {
         timeout_secs: u64,
     ) -> Option<ApiUsageResponse> {
         let url = format!("{}/api/oauth/usage", api_base_url);
-        let user_agent = Self::get_claude_code_version();
+        let user_agent = self
+            .user_agent
+            .clone()
+            .unwrap_or_else(Self::get_claude_code_version);
 
         let mut agent_builder = ureq::AgentBuilder::new();
 
         // Configure proxy from Claude settings if available
-        if let Some(proxy_url) = Self::get_proxy_from_settings() {
+        if let Some(proxy_url) = self
+            .settings_path()
+            .and_then(|path| credentials::get_proxy_from(&path))
+        {
             if let Ok(proxy) = ureq::Proxy::new(&proxy_url) {
                 agent_builder = agent_builder.proxy(proxy);
             }
@@ -190,7 +292,24 @@ impl UsageSegment {
 
 impl Segment for UsageSegment {
     fn collect(&self, _input: &InputData) -> Option<SegmentData> {
-        let token = match credentials::get_oauth_token() {
+        if let Some(response) = &self.canned {
+            return Some(
+                match serde_json::from_value::<ApiUsageResponse>(response.clone()) {
+                    Ok(response) => Self::usage_data(
+                        response.five_hour.utilization,
+                        response.seven_day.utilization,
+                        response.seven_day.resets_at,
+                    ),
+                    Err(_) => Self::api_error(),
+                },
+            );
+        }
+
+        let settings_path = self.settings_path();
+        let token = match settings_path
+            .as_deref()
+            .and_then(credentials::get_oauth_token_from)
+        {
             Some(t) => t,
             None => {
                 // No token found, return error segment
@@ -204,30 +323,23 @@ impl Segment for UsageSegment {
             }
         };
 
-        // Load config from file to get segment options
-        let config = crate::config::Config::load().ok()?;
-        let segment_config = config.segments.iter().find(|s| s.id == SegmentId::Usage);
-
-        // Always use the base URL from settings.json (ANTHROPIC_BASE_URL)
-        let api_base_url = crate::utils::credentials::get_anthropic_base_url()
-            .unwrap_or_else(|| "https://api.anthropic.com".to_string());
+        // Use the base URL from settings.json (ANTHROPIC_BASE_URL) unless overridden
+        let api_base_url = self
+            .base_url
+            .clone()
+            .or_else(|| {
+                settings_path
+                    .as_deref()
+                    .and_then(credentials::get_anthropic_base_url_from)
+            })
+            .unwrap_or_else(|| Self::DEFAULT_BASE_URL.to_string());
         // Remove trailing slash if present
         let api_base_url = api_base_url.trim_end_matches('/');
 
-        let cache_duration = segment_config
-            .and_then(|sc| sc.options.get("cache_duration"))
-            .and_then(|v| v.as_u64())
-            .unwrap_or(300);
-
-        let timeout = segment_config
-            .and_then(|sc| sc.options.get("timeout"))
-            .and_then(|v| v.as_u64())
-            .unwrap_or(2);
-
         let cached_data = self.load_cache();
         let use_cached = cached_data
             .as_ref()
-            .map(|cache| self.is_cache_valid(cache, cache_duration))
+            .map(|cache| self.is_cache_valid(cache, self.cache_duration))
             .unwrap_or(false);
 
         let (five_hour_util, seven_day_util, resets_at) = if use_cached {
@@ -238,7 +350,7 @@ impl Segment for UsageSegment {
                 cache.resets_at,
             )
         } else {
-            match self.fetch_api_usage(api_base_url, &token, timeout) {
+            match self.fetch_api_usage(api_base_url, &token, self.timeout) {
                 Some(response) => {
                     let cache = ApiUsageCache {
                         five_hour_utilization: response.five_hour.utilization,
@@ -264,39 +376,13 @@ impl Segment for UsageSegment {
                         )
                     } else {
                         // No cache available, return error segment
-                        let mut metadata = HashMap::new();
-                        metadata.insert("error".to_string(), "api_error".to_string());
-                        return Some(SegmentData {
-                            primary: "API Error".to_string(),
-                            secondary: String::new(),
-                            metadata,
-                        });
+                        return Some(Self::api_error());
                     }
                 }
             }
         };
 
-        let dynamic_icon = Self::get_circle_icon(seven_day_util / 100.0);
-        let five_hour_percent = five_hour_util.round() as u8;
-        let primary = format!("{}%", five_hour_percent);
-        let secondary = format!("· {}", Self::format_reset_time(resets_at.as_deref()));
-
-        let mut metadata = HashMap::new();
-        metadata.insert("dynamic_icon".to_string(), dynamic_icon);
-        metadata.insert(
-            "five_hour_utilization".to_string(),
-            five_hour_util.to_string(),
-        );
-        metadata.insert(
-            "seven_day_utilization".to_string(),
-            seven_day_util.to_string(),
-        );
-
-        Some(SegmentData {
-            primary,
-            secondary,
-            metadata,
-        })
+        Some(Self::usage_data(five_hour_util, seven_day_util, resets_at))
     }
 
     fn id(&self) -> SegmentId {
@@ -1,70 +1,208 @@
-use crate::config::{AnsiColor, Config, SegmentConfig, StyleMode};
+use crate::config::{
+    AnsiColor, ColorSupport, Config, SegmentAlign, SegmentConfig, StyleMode, TextAttributes,
+    UnderlineStyle,
+};
+use crate::core::color;
+use crate::core::fixtures::CannedResponses;
 use crate::core::segments::SegmentData;
+use unicode_width::UnicodeWidthStr;
 
-/// Strip ANSI escape sequences and return visible text length
-fn visible_width(text: &str) -> usize {
+/// Strip ANSI escape sequences (CSI and OSC), leaving only the visible text
+pub fn strip_ansi(text: &str) -> String {
     let mut visible = String::new();
-    let mut in_escape = false;
     let mut chars = text.chars().peekable();
 
     while let Some(ch) = chars.next() {
-        if ch == '\x1b' {
-            // Start of ANSI escape sequence
-            in_escape = true;
-            // Skip the [ character
-            if chars.peek() == Some(&'[') {
-                chars.next();
-            }
-        } else if in_escape {
-            // Skip until we find the end of the escape sequence (letter)
-            if ch.is_alphabetic() {
-                in_escape = false;
-            }
-        } else {
+        if ch != '\x1b' {
             // Regular character
             visible.push(ch);
+            continue;
+        }
+
+        match chars.next() {
+            // CSI: skip until the final letter, e.g. `\x1b[38;5;1m`
+            Some('[') => {
+                for ch in chars.by_ref() {
+                    if ch.is_alphabetic() {
+                        break;
+                    }
+                }
+            }
+            // OSC: skip until BEL or ST (`\x1b\\`), e.g. OSC 8 hyperlinks
+            Some(']') => {
+                while let Some(ch) = chars.next() {
+                    if ch == '\x07' {
+                        break;
+                    }
+                    if ch == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            // Two-character escapes
+            _ => {}
         }
     }
 
-    visible.chars().count()
+    visible
 }
 
+/// Strip ANSI escape sequences and return the number of terminal columns the
+/// text occupies (CJK and emoji take two)
+pub fn visible_width(text: &str) -> usize {
+    UnicodeWidthStr::width(strip_ansi(text).as_str())
+}
+
+// Powerline glyphs in the Nerd Fonts private use area: arrows, rounded,
+// slanted, flame and pixelated separators
+const POWERLINE_GLYPHS: std::ops::RangeInclusive<char> = '\u{e0b0}'..='\u{e0d7}';
+
+// Full (solid) glyphs that point or lean to the left
+const LEFT_POINTING_GLYPHS: [char; 5] =
+    ['\u{e0b2}', '\u{e0b6}', '\u{e0ba}', '\u{e0be}', '\u{e0c2}'];
+
+/// Whether the separator is a single Powerline glyph that needs color transitions
+fn is_powerline_glyph(separator: &str) -> bool {
+    let mut chars = separator.trim().chars();
+    matches!((chars.next(), chars.next()), (Some(c), None) if POWERLINE_GLYPHS.contains(&c))
+}
+
+fn points_left(separator: &str) -> bool {
+    separator.trim().chars().next().is_some_and(|c| {
+        LEFT_POINTING_GLYPHS
+            .iter()
+            .any(|glyph| c == *glyph || char::from_u32(*glyph as u32 + 1) == Some(c))
+    })
+}
+
+/// Thin (outline) variant of a solid glyph, e.g. `\u{e0b1}` for `\u{e0b0}`
+fn thin_variant(separator: &str) -> String {
+    let glyph = separator.trim();
+    match glyph.chars().next() {
+        Some(c)
+            if ('\u{e0b0}'..='\u{e0c3}').contains(&c) && (c as u32 - 0xe0b0).is_multiple_of(2) =>
+        {
+            char::from_u32(c as u32 + 1)
+                .map(|thin| thin.to_string())
+                .unwrap_or_else(|| glyph.to_string())
+        }
+        _ => glyph.to_string(),
+    }
+}
+
+/// Rendered segments of one alignment group, with their configs
+type RenderedGroup<'a> = Vec<(String, &'a SegmentConfig)>;
+
 pub struct StatusLineGenerator {
     config: Config,
+    color_support: ColorSupport,
+    extended_underline: bool,
+    hyperlinks: bool,
+    config_errors: usize,
 }
 
 impl StatusLineGenerator {
     pub fn new(config: Config) -> Self {
-        Self { config }
+        let color_support = config.style.color_support.resolve();
+        let hyperlinks = config.style.hyperlinks;
+        Self {
+            config,
+            color_support,
+            extended_underline: true,
+            hyperlinks,
+            config_errors: 0,
+        }
+    }
+
+    /// Lead the statusline with a notice about this many config errors
+    pub fn with_config_errors(mut self, config_errors: usize) -> Self {
+        self.config_errors = config_errors;
+        self
+    }
+
+    /// Toggle underline styles (curly, dotted, ...) and underline colors, which
+    /// not every ANSI parser understands
+    pub fn with_extended_underline(mut self, extended_underline: bool) -> Self {
+        self.extended_underline = extended_underline;
+        self
+    }
+
+    /// Toggle OSC 8 hyperlinks, e.g. for renderers that can't parse them
+    pub fn with_hyperlinks(mut self, hyperlinks: bool) -> Self {
+        self.hyperlinks = hyperlinks;
+        self
+    }
+
+    /// Override the color support detected from the config and environment
+    pub fn with_color_support(mut self, color_support: ColorSupport) -> Self {
+        self.color_support = color_support.resolve();
+        self
     }
 
     pub fn generate(&self, segments: Vec<(SegmentConfig, SegmentData)>) -> String {
-        let mut output = Vec::new();
-        let enabled_segments: Vec<_> = segments
-            .into_iter()
-            .filter(|(config, _)| config.enabled)
-            .collect();
+        let (left, right) = self.render_groups(&segments);
 
-        for (config, data) in enabled_segments.iter() {
-            let rendered = self.render_segment(config, data);
-            if !rendered.is_empty() {
-                output.push(rendered);
-            }
+        if left.is_empty() && right.is_empty() {
+            return self.config_error_notice().unwrap_or_default();
+        }
+
+        let mut left_line = self.join_group(&left);
+        if let Some(notice) = self.config_error_notice() {
+            left_line = format!("{} {}", notice, left_line);
         }
+        let mut statusline = if right.is_empty() {
+            left_line
+        } else {
+            let right_line = self.join_group(&right);
+            self.line_width()
+                .and_then(|width| self.pad_between(&left_line, &right_line, width))
+                .unwrap_or_else(|| match (left.last(), right.first()) {
+                    // Width unknown or too narrow: continue with the right group
+                    (Some((_, last)), Some((_, first))) => format!(
+                        "{}{}{}",
+                        left_line,
+                        self.separator_between(last, first),
+                        right_line
+                    ),
+                    _ => right_line,
+                })
+        };
 
-        if output.is_empty() {
-            return String::new();
+        // Powerline glyphs leave colors set; reset them at the end
+        let configs: Vec<&SegmentConfig> = left.iter().chain(&right).map(|(_, c)| *c).collect();
+        if self.uses_powerline(&configs) {
+            statusline.push_str("\x1b[0m");
         }
 
-        // Handle Powerline arrow separators with color transition
-        if self.config.style.separator == "\u{e0b0}" {
-            self.join_with_powerline_arrows(&output, &enabled_segments)
+        if self.color_support == ColorSupport::None {
+            strip_ansi(&statusline)
         } else {
-            // For all other separators, use white color and simple join
-            self.join_with_white_separators(&output)
+            statusline
         }
     }
 
+    fn config_error_notice(&self) -> Option<String> {
+        if self.config_errors == 0 {
+            return None;
+        }
+
+        let text = match self.config_errors {
+            1 => "⚠ config error: run cubenceline --check".to_string(),
+            n => format!("⚠ {} config errors: run cubenceline --check", n),
+        };
+        let attributes = TextAttributes {
+            bold: true,
+            ..Default::default()
+        };
+        let notice = self.apply_style(&text, Some(&AnsiColor::Color16 { c16: 9 }), &attributes);
+        Some(if self.color_support == ColorSupport::None {
+            strip_ansi(&notice)
+        } else {
+            notice
+        })
+    }
+
     /// Generate statusline for TUI preview with proper width calculation
     /// This method handles ANSI escape sequences properly for ratatui rendering
     #[cfg(feature = "tui")]
@@ -107,45 +245,41 @@ impl StatusLineGenerator {
         }
 
         // Render each segment individually
-        let mut rendered_segments = Vec::new();
-        let mut segment_configs = Vec::new();
+        let (left, right) = self.render_groups(&enabled_segments);
 
-        for (config, data) in &enabled_segments {
-            let rendered = self.render_segment(config, data);
-            if !rendered.is_empty() {
-                rendered_segments.push(rendered);
-                segment_configs.push(config.clone());
-            }
+        if left.is_empty() && right.is_empty() {
+            return Text::from(vec![Line::default()]);
         }
 
-        if rendered_segments.is_empty() {
-            return Text::from(vec![Line::default()]);
+        let max_w = max_width as usize;
+
+        // Right-aligned segments sit at the edge when everything fits on one line
+        if !right.is_empty() {
+            let aligned =
+                self.pad_between(&self.join_group(&left), &self.join_group(&right), max_w);
+            if let Some(line) = aligned {
+                if let Ok(text) = line.into_text() {
+                    return text;
+                }
+            }
         }
 
+        // Otherwise wrap the left group followed by the right one
+        let mut rendered_segments = self.with_caps(&left);
+        rendered_segments.extend(self.with_caps(&right));
+        let segment_configs: Vec<&SegmentConfig> =
+            left.iter().chain(&right).map(|(_, c)| *c).collect();
+
         // Pre-calculate separators between segments
-        let mut separators = Vec::new();
-        for i in 0..rendered_segments.len().saturating_sub(1) {
-            let separator = if self.config.style.separator == "\u{e0b0}" {
-                // Powerline arrows with color transition
-                let prev_bg = segment_configs
-                    .get(i)
-                    .and_then(|config| config.colors.background.as_ref());
-                let curr_bg = segment_configs
-                    .get(i + 1)
-                    .and_then(|config| config.colors.background.as_ref());
-                self.create_powerline_arrow(prev_bg, curr_bg)
-            } else {
-                // Regular separators with white color
-                format!("\x1b[37m{}\x1b[0m", self.config.style.separator)
-            };
-            separators.push(separator);
-        }
+        let separators: Vec<String> = segment_configs
+            .windows(2)
+            .map(|pair| self.separator_between(pair[0], pair[1]))
+            .collect();
 
         // Intelligent line wrapping by segment
         let mut lines: Vec<String> = Vec::new();
         let mut current_line = String::new();
         let mut current_width = 0usize;
-        let max_w = max_width as usize;
 
         for i in 0..rendered_segments.len() {
             let segment = &rendered_segments[i];
@@ -215,42 +349,49 @@ impl StatusLineGenerator {
     }
 
     fn render_segment(&self, config: &SegmentConfig, data: &SegmentData) -> String {
-        let icon = if let Some(dynamic_icon) = data.metadata.get("dynamic_icon") {
+        let plain_icon = data
+            .metadata
+            .get("dynamic_icon_plain")
+            .filter(|_| self.config.style.mode == StyleMode::Plain);
+        let icon = if let Some(dynamic_icon) = plain_icon.or(data.metadata.get("dynamic_icon")) {
             dynamic_icon.clone()
         } else {
             self.get_icon(config)
         };
 
+        let text_attributes = config.styles.text_attributes();
+
         // Apply background color to the entire segment if set
         if let Some(bg_color) = &config.colors.background {
             let bg_code = self.apply_background_color(bg_color);
 
             // Build the entire segment content first
-            let icon_colored = if let Some(icon_color) = &config.colors.icon {
-                self.apply_color(&icon, Some(icon_color))
-                    .replace("\x1b[0m", "")
+            let icon_colored = if config.colors.icon.is_some() || !config.styles.icon.is_plain() {
+                self.apply_style_on_background(
+                    &icon,
+                    config.colors.icon.as_ref(),
+                    &config.styles.icon,
+                    &bg_code,
+                )
             } else {
                 icon.clone()
             };
 
-            let text_styled = self
-                .apply_style(
-                    &data.primary,
-                    config.colors.text.as_ref(),
-                    config.styles.text_bold,
-                )
-                .replace("\x1b[0m", "");
+            let text_styled = self.apply_style_on_background(
+                &data.primary,
+                config.colors.text.as_ref(),
+                &text_attributes,
+                &bg_code,
+            );
 
             let mut segment_content = format!(" {} {} ", icon_colored, text_styled);
 
             if !data.secondary.is_empty() {
-                let secondary_styled = self
-                    .apply_style(
-                        &data.secondary,
-                        config.colors.text.as_ref(),
-                        config.styles.text_bold,
-                    )
-                    .replace("\x1b[0m", "");
+                let attributes = config.styles.secondary_attributes();
+                let secondary_styled =
+                    self.style_secondary(&data.secondary, config, |text, color| {
+                        self.apply_style_on_background(text, color, &attributes, &bg_code)
+                    });
                 segment_content.push_str(&format!("{} ", secondary_styled));
             }
 
@@ -258,23 +399,20 @@ impl StatusLineGenerator {
             format!("{}{}\x1b[49m", bg_code, segment_content)
         } else {
             // No background color, use original logic
-            let icon_colored = self.apply_color(&icon, config.colors.icon.as_ref());
-            let text_styled = self.apply_style(
-                &data.primary,
-                config.colors.text.as_ref(),
-                config.styles.text_bold,
-            );
+            let icon_colored =
+                self.apply_style(&icon, config.colors.icon.as_ref(), &config.styles.icon);
+            let text_styled =
+                self.apply_style(&data.primary, config.colors.text.as_ref(), &text_attributes);
 
             let mut segment = format!("{} {}", icon_colored, text_styled);
 
             if !data.secondary.is_empty() {
+                let attributes = config.styles.secondary_attributes();
                 segment.push_str(&format!(
                     " {}",
-                    self.apply_style(
-                        &data.secondary,
-                        config.colors.text.as_ref(),
-                        config.styles.text_bold
-                    )
+                    self.style_secondary(&data.secondary, config, |text, color| {
+                        self.apply_style(text, color, &attributes)
+                    })
                 ));
             }
 
@@ -282,6 +420,29 @@ impl StatusLineGenerator {
         }
     }
 
+    /// Style secondary text, coloring each space-separated token on its own
+    /// when token colors are configured
+    fn style_secondary(
+        &self,
+        text: &str,
+        config: &SegmentConfig,
+        style: impl Fn(&str, Option<&AnsiColor>) -> String,
+    ) -> String {
+        if config.colors.tokens.is_empty() {
+            let color = config
+                .colors
+                .secondary
+                .as_ref()
+                .or(config.colors.text.as_ref());
+            return style(text, color);
+        }
+
+        text.split(' ')
+            .map(|token| style(token, config.colors.token_color(token)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
     fn get_icon(&self, config: &SegmentConfig) -> String {
         match self.config.style.mode {
             StyleMode::Plain => config.icon.plain.clone(),
@@ -290,32 +451,48 @@ impl StatusLineGenerator {
         }
     }
 
-    fn apply_color(&self, text: &str, color: Option<&AnsiColor>) -> String {
-        match color {
-            Some(AnsiColor::Color16 { c16 }) => {
-                let code = if *c16 < 8 { 30 + c16 } else { 90 + (c16 - 8) };
-                format!("\x1b[{}m{}\x1b[0m", code, text)
-            }
-            Some(AnsiColor::Color256 { c256 }) => {
-                format!("\x1b[38;5;{}m{}\x1b[0m", c256, text)
-            }
-            Some(AnsiColor::Rgb { r, g, b }) => {
-                format!("\x1b[38;2;{};{};{}m{}\x1b[0m", r, g, b, text)
-            }
-            None => text.to_string(),
-        }
+    /// Fit a configured color to the terminal's color support
+    fn downsample(&self, color: Option<&AnsiColor>) -> Option<AnsiColor> {
+        color.and_then(|color| color::downsample(color, self.color_support))
     }
 
-    fn apply_style(&self, text: &str, color: Option<&AnsiColor>, bold: bool) -> String {
+    fn apply_style(
+        &self,
+        text: &str,
+        color: Option<&AnsiColor>,
+        attributes: &TextAttributes,
+    ) -> String {
         let mut codes = Vec::new();
 
         // Add style codes
-        if bold {
+        if attributes.bold {
             codes.push("1".to_string()); // Bold: \x1b[1m
         }
+        if attributes.dim {
+            codes.push("2".to_string());
+        }
+        if attributes.italic {
+            codes.push("3".to_string());
+        }
+        match attributes.underline {
+            UnderlineStyle::None => {}
+            // ansi-to-tui (TUI preview) only understands plain SGR 4
+            _ if !self.extended_underline => codes.push("4".to_string()),
+            UnderlineStyle::Single => codes.push("4".to_string()),
+            UnderlineStyle::Double => codes.push("4:2".to_string()),
+            UnderlineStyle::Curly => codes.push("4:3".to_string()),
+            UnderlineStyle::Dotted => codes.push("4:4".to_string()),
+            UnderlineStyle::Dashed => codes.push("4:5".to_string()),
+        }
+        if attributes.reverse {
+            codes.push("7".to_string());
+        }
+        if attributes.strikethrough {
+            codes.push("9".to_string());
+        }
 
         // Add color codes
-        match color {
+        match self.downsample(color).as_ref() {
             Some(AnsiColor::Color16 { c16 }) => {
                 let color_code = if *c16 < 8 { 30 + c16 } else { 90 + (c16 - 8) };
                 codes.push(color_code.to_string());
@@ -335,6 +512,17 @@ impl StatusLineGenerator {
             None => {}
         }
 
+        // Underline color (SGR 58) is an extension; 16 colors use their 256-color index
+        if self.extended_underline && !attributes.underline.is_none() {
+            match self.downsample(attributes.underline_color.as_ref()) {
+                Some(AnsiColor::Color16 { c16: c }) | Some(AnsiColor::Color256 { c256: c }) => {
+                    codes.push(format!("58;5;{}", c))
+                }
+                Some(AnsiColor::Rgb { r, g, b }) => codes.push(format!("58;2;{};{};{}", r, g, b)),
+                None => {}
+            }
+        }
+
         if codes.is_empty() {
             text.to_string()
         } else {
@@ -342,114 +530,266 @@ impl StatusLineGenerator {
         }
     }
 
+    /// Style text inside a segment with a background, restoring the background afterwards
+    fn apply_style_on_background(
+        &self,
+        text: &str,
+        color: Option<&AnsiColor>,
+        attributes: &TextAttributes,
+        bg_code: &str,
+    ) -> String {
+        let styled = self.apply_style(text, color, attributes);
+
+        if attributes.is_plain() {
+            // Only a foreground color: it is overridden by the next run anyway
+            styled.replace("\x1b[0m", "")
+        } else {
+            // Attributes must not leak into the rest of the segment
+            format!("{}{}", styled, bg_code)
+        }
+    }
+
     fn apply_background_color(&self, color: &AnsiColor) -> String {
-        match color {
-            AnsiColor::Color16 { c16 } => {
-                let code = if *c16 < 8 { 40 + c16 } else { 100 + (c16 - 8) };
+        match self.downsample(Some(color)) {
+            Some(AnsiColor::Color16 { c16 }) => {
+                let code = if c16 < 8 { 40 + c16 } else { 100 + (c16 - 8) };
                 format!("\x1b[{}m", code)
             }
-            AnsiColor::Color256 { c256 } => {
+            Some(AnsiColor::Color256 { c256 }) => {
                 format!("\x1b[48;5;{}m", c256)
             }
-            AnsiColor::Rgb { r, g, b } => {
+            Some(AnsiColor::Rgb { r, g, b }) => {
                 format!("\x1b[48;2;{};{};{}m", r, g, b)
             }
+            None => String::new(),
         }
     }
 
-    /// Join segments with white separators (non-Powerline)
-    fn join_with_white_separators(&self, rendered_segments: &[String]) -> String {
-        if rendered_segments.is_empty() {
-            return String::new();
+    /// Render enabled segments, split into the left- and right-aligned groups
+    fn render_groups<'a>(
+        &self,
+        segments: &'a [(SegmentConfig, SegmentData)],
+    ) -> (RenderedGroup<'a>, RenderedGroup<'a>) {
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+
+        for (config, data) in segments.iter().filter(|(config, _)| config.enabled) {
+            let rendered = self.render_segment(config, data);
+            if rendered.is_empty() {
+                continue;
+            }
+            let rendered = self.with_link(rendered, data);
+            match config.align {
+                SegmentAlign::Left => left.push((rendered, config)),
+                SegmentAlign::Right => right.push((rendered, config)),
+            }
         }
 
-        // Use white color for separator
-        let white_separator = format!("\x1b[37m{}\x1b[0m", self.config.style.separator);
-        rendered_segments.join(&white_separator)
+        (left, right)
     }
 
-    /// Join segments with Powerline arrow separators with proper color transitions
-    fn join_with_powerline_arrows(
-        &self,
-        rendered_segments: &[String],
-        segment_configs: &[(SegmentConfig, SegmentData)],
-    ) -> String {
-        if rendered_segments.is_empty() {
-            return String::new();
+    /// Wrap a rendered segment in an OSC 8 hyperlink to its `link` metadata
+    fn with_link(&self, rendered: String, data: &SegmentData) -> String {
+        match data.metadata.get("link") {
+            Some(url) if self.hyperlinks => {
+                format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, rendered)
+            }
+            _ => rendered,
         }
+    }
 
-        if rendered_segments.len() == 1 {
-            return rendered_segments[0].clone();
+    /// Rendered segments of a group with the caps attached to its ends
+    fn with_caps(&self, group: &[(String, &SegmentConfig)]) -> Vec<String> {
+        let mut rendered: Vec<String> = group.iter().map(|(text, _)| text.clone()).collect();
+        if let (Some((_, first)), Some((_, last))) = (group.first(), group.last()) {
+            rendered[0].insert_str(0, &self.start_cap(first));
+            if let Some(segment) = rendered.last_mut() {
+                segment.push_str(&self.end_cap(last));
+            }
         }
+        rendered
+    }
 
-        let mut result = rendered_segments[0].clone();
+    /// Join a group of rendered segments with separators and caps
+    fn join_group(&self, group: &[(String, &SegmentConfig)]) -> String {
+        let rendered = self.with_caps(group);
+        let mut line = String::new();
+        for (i, segment) in rendered.iter().enumerate() {
+            if i > 0 {
+                line.push_str(&self.separator_between(group[i - 1].1, group[i].1));
+            }
+            line.push_str(segment);
+        }
+        line
+    }
+
+    /// Pad between the left and right groups so the right group ends at `width`;
+    /// `None` when they don't fit side by side
+    fn pad_between(&self, left: &str, right: &str, width: usize) -> Option<String> {
+        let gap = width.checked_sub(visible_width(left) + visible_width(right))?;
+        if gap == 0 && !left.is_empty() {
+            return None;
+        }
+
+        let fill = self
+            .config
+            .style
+            .fill
+            .as_deref()
+            .filter(|fill| visible_width(fill) > 0)
+            .unwrap_or(" ");
+
+        // Count columns, not chars: a wide fill character takes two. One that
+        // would overhang the gap is replaced by spaces.
+        let mut padding = String::new();
+        let mut used = 0;
+        for c in fill.chars().cycle() {
+            let c_width = visible_width(c.encode_utf8(&mut [0; 4]));
+            if used + c_width > gap {
+                break;
+            }
+            padding.push(c);
+            used += c_width;
+        }
+        padding.extend(std::iter::repeat_n(' ', gap - used));
+        Some(format!("{}\x1b[0m{}{}", left, padding, right))
+    }
 
-        for (i, _) in rendered_segments.iter().enumerate().skip(1) {
-            let prev_bg = segment_configs
-                .get(i - 1)
-                .and_then(|(config, _)| config.colors.background.as_ref());
-            let curr_bg = segment_configs
-                .get(i)
-                .and_then(|(config, _)| config.colors.background.as_ref());
+    /// Width available for the statusline: `style.width`, then `COLUMNS`, then the terminal size
+    fn line_width(&self) -> Option<usize> {
+        if let Some(width) = self.config.style.width {
+            return Some(width as usize);
+        }
 
-            // Create Powerline arrow with color transition
-            let arrow = self.create_powerline_arrow(prev_bg, curr_bg);
+        if let Some(columns) = std::env::var("COLUMNS")
+            .ok()
+            .and_then(|columns| columns.trim().parse::<usize>().ok())
+            .filter(|columns| *columns > 0)
+        {
+            return Some(columns);
+        }
 
-            result.push_str(&arrow);
-            result.push_str(&rendered_segments[i]);
+        #[cfg(feature = "tui")]
+        if let Ok((columns, _)) = crossterm::terminal::size() {
+            if columns > 0 {
+                return Some(columns as usize);
+            }
         }
 
-        // Reset colors at the end
-        result.push_str("\x1b[0m");
-        result
+        None
     }
 
-    /// Create a Powerline arrow with proper color transition
-    fn create_powerline_arrow(
-        &self,
-        prev_bg: Option<&AnsiColor>,
-        curr_bg: Option<&AnsiColor>,
-    ) -> String {
-        let arrow_char = "\u{e0b0}";
+    /// Separator drawn between two adjacent segments. A segment's own
+    /// `separator` overrides the global one on its right-hand side.
+    fn separator_between(&self, prev: &SegmentConfig, curr: &SegmentConfig) -> String {
+        let separator = prev
+            .separator
+            .as_deref()
+            .unwrap_or(&self.config.style.separator);
+
+        if !is_powerline_glyph(separator) {
+            // Use white color for plain separators
+            return format!("\x1b[37m{}\x1b[0m", separator);
+        }
 
-        match (prev_bg, curr_bg) {
-            (Some(prev), Some(curr)) => {
-                // Arrow foreground = previous segment's background
-                // Arrow background = current segment's background
-                let fg_code = self.color_to_foreground_code(prev);
-                let bg_code = self.apply_background_color(curr);
-                format!("{}{}{}\x1b[0m", bg_code, fg_code, arrow_char)
+        let prev_bg = prev.colors.background.as_ref();
+        let curr_bg = curr.colors.background.as_ref();
+
+        if let (Some(prev_color), Some(curr_color)) = (prev_bg, curr_bg) {
+            if prev_color == curr_color {
+                // A full glyph would be invisible on a shared background
+                let thin = self
+                    .config
+                    .style
+                    .thin_separator
+                    .clone()
+                    .unwrap_or_else(|| thin_variant(separator));
+                let fg = prev
+                    .colors
+                    .text
+                    .clone()
+                    .unwrap_or(AnsiColor::Color16 { c16: 7 });
+                return self.create_powerline_separator(&thin, Some(&fg), Some(curr_color));
             }
-            (Some(prev), None) => {
-                // Previous segment has background, current doesn't
-                let fg_code = self.color_to_foreground_code(prev);
-                format!("{}{}\x1b[0m", fg_code, arrow_char)
+        }
+
+        if points_left(separator) {
+            // Left-pointing glyphs are drawn in the color of the segment they point from
+            self.create_powerline_separator(separator, curr_bg, prev_bg)
+        } else {
+            self.create_powerline_separator(separator, prev_bg, curr_bg)
+        }
+    }
+
+    /// Cap drawn before the first segment, in that segment's background color
+    fn start_cap(&self, first: &SegmentConfig) -> String {
+        self.cap(self.config.style.start_cap.as_deref(), first)
+    }
+
+    /// Cap drawn after the last segment, in that segment's background color
+    fn end_cap(&self, last: &SegmentConfig) -> String {
+        self.cap(self.config.style.end_cap.as_deref(), last)
+    }
+
+    fn cap(&self, glyph: Option<&str>, config: &SegmentConfig) -> String {
+        match (glyph, config.colors.background.as_ref()) {
+            (Some(glyph), Some(bg)) => {
+                format!("{}{}\x1b[0m", self.color_to_foreground_code(bg), glyph)
+            }
+            // Caps only make sense against a segment background
+            _ => String::new(),
+        }
+    }
+
+    fn uses_powerline(&self, segment_configs: &[&SegmentConfig]) -> bool {
+        self.config.style.start_cap.is_some()
+            || self.config.style.end_cap.is_some()
+            || is_powerline_glyph(&self.config.style.separator)
+            || segment_configs
+                .iter()
+                .filter_map(|config| config.separator.as_deref())
+                .any(is_powerline_glyph)
+    }
+
+    /// Create a Powerline glyph with proper color transition
+    fn create_powerline_separator(
+        &self,
+        glyph: &str,
+        fg: Option<&AnsiColor>,
+        bg: Option<&AnsiColor>,
+    ) -> String {
+        match (fg, bg) {
+            (Some(fg), Some(bg)) => {
+                let fg_code = self.color_to_foreground_code(fg);
+                let bg_code = self.apply_background_color(bg);
+                format!("{}{}{}\x1b[0m", bg_code, fg_code, glyph)
             }
-            (None, Some(curr)) => {
-                // Current segment has background, previous doesn't
-                let bg_code = self.apply_background_color(curr);
-                format!("{}{}\x1b[0m", bg_code, arrow_char)
+            (Some(fg), None) => {
+                let fg_code = self.color_to_foreground_code(fg);
+                format!("{}{}\x1b[0m", fg_code, glyph)
             }
-            (None, None) => {
-                // Neither segment has background color
-                arrow_char.to_string()
+            (None, Some(bg)) => {
+                let bg_code = self.apply_background_color(bg);
+                format!("{}{}\x1b[0m", bg_code, glyph)
             }
+            (None, None) => glyph.to_string(),
         }
     }
 
     /// Convert AnsiColor to foreground color code
     fn color_to_foreground_code(&self, color: &AnsiColor) -> String {
-        match color {
-            AnsiColor::Color16 { c16 } => {
-                let code = if *c16 < 8 { 30 + c16 } else { 90 + (c16 - 8) };
+        match self.downsample(Some(color)) {
+            Some(AnsiColor::Color16 { c16 }) => {
+                let code = if c16 < 8 { 30 + c16 } else { 90 + (c16 - 8) };
                 format!("\x1b[{}m", code)
             }
-            AnsiColor::Color256 { c256 } => {
+            Some(AnsiColor::Color256 { c256 }) => {
                 format!("\x1b[38;5;{}m", c256)
             }
-            AnsiColor::Rgb { r, g, b } => {
+            Some(AnsiColor::Rgb { r, g, b }) => {
                 format!("\x1b[38;2;{};{};{}m", r, g, b)
             }
+            None => String::new(),
         }
     }
 }
@@ -457,6 +797,15 @@ impl StatusLineGenerator {
 pub fn collect_all_segments(
     config: &Config,
     input: &crate::config::InputData,
+) -> Vec<(SegmentConfig, SegmentData)> {
+    collect_segments_with_responses(config, input, &CannedResponses::default())
+}
+
+/// Collect segment data, answering network (and git) requests from recorded responses
+pub fn collect_segments_with_responses(
+    config: &Config,
+    input: &crate::config::InputData,
+    responses: &CannedResponses,
 ) -> Vec<(SegmentConfig, SegmentData)> {
     use crate::core::segments::*;
 
@@ -469,7 +818,20 @@ pub fn collect_all_segments(
                 segment.collect(input)
             }
             crate::config::SegmentId::Directory => {
-                let segment = DirectorySegment::new();
+                let mode = segment_config
+                    .options
+                    .get("mode")
+                    .and_then(|v| v.as_str())
+                    .and_then(DirectoryMode::parse)
+                    .unwrap_or_default();
+                let max_components = segment_config
+                    .options
+                    .get("max_components")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(0) as usize;
+                let segment = DirectorySegment::new()
+                    .with_mode(mode)
+                    .with_max_components(max_components);
                 segment.collect(input)
             }
             crate::config::SegmentId::Git => {
@@ -478,20 +840,48 @@ pub fn collect_all_segments(
                     .get("show_sha")
                     .and_then(|v| v.as_bool())
                     .unwrap_or(false);
-                let segment = GitSegment::new().with_sha(show_sha);
-                segment.collect(input)
+                let segment = GitSegment::new()
+                    .with_sha(show_sha)
+                    .with_remote_url(config.style.hyperlinks);
+                match responses
+                    .git
+                    .as_ref()
+                    .map(|git| serde_json::from_value::<git::GitInfo>(git.clone()))
+                {
+                    Some(Ok(info)) => segment.with_canned_info(info).collect(input),
+                    Some(Err(_)) => None,
+                    None => segment.collect(input),
+                }
             }
             crate::config::SegmentId::ContextWindow => {
                 let segment = ContextWindowSegment::new();
                 segment.collect(input)
             }
             crate::config::SegmentId::Usage => {
-                let segment = UsageSegment::new();
-                segment.collect(input)
+                let (cache_duration, timeout) = network_options(segment_config, 300);
+                let segment = UsageSegment::new()
+                    .with_cache_duration(cache_duration)
+                    .with_timeout(timeout);
+                match (&responses.usage, responses.offline) {
+                    (Some(response), _) => segment
+                        .with_canned_response(response.clone())
+                        .collect(input),
+                    (None, true) => None,
+                    (None, false) => segment.collect(input),
+                }
             }
             crate::config::SegmentId::Cubence => {
-                let segment = CubenceSegment::new();
-                segment.collect(input)
+                let (cache_duration, timeout) = network_options(segment_config, 180);
+                let segment = CubenceSegment::new()
+                    .with_cache_duration(cache_duration)
+                    .with_timeout(timeout);
+                match (&responses.cubence, responses.offline) {
+                    (Some(response), _) => segment
+                        .with_canned_response(response.clone())
+                        .collect(input),
+                    (None, true) => None,
+                    (None, false) => segment.collect(input),
+                }
             }
             crate::config::SegmentId::Cost => {
                 let segment = CostSegment::new();
@@ -505,8 +895,28 @@ pub fn collect_all_segments(
                 let segment = OutputStyleSegment::new();
                 segment.collect(input)
             }
-            crate::config::SegmentId::Update => {
-                let segment = UpdateSegment::new();
+            crate::config::SegmentId::Update => match (&responses.update, responses.offline) {
+                (Some(state), _) => serde_json::from_value(state.clone())
+                    .ok()
+                    .and_then(|state| UpdateSegment::new().with_canned_state(state).collect(input)),
+                (None, true) => None,
+                (None, false) => UpdateSegment::new().collect(input),
+            },
+            crate::config::SegmentId::Language => {
+                let cache_duration = segment_config
+                    .options
+                    .get("cache_duration")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(300);
+                let show_version = segment_config
+                    .options
+                    .get("show_version")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(true);
+                let segment = LanguageSegment::new()
+                    .with_cache_duration(cache_duration)
+                    .with_version(show_version)
+                    .with_dynamic_icon(!LanguageSegment::icon_is_configured(&segment_config.icon));
                 segment.collect(input)
             }
         };
@@ -518,3 +928,18 @@ pub fn collect_all_segments(
 
     results
 }
+
+/// `cache_duration` and `timeout` options of the segments that call an API
+fn network_options(segment_config: &SegmentConfig, default_cache_duration: u64) -> (u64, u64) {
+    let cache_duration = segment_config
+        .options
+        .get("cache_duration")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(default_cache_duration);
+    let timeout = segment_config
+        .options
+        .get("timeout")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(2);
+    (cache_duration, timeout)
+}
@@ -1,37 +1,223 @@
-use cubenceline::cli::Cli;
-use cubenceline::config::{Config, InputData};
-use cubenceline::core::{collect_all_segments, StatusLineGenerator};
+use cubenceline::cli::{Cli, Command, ThemeCommand};
+use cubenceline::config::{
+    ColorSupport, Config, ConfigLayer, ConfigReport, InputData, SegmentConfig,
+};
+use cubenceline::core::segments::SegmentData;
+use cubenceline::core::{
+    collect_all_segments, collect_segments_with_responses, output, OutputFormat,
+    StatusLineGenerator,
+};
 use std::io::{self, IsTerminal};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse_args();
 
+    if let Some(config_file) = cli.config_file()? {
+        cubenceline::config::paths::set_config_file(config_file);
+    }
+
+    if let Some(Command::Theme { action }) = cli.command {
+        use cubenceline::ui::themes::{import, ThemePresets};
+
+        match action {
+            ThemeCommand::Import { file, name, force } => {
+                let path = std::path::Path::new(&file);
+                let theme_name = match &name {
+                    Some(name) => import::sanitize_theme_name(name),
+                    None => import::theme_name_from_path(path),
+                };
+                if theme_name.is_empty() {
+                    return Err(match name {
+                        Some(name) => format!("Invalid theme name '{}'", name).into(),
+                        None => "Cannot derive a theme name from the file name; pass --name".into(),
+                    });
+                }
+
+                let theme_path =
+                    ThemePresets::get_themes_path().join(format!("{}.toml", theme_name));
+                if theme_path.exists() && !force {
+                    return Err(format!(
+                        "Theme '{}' already exists at {}; pass --force to overwrite or --name to pick another name",
+                        theme_name,
+                        theme_path.display()
+                    )
+                    .into());
+                }
+
+                let (config, format) = import::import_theme(path, &theme_name)?;
+                ThemePresets::save_theme(&theme_name, &config)?;
+                println!("Imported {} as theme '{}'", format.as_str(), theme_name);
+                println!("Saved to {}", theme_path.display());
+                println!("Try it with: cubenceline --theme {}", theme_name);
+            }
+            ThemeCommand::Export { name } => {
+                let config = ThemePresets::resolve_theme(&name)?;
+                print!("{}", toml::to_string_pretty(&config)?);
+            }
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Render {
+        input,
+        responses,
+        fixture,
+        list,
+    }) = cli.command
+    {
+        use cubenceline::core::fixtures::{CannedResponses, Fixture, BUILTIN_FIXTURES};
+
+        if list {
+            for name in BUILTIN_FIXTURES {
+                println!("{}", name);
+            }
+            return Ok(());
+        }
+
+        // Kept until the render is done: it owns the transcript file of a built-in fixture
+        let fixture = match (input, fixture) {
+            (Some(path), _) => {
+                let content = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+                let mut input: InputData = serde_json::from_str(&content)?;
+
+                // Recorded transcripts usually sit next to the payload
+                let payload_dir = std::path::Path::new(&path).parent();
+                if let Some(dir) = payload_dir
+                    .filter(|_| std::path::Path::new(&input.transcript_path).is_relative())
+                {
+                    input.transcript_path = dir.join(&input.transcript_path).display().to_string();
+                }
+                let responses = match responses {
+                    Some(dir) => CannedResponses::from_dir(std::path::Path::new(&dir))?,
+                    None => CannedResponses {
+                        offline: true,
+                        ..Default::default()
+                    },
+                };
+                Fixture::new(input, responses)
+            }
+            (None, Some(name)) => Fixture::load(&name)?,
+            (None, None) => return Err("Pass --input <payload.json> or --fixture <name>".into()),
+        };
+
+        let (config, config_errors) = load_config(
+            cli.theme.as_deref(),
+            Some(std::path::Path::new(&fixture.input.workspace.current_dir)),
+        )?;
+        let segments_data =
+            collect_segments_with_responses(&config, &fixture.input, &fixture.responses);
+        println!(
+            "{}",
+            render(config, config_errors, segments_data, cli.format)
+        );
+        return Ok(());
+    }
+
+    if let Some(Command::Schema { kind }) = cli.command {
+        let schema = cubenceline::config::schema::schema(kind);
+        println!("{}", serde_json::to_string_pretty(&schema)?);
+        return Ok(());
+    }
+
+    if let Some(Command::Migrate { dry_run }) = cli.command {
+        use cubenceline::config::migrate;
+
+        let path = Config::get_config_path();
+        if !path.exists() {
+            return Err(format!("No config file at {}", path.display()).into());
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        let value: toml::Value = toml::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+        let Some(migrated) = migrate::migrate(value)? else {
+            println!(
+                "{} is up to date (version {})",
+                path.display(),
+                migrate::CURRENT_VERSION
+            );
+            return Ok(());
+        };
+
+        println!(
+            "Migrating {} from version {} to {}",
+            path.display(),
+            migrated.from,
+            migrate::CURRENT_VERSION
+        );
+        for change in &migrated.changes {
+            println!("  - {}", change);
+        }
+
+        if dry_run {
+            println!();
+            print!(
+                "{}",
+                migrate::diff(&path, &content, &migrate::render(&migrated)?)
+            );
+            return Ok(());
+        }
+
+        migrate::migrate_file(&path)?;
+        println!(
+            "Original kept at {}",
+            migrate::backup_path(&path, migrated.from).display()
+        );
+        return Ok(());
+    }
+
     // Handle configuration commands
     if cli.init {
         Config::init()?;
         return Ok(());
     }
 
-    if cli.print {
-        let mut config = Config::load().unwrap_or_else(|_| Config::default());
+    if cli.list_themes {
+        use cubenceline::ui::themes::{ThemePresets, BUILTIN_THEMES};
 
-        // Apply theme override if provided
-        if let Some(theme) = cli.theme {
-            config = cubenceline::ui::themes::ThemePresets::get_theme(&theme);
+        println!("Built-in themes:");
+        for theme in BUILTIN_THEMES {
+            println!("  {}", theme);
         }
 
-        config.print()?;
+        let user_themes = ThemePresets::list_user_themes();
+        if !user_themes.is_empty() {
+            println!(
+                "\nUser themes ({}):",
+                ThemePresets::get_themes_path().display()
+            );
+            for theme in user_themes {
+                match (
+                    ThemePresets::resolve_theme(&theme),
+                    ThemePresets::get_theme_parent(&theme),
+                ) {
+                    (Err(e), _) => println!("  {} (error: {})", theme, e),
+                    (Ok(_), Some(parent)) => println!("  {} (extends {})", theme, parent),
+                    (Ok(_), None) => println!("  {}", theme),
+                }
+            }
+        }
         return Ok(());
     }
 
-    if cli.check {
-        let config = Config::load()?;
-        config.check()?;
-        println!("✓ Configuration valid");
+    if cli.print {
+        let current_dir = std::env::current_dir()?;
+        let layers = config_layers(cli.theme.as_deref(), Some(&current_dir))?;
+        let config = Config::from_layers(&layers)?;
+        if cli.show_origin {
+            config.print_with_origins(&layers)?;
+        } else {
+            config.print()?;
+        }
         return Ok(());
     }
 
-    if cli.config {
+    if cli.check {
+        return check_config();
+    }
+
+    if cli.open_configurator() {
         #[cfg(feature = "tui")]
         {
             cubenceline::ui::run_configurator()?;
@@ -96,14 +282,6 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
-    // Load configuration
-    let mut config = Config::load().unwrap_or_else(|_| Config::default());
-
-    // Apply theme override if provided
-    if let Some(theme) = cli.theme {
-        config = cubenceline::ui::themes::ThemePresets::get_theme(&theme);
-    }
-
     // Check if stdin has data
     if io::stdin().is_terminal() {
         // No input data available, show main menu
@@ -120,11 +298,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         cubenceline::config::Config::init()?;
                         println!("Configuration initialized successfully!");
                     }
-                    MenuResult::CheckConfig => {
-                        let config = cubenceline::config::Config::load()?;
-                        config.check()?;
-                        println!("Configuration is valid!");
-                    }
+                    MenuResult::CheckConfig => check_config()?,
                     MenuResult::Exit => {
                         // Exit gracefully
                     }
@@ -144,14 +318,91 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let stdin = io::stdin();
     let input: InputData = serde_json::from_reader(stdin.lock())?;
 
+    // Load configuration, with the workspace's .ccline.toml applied
+    let (config, config_errors) = load_config(
+        cli.theme.as_deref(),
+        Some(std::path::Path::new(&input.workspace.current_dir)),
+    )?;
+
     // Collect segment data
     let segments_data = collect_all_segments(&config, &input);
 
     // Render statusline
-    let generator = StatusLineGenerator::new(config);
-    let statusline = generator.generate(segments_data);
+    let statusline = render(config, config_errors, segments_data, cli.format);
 
     println!("{}", statusline);
 
     Ok(())
 }
+
+/// Load the configuration for rendering, with the `--theme` override and the
+/// project's `.ccline.toml` applied. Config errors don't stop the statusline:
+/// the count is returned so it can show a notice, and a config that can't be
+/// loaded at all (including a `--theme` that doesn't resolve) is replaced by
+/// the defaults.
+fn load_config(
+    theme: Option<&str>,
+    project_dir: Option<&std::path::Path>,
+) -> Result<(Config, usize), Box<dyn std::error::Error>> {
+    let report = ConfigReport::load_with_theme(theme, project_dir);
+    let errors = report.error_count();
+    Ok((report.config.unwrap_or_default(), errors))
+}
+
+/// Print every diagnostic of the config files, exiting with 1 on errors
+fn check_config() -> Result<(), Box<dyn std::error::Error>> {
+    let current_dir = std::env::current_dir()?;
+    let report = ConfigReport::load(Some(&current_dir));
+    for diagnostic in &report.diagnostics {
+        println!("{}", diagnostic);
+    }
+
+    let errors = report.error_count();
+    if errors > 0 {
+        println!("✗ Configuration has {} error(s)", errors);
+        std::process::exit(1);
+    }
+    println!("✓ Configuration valid");
+    Ok(())
+}
+
+/// The global config (or the `--theme` override), then the project's `.ccline.toml`
+fn config_layers(
+    theme: Option<&str>,
+    project_dir: Option<&std::path::Path>,
+) -> Result<Vec<ConfigLayer>, Box<dyn std::error::Error>> {
+    let base = match theme {
+        Some(theme) => ConfigLayer::from_config(
+            format!("theme:{}", theme),
+            &cubenceline::ui::themes::ThemePresets::resolve_theme(theme)?,
+        )?,
+        None => Config::global_layer()?,
+    };
+
+    let mut layers = vec![base];
+    if let Some(dir) = project_dir {
+        layers.extend(Config::project_layer(dir)?);
+    }
+    Ok(layers)
+}
+
+fn render(
+    config: Config,
+    config_errors: usize,
+    segments_data: Vec<(SegmentConfig, SegmentData)>,
+    format: OutputFormat,
+) -> String {
+    let generator = StatusLineGenerator::new(config).with_config_errors(config_errors);
+    match format {
+        OutputFormat::Ansi => generator.generate(segments_data),
+        OutputFormat::Json => output::to_json(&segments_data),
+        OutputFormat::Plain => generator
+            .with_color_support(ColorSupport::None)
+            .generate(segments_data),
+        OutputFormat::Tmux => {
+            output::to_tmux(&generator.with_hyperlinks(false).generate(segments_data))
+        }
+        OutputFormat::Zsh => output::to_zsh(&generator.generate(segments_data)),
+        OutputFormat::Bash => output::to_bash(&generator.generate(segments_data)),
+    }
+}
@@ -1,17 +1,26 @@
-use crate::config::{Config, SegmentId, StyleMode};
+use crate::config::{Config, SegmentAlign, SegmentId, StyleMode};
 use crate::ui::components::{
     color_picker::{ColorPickerComponent, NavDirection},
+    command_palette::{CommandPaletteComponent, PaletteCommand, PaletteEntry},
+    conflict_prompt::ConflictPromptComponent,
     help::HelpComponent,
-    icon_selector::IconSelectorComponent,
+    icon_selector::{icon_catalog, IconSelectorComponent, IconStyle},
     name_input::NameInputComponent,
     preview::PreviewComponent,
     segment_list::{FieldSelection, Panel, SegmentListComponent},
     separator_editor::SeparatorEditorComponent,
     settings::SettingsComponent,
+    text_style_editor::TextStyleEditorComponent,
     theme_selector::ThemeSelectorComponent,
 };
+use crate::ui::history::History;
+use crate::ui::keymap::{join_keys, Action, Binding, Keymap};
+use crate::ui::watcher::FileWatcher;
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers,
+        MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -23,6 +32,11 @@ use ratatui::{
     Frame, Terminal,
 };
 use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// How often files are checked for edits made outside the TUI
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
 
 pub struct App {
     config: Config,
@@ -31,15 +45,25 @@ pub struct App {
     selected_field: FieldSelection,
     should_quit: bool,
     color_picker: ColorPickerComponent,
+    command_palette: CommandPaletteComponent,
+    conflict_prompt: ConflictPromptComponent,
     icon_selector: IconSelectorComponent,
     name_input: NameInputComponent,
     preview: PreviewComponent,
     segment_list: SegmentListComponent,
     separator_editor: SeparatorEditorComponent,
     settings: SettingsComponent,
+    text_style_editor: TextStyleEditorComponent,
     theme_selector: ThemeSelectorComponent,
     help: HelpComponent,
     status_message: Option<String>,
+    /// The configuration as last loaded or saved, to tell unsaved edits apart
+    saved: Option<toml::Value>,
+    watcher: FileWatcher,
+    history: History,
+    keymap: Keymap,
+    /// The segment being dragged and the config from before the drag started
+    drag: Option<(usize, Config)>,
 }
 
 impl App {
@@ -51,20 +75,163 @@ impl App {
             selected_field: FieldSelection::Enabled,
             should_quit: false,
             color_picker: ColorPickerComponent::new(),
+            command_palette: CommandPaletteComponent::new(),
+            conflict_prompt: ConflictPromptComponent::new(),
             icon_selector: IconSelectorComponent::new(),
             name_input: NameInputComponent::new(),
             preview: PreviewComponent::new(),
             segment_list: SegmentListComponent::new(),
             separator_editor: SeparatorEditorComponent::new(),
             settings: SettingsComponent::new(),
+            text_style_editor: TextStyleEditorComponent::new(),
             theme_selector: ThemeSelectorComponent::new(),
             help: HelpComponent::new(),
             status_message: None,
+            saved: snapshot(&config),
+            watcher: FileWatcher::new(),
+            history: History::new(),
+            keymap: Keymap::new(&config.keymap),
+            drag: None,
         };
+        app.help.set_keymap(&app.keymap);
+        app.watcher.watch(&app.watched_files());
         app.preview.update_preview(&config);
         app
     }
 
+    /// The config file, with the theme file it names applied
+    fn load_config() -> Config {
+        let config = Config::load().unwrap_or_else(|_| Config::default());
+        Self::apply_theme_file(config)
+    }
+
+    /// If a theme is specified, reload it to get the latest changes
+    fn apply_theme_file(config: Config) -> Config {
+        match Self::theme_file(&config) {
+            Some(_) => crate::ui::themes::ThemePresets::load_theme_from_file(&config.theme)
+                .map(|theme| with_keymap_of(theme, &config))
+                .unwrap_or(config),
+            None => config,
+        }
+    }
+
+    /// The theme file the configurator shows instead of the config, if any
+    fn theme_file(config: &Config) -> Option<PathBuf> {
+        if config.theme.is_empty() || config.theme == "default" {
+            return None;
+        }
+        let path = crate::ui::themes::ThemePresets::get_themes_path()
+            .join(format!("{}.toml", config.theme));
+        path.exists().then_some(path)
+    }
+
+    fn watched_files(&self) -> Vec<PathBuf> {
+        let mut files = vec![Config::get_config_path()];
+        files.extend(Self::theme_file(&self.config));
+        files
+    }
+
+    fn is_dirty(&self) -> bool {
+        snapshot(&self.config) != self.saved
+    }
+
+    /// The TUI wrote config.toml: it is no longer dirty, and the write is
+    /// not an outside edit
+    fn mark_saved(&mut self) {
+        self.saved = snapshot(&self.config);
+        self.mark_written();
+    }
+
+    /// The TUI wrote a file it watches, e.g. a theme; config.toml may still
+    /// differ from what is shown
+    fn mark_written(&mut self) {
+        self.watcher.watch(&self.watched_files());
+        self.watcher.mark_seen();
+    }
+
+    /// Reload files edited by another program. Without unsaved edits they are
+    /// applied right away; otherwise the user picks in the conflict prompt.
+    fn check_external_changes(&mut self) {
+        if self.conflict_prompt.is_open {
+            return;
+        }
+        self.watcher.watch(&self.watched_files());
+        let changed = self.watcher.changed();
+        let Some(file) = changed.first() else {
+            return;
+        };
+
+        let config_path = Config::get_config_path();
+        let theirs = if changed.contains(&config_path) {
+            Config::load().map(Self::apply_theme_file)
+        } else {
+            crate::ui::themes::ThemePresets::load_theme_from_file(&self.config.theme)
+                .map(|theme| with_keymap_of(theme, &self.config))
+        };
+        let theirs = match theirs {
+            Ok(theirs) => theirs,
+            Err(e) => {
+                self.status_message = Some(format!(
+                    "{} changed on disk but could not be loaded: {}",
+                    file.display(),
+                    e
+                ));
+                return;
+            }
+        };
+
+        if snapshot(&theirs) == snapshot(&self.config) {
+            self.saved = snapshot(&theirs);
+        } else if self.is_dirty() {
+            self.conflict_prompt
+                .open(&file.display().to_string(), &self.config, theirs);
+        } else {
+            self.history.record(self.config.clone());
+            self.replace_config(theirs);
+            self.status_message = Some(format!("Reloaded {} (changed on disk)", file.display()));
+        }
+    }
+
+    /// Show a configuration read from disk
+    fn replace_config(&mut self, config: Config) {
+        self.saved = snapshot(&config);
+        self.show_config(config);
+    }
+
+    fn show_config(&mut self, config: Config) {
+        // The dragged index means nothing in another segment list
+        self.drag = None;
+        if config.keymap != self.config.keymap {
+            self.keymap = Keymap::new(&config.keymap);
+            self.help.set_keymap(&self.keymap);
+        }
+        self.config = config;
+        self.selected_segment = self
+            .selected_segment
+            .min(self.config.segments.len().saturating_sub(1));
+        self.preview.update_preview(&self.config);
+    }
+
+    fn undo(&mut self) {
+        match self.history.undo(self.config.clone()) {
+            Some(previous) => {
+                self.show_config(previous);
+                self.status_message = Some("Undid last change".to_string());
+            }
+            None => self.status_message = Some("Nothing to undo".to_string()),
+        }
+    }
+
+    fn redo(&mut self) {
+        match self.history.redo(self.config.clone()) {
+            Some(next) => {
+                self.show_config(next);
+                self.status_message = Some("Redid last change".to_string());
+            }
+            None => self.status_message = Some("Nothing to redo".to_string()),
+        }
+    }
+
     pub fn run() -> Result<(), Box<dyn std::error::Error>> {
         // Ensure themes directory and built-in themes exist
         if let Err(e) = crate::config::loader::ConfigLoader::init_themes() {
@@ -72,21 +239,12 @@ impl App {
         }
 
         // Load config
-        let mut config = Config::load().unwrap_or_else(|_| Config::default());
-
-        // If a theme is specified, reload it to get the latest changes
-        if !config.theme.is_empty() && config.theme != "default" {
-            if let Ok(theme_config) =
-                crate::ui::themes::ThemePresets::load_theme_from_file(&config.theme)
-            {
-                config = theme_config;
-            }
-        }
+        let config = Self::load_config();
 
         // Terminal setup
         enable_raw_mode()?;
         let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen)?;
+        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
         let backend = CrosstermBackend::new(stdout);
         let mut terminal = Terminal::new(backend)?;
 
@@ -94,16 +252,67 @@ impl App {
 
         // Main loop
         let result = loop {
+            // Before every key, so `s` can't overwrite an edit it hasn't seen
+            app.check_external_changes();
             terminal.draw(|f| app.ui(f))?;
 
-            if let Event::Key(key) = event::read()? {
+            if !event::poll(WATCH_INTERVAL)? {
+                continue;
+            }
+            let event = event::read()?;
+            if let Event::Mouse(mouse) = event {
+                app.handle_mouse(mouse);
+            }
+            if let Event::Key(key) = event {
                 // Only handle KeyDown events to prevent double triggering on Windows
                 if key.kind != KeyEventKind::Press {
                     continue;
                 }
 
+                // Keys end a drag, so it never spans another change
+                app.end_drag();
+
+                // Any key that changes the config becomes one undo step,
+                // except that typing into one option is a single step
+                let before = app.config.clone();
+                let mut history_key = false;
+                let group = app
+                    .config
+                    .segments
+                    .get(app.selected_segment)
+                    .filter(|_| app.settings.options_editor.is_open)
+                    .and_then(|segment| app.settings.options_editor.editing_key(segment))
+                    .map(|key| format!("{}.{}", app.selected_segment, key));
+
                 // Handle popup events first
-                if app.name_input.is_open {
+                if app.conflict_prompt.is_open {
+                    match key.code {
+                        KeyCode::Char('k') | KeyCode::Char('K') | KeyCode::Esc => {
+                            app.status_message = Some(format!(
+                                "Kept your edits; saving will overwrite {}",
+                                app.conflict_prompt.file
+                            ));
+                            app.conflict_prompt.close();
+                        }
+                        KeyCode::Char('t') | KeyCode::Char('T') => {
+                            let file = app.conflict_prompt.file.clone();
+                            if let Some(theirs) = app.conflict_prompt.take_theirs() {
+                                app.history.record(app.config.clone());
+                                history_key = true;
+                                app.replace_config(theirs);
+                                app.status_message = Some(format!("Reloaded {}", file));
+                            }
+                        }
+                        KeyCode::Char('d') | KeyCode::Char('D') => {
+                            app.conflict_prompt.toggle_diff()
+                        }
+                        KeyCode::Up => app.conflict_prompt.scroll(-1),
+                        KeyCode::Down => app.conflict_prompt.scroll(1),
+                        KeyCode::PageUp => app.conflict_prompt.scroll(-10),
+                        KeyCode::PageDown => app.conflict_prompt.scroll(10),
+                        _ => {}
+                    }
+                } else if app.name_input.is_open {
                     match key.code {
                         KeyCode::Esc => app.name_input.close(),
                         KeyCode::Enter => {
@@ -122,6 +331,12 @@ impl App {
                         KeyCode::Enter => {
                             let new_separator = app.separator_editor.get_separator();
                             app.config.style.separator = new_separator;
+                            // Presets carry their caps; hand-typed separators keep the current ones
+                            if let Some(caps) = app.separator_editor.get_preset_caps() {
+                                let (start_cap, end_cap) = caps.unzip();
+                                app.config.style.start_cap = start_cap;
+                                app.config.style.end_cap = end_cap;
+                            }
                             app.separator_editor.close();
                             app.preview.update_preview(&app.config);
                             app.status_message = Some("Separator updated!".to_string());
@@ -136,6 +351,23 @@ impl App {
                         KeyCode::Backspace => app.separator_editor.backspace(),
                         _ => {}
                     }
+                } else if app.text_style_editor.is_open {
+                    match key.code {
+                        KeyCode::Esc => app.text_style_editor.close(),
+                        KeyCode::Up => app.text_style_editor.move_selection(-1),
+                        KeyCode::Down => app.text_style_editor.move_selection(1),
+                        KeyCode::Tab => app.text_style_editor.cycle_target(),
+                        KeyCode::Enter | KeyCode::Char(' ') => {
+                            if let Some(segment) = app.config.segments.get_mut(app.selected_segment)
+                            {
+                                let message =
+                                    app.text_style_editor.toggle_selected(&mut segment.styles);
+                                app.status_message = Some(message);
+                                app.preview.update_preview(&app.config);
+                            }
+                        }
+                        _ => {}
+                    }
                 } else if app.color_picker.is_open {
                     match key.code {
                         KeyCode::Esc => app.color_picker.close(),
@@ -180,54 +412,86 @@ impl App {
                         }
                         _ => {}
                     }
-                } else {
-                    // Handle main app events
-                    match key.code {
-                        KeyCode::Esc => app.should_quit = true,
-                        KeyCode::Char('s') => {
-                            if key.modifiers.contains(KeyModifiers::CONTROL) {
-                                // Ctrl+S: Save as new theme with name input
-                                app.name_input.open("Save as New Theme", "Enter theme name");
-                            } else {
-                                // s: Save config to config.toml
-                                if let Err(e) = app.save_config() {
-                                    app.status_message =
-                                        Some(format!("Failed to save config: {}", e));
-                                } else {
-                                    app.status_message =
-                                        Some("Configuration saved to config.toml!".to_string());
+                } else if app.settings.options_editor.is_open {
+                    if let Some(segment) = app.config.segments.get_mut(app.selected_segment) {
+                        let editor = &mut app.settings.options_editor;
+                        let message = if editor.is_editing() {
+                            match key.code {
+                                KeyCode::Enter => editor.finish_edit(segment),
+                                KeyCode::Esc => {
+                                    editor.cancel_edit(segment);
+                                    None
+                                }
+                                KeyCode::Char(c) => {
+                                    editor.input_char(c, segment);
+                                    None
                                 }
+                                KeyCode::Backspace => {
+                                    editor.backspace(segment);
+                                    None
+                                }
+                                _ => None,
                             }
-                        }
-                        KeyCode::Char('w') | KeyCode::Char('W') => {
-                            // w/W: Write config to current theme
-                            app.write_to_current_theme();
-                        }
-                        KeyCode::Up => {
-                            if key.modifiers.contains(KeyModifiers::SHIFT) {
-                                app.move_segment_up();
-                            } else {
-                                app.move_selection(-1);
+                        } else {
+                            match key.code {
+                                KeyCode::Esc | KeyCode::Tab => {
+                                    editor.close();
+                                    None
+                                }
+                                KeyCode::Up => {
+                                    editor.move_selection(-1, segment);
+                                    None
+                                }
+                                KeyCode::Down => {
+                                    editor.move_selection(1, segment);
+                                    None
+                                }
+                                KeyCode::Enter | KeyCode::Char(' ') => editor.activate(segment),
+                                KeyCode::Left => editor.adjust(-1, segment),
+                                KeyCode::Right => editor.adjust(1, segment),
+                                KeyCode::Char('d') | KeyCode::Char('D') => {
+                                    editor.reset_selected(segment)
+                                }
+                                _ => None,
                             }
+                        };
+                        if message.is_some() {
+                            app.status_message = message;
                         }
-                        KeyCode::Down => {
-                            if key.modifiers.contains(KeyModifiers::SHIFT) {
-                                app.move_segment_down();
-                            } else {
-                                app.move_selection(1);
+                        app.preview.update_preview(&app.config);
+                    } else {
+                        app.settings.options_editor.close();
+                    }
+                } else if app.command_palette.is_open {
+                    match key.code {
+                        KeyCode::Esc => app.command_palette.close(),
+                        KeyCode::Enter => {
+                            let command = app.command_palette.selected_command();
+                            app.command_palette.close();
+                            if let Some(command) = command {
+                                history_key = app.run_palette_command(command);
                             }
                         }
-                        KeyCode::Enter => app.toggle_current(),
-                        KeyCode::Tab => app.switch_panel(),
-                        KeyCode::Char('1') => app.switch_to_theme("default"),
-                        KeyCode::Char('2') => app.switch_to_theme("minimal"),
-                        KeyCode::Char('3') => app.switch_to_theme("gruvbox"),
-                        KeyCode::Char('4') => app.switch_to_theme("nord"),
-                        KeyCode::Char('p') => app.cycle_theme(),
-                        KeyCode::Char('r') => app.reset_to_theme_defaults(),
-                        KeyCode::Char('e') | KeyCode::Char('E') => app.open_separator_editor(),
+                        KeyCode::Up => app.command_palette.move_selection(-1),
+                        KeyCode::Down => app.command_palette.move_selection(1),
+                        KeyCode::Backspace => app.command_palette.backspace(),
+                        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app.command_palette.input_char(c)
+                        }
                         _ => {}
                     }
+                } else if let Some(binding) = app.keymap.lookup(&key).cloned() {
+                    // Handle main app events
+                    history_key = app.run_binding(binding);
+                }
+
+                if !history_key && snapshot(&before) != snapshot(&app.config) {
+                    match &group {
+                        Some(group) => app.history.record_in_group(before, group),
+                        None => app.history.record(before),
+                    }
+                } else if group.is_none() {
+                    app.history.end_group();
                 }
             }
 
@@ -238,7 +502,11 @@ impl App {
 
         // Restore terminal
         disable_raw_mode()?;
-        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        execute!(
+            terminal.backend_mut(),
+            LeaveAlternateScreen,
+            DisableMouseCapture
+        )?;
         terminal.show_cursor()?;
 
         result
@@ -282,45 +550,17 @@ impl App {
     }
 
     fn calculate_help_height(&self, total_width: u16) -> u16 {
-        // Use same help_items as in help.render
-        let help_items = if self.color_picker.is_open {
-            vec![
-                "[↑↓] Navigate",
-                "[Tab] Mode",
-                "[Enter] Select",
-                "[Esc] Cancel",
-            ]
-        } else if self.icon_selector.is_open {
-            vec![
-                "[↑↓] Navigate",
-                "[Tab] Style",
-                "[C] Custom",
-                "[Enter] Select",
-                "[Esc] Cancel",
-            ]
-        } else {
-            vec![
-                "[Tab] Switch Panel",
-                "[Enter] Toggle/Edit",
-                "[Shift+↑↓] Reorder",
-                "[1-4] Theme",
-                "[P] Switch Theme",
-                "[R] Reset",
-                "[E] Edit Separator",
-                "[S] Save Config",
-                "[W] Write Theme",
-                "[Ctrl+S] Save Theme",
-                "[Esc] Quit",
-            ]
-        };
+        let help_items = self
+            .help
+            .items(self.color_picker.is_open, self.icon_selector.is_open);
 
         let content_width = total_width.saturating_sub(2); // Remove borders
         let mut lines_needed = 1u16;
         let mut current_width = 0usize;
 
         // Use same logic as help.render for line wrapping
-        for (i, item) in help_items.iter().enumerate() {
-            let item_width = item.chars().count();
+        for (i, (key, description)) in help_items.iter().enumerate() {
+            let item_width = key.chars().count() + description.chars().count() + 1;
             let needs_separator = i > 0 && current_width > 0;
             let separator_width = if needs_separator { 2 } else { 0 };
             let total_width = item_width + separator_width;
@@ -337,9 +577,9 @@ impl App {
             }
         }
 
-        // Add line for status message if present
+        // Add a blank line and the status message if present
         if self.status_message.is_some() {
-            lines_needed += 1;
+            lines_needed += 2;
         }
 
         // Return height: content lines + borders, max 6
@@ -435,6 +675,7 @@ impl App {
             self.status_message.as_deref(),
             self.color_picker.is_open,
             self.icon_selector.is_open,
+            self.is_dirty(),
         );
 
         // Render popups on top
@@ -450,6 +691,17 @@ impl App {
         if self.separator_editor.is_open {
             self.separator_editor.render(f, f.area());
         }
+        if self.text_style_editor.is_open {
+            if let Some(segment) = self.config.segments.get(self.selected_segment) {
+                self.text_style_editor.render(f, f.area(), &segment.styles);
+            }
+        }
+        if self.command_palette.is_open {
+            self.command_palette.render(f, f.area());
+        }
+        if self.conflict_prompt.is_open {
+            self.conflict_prompt.render(f, f.area());
+        }
     }
 
     fn move_selection(&mut self, delta: i32) {
@@ -462,15 +714,16 @@ impl App {
                 self.selected_segment = new_selection;
             }
             Panel::Settings => {
-                let field_count = 7; // Enabled, Icon, IconColor, TextColor, TextStyle, BackgroundColor, Options
+                let field_count = 8; // Enabled, Icon, IconColor, TextColor, SecondaryColor, BackgroundColor, TextStyle, Options
                 let current_field = match self.selected_field {
                     FieldSelection::Enabled => 0i32,
                     FieldSelection::Icon => 1,
                     FieldSelection::IconColor => 2,
                     FieldSelection::TextColor => 3,
-                    FieldSelection::BackgroundColor => 4,
-                    FieldSelection::TextStyle => 5,
-                    FieldSelection::Options => 6,
+                    FieldSelection::SecondaryColor => 4,
+                    FieldSelection::BackgroundColor => 5,
+                    FieldSelection::TextStyle => 6,
+                    FieldSelection::Options => 7,
                 };
                 let new_field = (current_field + delta).clamp(0, field_count - 1) as usize;
                 self.selected_field = match new_field {
@@ -478,9 +731,10 @@ impl App {
                     1 => FieldSelection::Icon,
                     2 => FieldSelection::IconColor,
                     3 => FieldSelection::TextColor,
-                    4 => FieldSelection::BackgroundColor,
-                    5 => FieldSelection::TextStyle,
-                    6 => FieldSelection::Options,
+                    4 => FieldSelection::SecondaryColor,
+                    5 => FieldSelection::BackgroundColor,
+                    6 => FieldSelection::TextStyle,
+                    7 => FieldSelection::Options,
                     _ => FieldSelection::Enabled,
                 };
             }
@@ -493,18 +747,7 @@ impl App {
                 // Toggle segment enabled/disabled in segment list
                 if let Some(segment) = self.config.segments.get_mut(self.selected_segment) {
                     segment.enabled = !segment.enabled;
-                    let segment_name = match segment.id {
-                        SegmentId::Model => "Model",
-                        SegmentId::Directory => "Directory",
-                        SegmentId::Git => "Git",
-                        SegmentId::ContextWindow => "Context Window",
-                        SegmentId::Usage => "Usage",
-                        SegmentId::Cubence => "Cubence",
-                        SegmentId::Cost => "Cost",
-                        SegmentId::Session => "Session",
-                        SegmentId::OutputStyle => "Output Style",
-                        SegmentId::Update => "Update",
-                    };
+                    let segment_name = segment_name(segment.id);
                     let is_enabled = segment.enabled;
                     self.status_message = Some(format!(
                         "{} segment {}",
@@ -521,18 +764,7 @@ impl App {
                         // Toggle enabled state in settings panel too
                         if let Some(segment) = self.config.segments.get_mut(self.selected_segment) {
                             segment.enabled = !segment.enabled;
-                            let segment_name = match segment.id {
-                                SegmentId::Model => "Model",
-                                SegmentId::Directory => "Directory",
-                                SegmentId::Git => "Git",
-                                SegmentId::ContextWindow => "Context Window",
-                                SegmentId::Usage => "Usage",
-                                SegmentId::Cubence => "Cubence",
-                                SegmentId::Cost => "Cost",
-                                SegmentId::Session => "Session",
-                                SegmentId::OutputStyle => "Output Style",
-                                SegmentId::Update => "Update",
-                            };
+                            let segment_name = segment_name(segment.id);
                             let is_enabled = segment.enabled;
                             self.status_message = Some(format!(
                                 "{} segment {}",
@@ -545,26 +777,18 @@ impl App {
                     FieldSelection::Icon => self.open_icon_selector(),
                     FieldSelection::IconColor
                     | FieldSelection::TextColor
+                    | FieldSelection::SecondaryColor
                     | FieldSelection::BackgroundColor => self.open_color_picker(),
-                    FieldSelection::TextStyle => {
-                        // Toggle text bold style
-                        if let Some(segment) = self.config.segments.get_mut(self.selected_segment) {
-                            segment.styles.text_bold = !segment.styles.text_bold;
-                            self.status_message = Some(format!(
-                                "Text bold {}",
-                                if segment.styles.text_bold {
-                                    "enabled"
-                                } else {
-                                    "disabled"
-                                }
-                            ));
-                            self.preview.update_preview(&self.config);
-                        }
-                    }
+                    FieldSelection::TextStyle => self.text_style_editor.open(),
                     FieldSelection::Options => {
-                        // TODO: Implement options editor
-                        self.status_message =
-                            Some("Options editor not implemented yet".to_string());
+                        if let Some(segment) = self.config.segments.get(self.selected_segment) {
+                            if crate::config::options::option_specs(segment.id).is_empty() {
+                                self.status_message =
+                                    Some("This segment has no options".to_string());
+                            } else {
+                                self.settings.options_editor.open();
+                            }
+                        }
                     }
                 }
             }
@@ -582,6 +806,7 @@ impl App {
         if self.selected_panel == Panel::Settings
             && (self.selected_field == FieldSelection::IconColor
                 || self.selected_field == FieldSelection::TextColor
+                || self.selected_field == FieldSelection::SecondaryColor
                 || self.selected_field == FieldSelection::BackgroundColor)
         {
             self.color_picker.open();
@@ -599,6 +824,7 @@ impl App {
             match self.selected_field {
                 FieldSelection::IconColor => segment.colors.icon = Some(color),
                 FieldSelection::TextColor => segment.colors.text = Some(color),
+                FieldSelection::SecondaryColor => segment.colors.secondary = Some(color),
                 FieldSelection::BackgroundColor => segment.colors.background = Some(color),
                 _ => {}
             }
@@ -627,8 +853,95 @@ impl App {
         self.switch_to_theme(next_theme);
     }
 
+    /// Clicks select themes, segments and colors; dragging a segment moves it
+    /// and the wheel scrolls the color and icon popups
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        let before = self.config.clone();
+        let (column, row) = (mouse.column, mouse.row);
+
+        if self.color_picker.is_open {
+            match mouse.kind {
+                // Clicking the selected color again picks it, like Enter
+                MouseEventKind::Down(MouseButton::Left) if self.color_picker.click(column, row) => {
+                    if let Some(color) = self.color_picker.get_selected_color() {
+                        self.apply_selected_color(color);
+                    }
+                    self.color_picker.close();
+                }
+                MouseEventKind::ScrollUp => self.color_picker.move_direction(NavDirection::Up),
+                MouseEventKind::ScrollDown => self.color_picker.move_direction(NavDirection::Down),
+                _ => {}
+            }
+        } else if self.icon_selector.is_open {
+            match mouse.kind {
+                MouseEventKind::ScrollUp => self.icon_selector.move_selection(-1),
+                MouseEventKind::ScrollDown => self.icon_selector.move_selection(1),
+                _ => {}
+            }
+        } else if self.conflict_prompt.is_open
+            || self.name_input.is_open
+            || self.separator_editor.is_open
+            || self.text_style_editor.is_open
+            || self.settings.options_editor.is_open
+            || self.command_palette.is_open
+        {
+            // Keyboard only
+        } else {
+            let count = self.config.segments.len();
+            match mouse.kind {
+                MouseEventKind::Down(MouseButton::Left) => {
+                    if let Some(theme) = self.theme_selector.theme_at(column, row) {
+                        let theme = theme.to_string();
+                        self.switch_to_theme(&theme);
+                    } else if let Some(index) = self.segment_list.segment_at(column, row, count) {
+                        self.selected_segment = index;
+                        self.selected_panel = Panel::SegmentList;
+                        self.drag = Some((index, self.config.clone()));
+                    }
+                }
+                MouseEventKind::Drag(MouseButton::Left) => {
+                    if let Some((from, _)) = self.drag.as_mut() {
+                        let to = self.segment_list.nearest_segment(row, count);
+                        if to != *from && *from < count {
+                            let segment = self.config.segments.remove(*from);
+                            self.config.segments.insert(to, segment);
+                            *from = to;
+                            self.selected_segment = to;
+                            self.preview.update_preview(&self.config);
+                        }
+                    }
+                }
+                MouseEventKind::Up(MouseButton::Left) => self.end_drag(),
+                _ => {}
+            }
+        }
+
+        // A drag becomes one undo step when the button is released
+        if self.drag.is_none() && snapshot(&before) != snapshot(&self.config) {
+            self.history.record(before);
+        }
+    }
+
+    /// Finish a segment drag as one undo step
+    fn end_drag(&mut self) {
+        let Some((to, origin)) = self.drag.take() else {
+            return;
+        };
+        if snapshot(&origin) != snapshot(&self.config) {
+            self.history.record(origin);
+            if let Some(segment) = self.config.segments.get(to) {
+                self.status_message = Some(format!(
+                    "Moved {} segment to position {}",
+                    segment_name(segment.id),
+                    to + 1
+                ));
+            }
+        }
+    }
+
     fn switch_to_theme(&mut self, theme_name: &str) {
-        self.config = crate::ui::themes::ThemePresets::get_theme(theme_name);
+        let theme = crate::ui::themes::ThemePresets::get_theme(theme_name);
+        self.config = with_keymap_of(theme, &self.config);
         self.selected_segment = 0;
         self.preview.update_preview(&self.config);
         self.status_message = Some(format!("Switched to {} theme", theme_name));
@@ -637,7 +950,8 @@ impl App {
     /// Reset current theme to its default configuration
     fn reset_to_theme_defaults(&mut self) {
         let current_theme = self.config.theme.clone();
-        self.config = crate::ui::themes::ThemePresets::get_theme(&current_theme);
+        let theme = crate::ui::themes::ThemePresets::get_theme(&current_theme);
+        self.config = with_keymap_of(theme, &self.config);
         self.selected_segment = 0;
         self.preview.update_preview(&self.config);
         self.status_message = Some(format!("Reset {} theme to defaults", current_theme));
@@ -645,6 +959,7 @@ impl App {
 
     fn save_config(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         self.config.save()?;
+        self.mark_saved();
         Ok(())
     }
 
@@ -659,6 +974,166 @@ impl App {
         }
     }
 
+    /// Move the currently selected segment between the left and right groups
+    fn toggle_segment_align(&mut self) {
+        if self.selected_panel != Panel::SegmentList {
+            return;
+        }
+        if let Some(segment) = self.config.segments.get_mut(self.selected_segment) {
+            segment.align = match segment.align {
+                SegmentAlign::Left => SegmentAlign::Right,
+                SegmentAlign::Right => SegmentAlign::Left,
+            };
+            let side = if segment.align.is_left() {
+                "left"
+            } else {
+                "right"
+            };
+            self.status_message = Some(format!("Segment aligned {}", side));
+            self.preview.update_preview(&self.config);
+        }
+    }
+
+    /// Do what a key is bound to. Returns true for undo/redo, which manage
+    /// the history themselves.
+    fn run_binding(&mut self, binding: Binding) -> bool {
+        let action = match binding {
+            Binding::Action(action) => action,
+            Binding::Theme(theme) => {
+                self.switch_to_theme(&theme);
+                return false;
+            }
+            Binding::Unbound => return false,
+        };
+
+        match action {
+            Action::Up => self.move_selection(-1),
+            Action::Down => self.move_selection(1),
+            Action::MoveSegmentUp => self.move_segment_up(),
+            Action::MoveSegmentDown => self.move_segment_down(),
+            Action::Toggle => self.toggle_current(),
+            Action::SwitchPanel => self.switch_panel(),
+            Action::NextTheme => self.cycle_theme(),
+            Action::ResetTheme => self.reset_to_theme_defaults(),
+            Action::Undo => self.undo(),
+            Action::Redo => self.redo(),
+            Action::EditSeparator => self.open_separator_editor(),
+            Action::ToggleAlign => self.toggle_segment_align(),
+            Action::LivePreview => self.toggle_live_preview(),
+            Action::CommandPalette => self.open_command_palette(),
+            Action::SaveConfig => {
+                if let Err(e) = self.save_config() {
+                    self.status_message = Some(format!("Failed to save config: {}", e));
+                } else {
+                    self.status_message = Some(format!(
+                        "Configuration saved to {}!",
+                        Config::get_config_path().display()
+                    ));
+                }
+            }
+            // Write config to current theme
+            Action::WriteTheme => self.write_to_current_theme(),
+            // Save as new theme with name input
+            Action::SaveThemeAs => self
+                .name_input
+                .open("Save as New Theme", "Enter theme name"),
+            Action::Quit => self.should_quit = true,
+        }
+        matches!(action, Action::Undo | Action::Redo)
+    }
+
+    fn open_command_palette(&mut self) {
+        let mut entries: Vec<PaletteEntry> = Action::ALL
+            .iter()
+            .filter(|action| **action != Action::CommandPalette)
+            .map(|action| {
+                let binding = Binding::Action(*action);
+                PaletteEntry::new(
+                    "Action",
+                    action.description(),
+                    PaletteCommand::Run(binding.clone()),
+                )
+                .with_hint(join_keys(&self.keymap.keys_for(&binding)))
+            })
+            .collect();
+
+        for theme in crate::ui::themes::ThemePresets::list_available_themes() {
+            let binding = Binding::Theme(theme.clone());
+            let hint = join_keys(&self.keymap.keys_for(&binding));
+            let hint = if theme == self.config.theme {
+                format!("current {}", hint).trim_end().to_string()
+            } else {
+                hint
+            };
+            entries.push(
+                PaletteEntry::new("Theme", theme, PaletteCommand::Run(binding)).with_hint(hint),
+            );
+        }
+
+        for (index, segment) in self.config.segments.iter().enumerate() {
+            let state = if segment.enabled { "on" } else { "off" };
+            entries.push(
+                PaletteEntry::new(
+                    "Segment",
+                    segment_name(segment.id),
+                    PaletteCommand::SelectSegment(index),
+                )
+                .with_hint(state),
+            );
+        }
+
+        for (style, icon, name) in icon_catalog() {
+            let nerd_font = style == IconStyle::NerdFont;
+            entries.push(
+                PaletteEntry::new(
+                    "Icon",
+                    format!("{} {}", icon, name),
+                    PaletteCommand::SetIcon {
+                        icon: icon.to_string(),
+                        nerd_font,
+                    },
+                )
+                .with_hint(if nerd_font { "nerd font" } else { "plain" }),
+            );
+        }
+
+        self.command_palette.open(entries);
+    }
+
+    /// Returns true for undo/redo, like `run_binding`
+    fn run_palette_command(&mut self, command: PaletteCommand) -> bool {
+        match command {
+            PaletteCommand::Run(binding) => return self.run_binding(binding),
+            PaletteCommand::SelectSegment(index) => {
+                self.selected_segment = index.min(self.config.segments.len().saturating_sub(1));
+                self.selected_panel = Panel::SegmentList;
+                if let Some(segment) = self.config.segments.get(self.selected_segment) {
+                    self.status_message =
+                        Some(format!("Selected {} segment", segment_name(segment.id)));
+                }
+            }
+            PaletteCommand::SetIcon { icon, nerd_font } => {
+                if let Some(segment) = self.config.segments.get_mut(self.selected_segment) {
+                    let name = segment_name(segment.id);
+                    if nerd_font {
+                        segment.icon.nerd_font = icon.clone();
+                    } else {
+                        segment.icon.plain = icon.clone();
+                    }
+                    self.status_message = Some(format!("Set {} icon to {}", name, icon));
+                    self.preview.update_preview(&self.config);
+                }
+            }
+        }
+        false
+    }
+
+    /// Switch the preview between sample data and this machine's real data
+    fn toggle_live_preview(&mut self) {
+        self.status_message = Some(self.preview.toggle_live());
+        self.preview.update_preview(&self.config);
+    }
+
     /// Move the currently selected segment down in the list
     fn move_segment_down(&mut self) {
         if self.selected_panel == Panel::SegmentList
@@ -678,6 +1153,7 @@ impl App {
         match crate::ui::themes::ThemePresets::save_theme(current_theme, &self.config) {
             Ok(_) => {
                 self.status_message = Some(format!("Wrote config to theme: {}", current_theme));
+                self.mark_written();
             }
             Err(e) => {
                 self.status_message =
@@ -693,6 +1169,7 @@ impl App {
                 // Update current theme to the new one
                 self.config.theme = theme_name.to_string();
                 self.status_message = Some(format!("Saved as new theme: {}", theme_name));
+                self.mark_written();
             }
             Err(e) => {
                 self.status_message = Some(format!("Failed to save theme {}: {}", theme_name, e));
@@ -706,3 +1183,30 @@ impl App {
         self.separator_editor.open(&self.config.style.separator);
     }
 }
+
+/// Comparable form of a configuration
+fn snapshot(config: &Config) -> Option<toml::Value> {
+    toml::Value::try_from(config).ok()
+}
+
+/// `config` with the key bindings of `current`, which themes don't carry
+fn with_keymap_of(mut config: Config, current: &Config) -> Config {
+    config.keymap = current.keymap.clone();
+    config
+}
+
+fn segment_name(id: SegmentId) -> &'static str {
+    match id {
+        SegmentId::Model => "Model",
+        SegmentId::Directory => "Directory",
+        SegmentId::Git => "Git",
+        SegmentId::ContextWindow => "Context Window",
+        SegmentId::Usage => "Usage",
+        SegmentId::Cubence => "Cubence",
+        SegmentId::Cost => "Cost",
+        SegmentId::Session => "Session",
+        SegmentId::OutputStyle => "Output Style",
+        SegmentId::Update => "Update",
+        SegmentId::Language => "Language",
+    }
+}
@@ -1,6 +1,6 @@
 use crate::config::AnsiColor;
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Constraint, Direction, Layout, Position, Rect},
     style::{Color, Style},
     widgets::{
         Block, Borders, Clear, ListState, Paragraph, Scrollbar, ScrollbarOrientation,
@@ -38,6 +38,8 @@ pub struct ColorPickerComponent {
     // Cache columns per row for navigation
     pub cached_basic_cols: usize,
     pub cached_extended_cols: usize,
+    /// Where each color of the current mode was last drawn, for mouse clicks
+    swatches: Vec<(Rect, usize)>,
 }
 
 #[derive(Debug, Clone)]
@@ -83,6 +85,7 @@ impl ColorPickerComponent {
             basic_scrollbar_state: ScrollbarState::new(16),
             cached_basic_cols: 4,
             cached_extended_cols: 16,
+            swatches: Vec::new(),
         }
     }
 
@@ -345,6 +348,28 @@ impl ColorPickerComponent {
         }
     }
 
+    /// Select the color under the mouse. Returns true when it was already
+    /// selected, so a second click picks it.
+    pub fn click(&mut self, column: u16, row: u16) -> bool {
+        let Some(index) = self
+            .swatches
+            .iter()
+            .find(|(area, _)| area.contains(Position::new(column, row)))
+            .map(|(_, index)| *index)
+        else {
+            return false;
+        };
+        let current = match self.mode {
+            ColorPickerMode::Basic16 => self.selected_basic,
+            ColorPickerMode::Extended256 => self.selected_extended,
+            ColorPickerMode::RgbInput => return false,
+        };
+
+        let before = self.current_color.clone();
+        self.move_selection(index as i32 - current as i32);
+        before.is_some() && before == self.current_color
+    }
+
     pub fn get_selected_color(&self) -> Option<AnsiColor> {
         self.current_color.clone()
     }
@@ -355,6 +380,7 @@ impl ColorPickerComponent {
         }
 
         let popup_area = centered_rect(70, 75, area);
+        self.swatches.clear();
 
         // Clear the popup area first
         f.render_widget(Clear, popup_area);
@@ -448,6 +474,7 @@ impl ColorPickerComponent {
                 width: 6, // "[ ██ ]"
                 height: 1,
             };
+            self.swatches.push((item_area, color_index));
 
             let is_selected = color_index == self.selected_basic;
             let color = ansi_to_ratatui_color(color_index as u8);
@@ -551,6 +578,7 @@ impl ColorPickerComponent {
                 width: 7, // "[ ██ ]" or "  ██  "
                 height: 1,
             };
+            self.swatches.push((item_area, color_index));
 
             let is_selected = color_index == self.selected_extended;
             let color = Color::Indexed(color_index as u8);
@@ -0,0 +1,270 @@
+use crate::ui::keymap::Binding;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// What picking a palette entry does
+#[derive(Debug, Clone, PartialEq)]
+pub enum PaletteCommand {
+    Run(Binding),
+    SelectSegment(usize),
+    /// Set the selected segment's plain or Nerd Font icon
+    SetIcon {
+        icon: String,
+        nerd_font: bool,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct PaletteEntry {
+    /// Searched together with the label, e.g. "Theme"
+    pub category: &'static str,
+    pub label: String,
+    /// Shown dimmed on the right, e.g. the key bound to an action
+    pub hint: String,
+    pub command: PaletteCommand,
+}
+
+impl PaletteEntry {
+    pub fn new(category: &'static str, label: impl Into<String>, command: PaletteCommand) -> Self {
+        Self {
+            category,
+            label: label.into(),
+            hint: String::new(),
+            command,
+        }
+    }
+
+    pub fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = hint.into();
+        self
+    }
+
+    fn text(&self) -> String {
+        format!("{}: {}", self.category, self.label)
+    }
+}
+
+/// Ctrl+P popup: fuzzy search across actions, themes, segments and icons
+#[derive(Debug, Clone, Default)]
+pub struct CommandPaletteComponent {
+    pub is_open: bool,
+    pub query: String,
+    entries: Vec<PaletteEntry>,
+    /// Entries matching the query, best first, with the matched character positions
+    matches: Vec<(usize, Vec<usize>)>,
+    selected: usize,
+}
+
+impl CommandPaletteComponent {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, entries: Vec<PaletteEntry>) {
+        self.is_open = true;
+        self.query.clear();
+        self.entries = entries;
+        self.filter();
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+        self.query.clear();
+        self.entries.clear();
+        self.matches.clear();
+    }
+
+    pub fn input_char(&mut self, c: char) {
+        self.query.push(c);
+        self.filter();
+    }
+
+    pub fn backspace(&mut self) {
+        self.query.pop();
+        self.filter();
+    }
+
+    pub fn move_selection(&mut self, delta: i32) {
+        let count = self.matches.len() as i32;
+        if count > 0 {
+            self.selected = (self.selected as i32 + delta).rem_euclid(count) as usize;
+        }
+    }
+
+    /// Labels of the matching entries, best first
+    pub fn results(&self) -> Vec<&str> {
+        self.matches
+            .iter()
+            .map(|(index, _)| self.entries[*index].label.as_str())
+            .collect()
+    }
+
+    pub fn selected_command(&self) -> Option<PaletteCommand> {
+        let (index, _) = self.matches.get(self.selected)?;
+        Some(self.entries[*index].command.clone())
+    }
+
+    fn filter(&mut self) {
+        let mut scored: Vec<(i32, usize, Vec<usize>)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                let (score, positions) = fuzzy_match(&self.query, &entry.text())?;
+                Some((score, index, positions))
+            })
+            .collect();
+        // Stable, so equal scores keep the entries' order
+        scored.sort_by_key(|(score, _, _)| std::cmp::Reverse(*score));
+        self.matches = scored
+            .into_iter()
+            .map(|(_, index, positions)| (index, positions))
+            .collect();
+        self.selected = 0;
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect) {
+        if !self.is_open {
+            return;
+        }
+
+        let popup_width = 72_u16.min(area.width.saturating_sub(4));
+        let popup_height = 20_u16.min(area.height.saturating_sub(2));
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(popup_width)) / 2,
+            y: (area.height.saturating_sub(popup_height)) / 3,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        f.render_widget(Clear, popup_area);
+
+        let popup_block = Block::default()
+            .borders(Borders::ALL)
+            .title("Command Palette")
+            .border_style(Style::default().fg(Color::Cyan));
+        let inner = popup_block.inner(popup_area);
+        f.render_widget(popup_block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Query
+                Constraint::Min(1),    // Results
+                Constraint::Length(1), // Keys
+            ])
+            .split(inner);
+
+        let query = if self.query.is_empty() {
+            Line::from(Span::styled(
+                "Type to search actions, themes, segments and icons",
+                Style::default().fg(Color::DarkGray),
+            ))
+        } else {
+            Line::from(Span::styled(
+                format!("{}▏", self.query),
+                Style::default().fg(Color::Yellow),
+            ))
+        };
+        f.render_widget(
+            Paragraph::new(query).block(Block::default().borders(Borders::ALL)),
+            chunks[0],
+        );
+
+        let items: Vec<ListItem> = self
+            .matches
+            .iter()
+            .map(|(index, positions)| {
+                let entry = &self.entries[*index];
+                let mut spans: Vec<Span> = entry
+                    .text()
+                    .chars()
+                    .enumerate()
+                    .map(|(i, c)| {
+                        let style = if positions.contains(&i) {
+                            Style::default()
+                                .fg(Color::Yellow)
+                                .add_modifier(Modifier::BOLD)
+                        } else if i < entry.category.chars().count() + 1 {
+                            Style::default().fg(Color::DarkGray)
+                        } else {
+                            Style::default()
+                        };
+                        Span::styled(c.to_string(), style)
+                    })
+                    .collect();
+                if !entry.hint.is_empty() {
+                    spans.push(Span::styled(
+                        format!("  {}", entry.hint),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        if items.is_empty() {
+            f.render_widget(
+                Paragraph::new(Span::styled(
+                    " No matches",
+                    Style::default().fg(Color::DarkGray),
+                )),
+                chunks[1],
+            );
+        } else {
+            let mut state = ListState::default().with_selected(Some(self.selected));
+            f.render_stateful_widget(
+                List::new(items)
+                    .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+                    .highlight_symbol("▶ "),
+                chunks[1],
+                &mut state,
+            );
+        }
+
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                "[↑↓] Select  [Enter] Run  [Esc] Close",
+                Style::default().fg(Color::DarkGray),
+            )),
+            chunks[2],
+        );
+    }
+}
+
+/// Match every whitespace-separated word of `query` as a case-insensitive
+/// subsequence of `text`. Higher scores for matches at word starts and runs
+/// of consecutive characters; `None` when a word doesn't match.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<(i32, Vec<usize>)> {
+    let text: Vec<char> = text.chars().map(|c| c.to_ascii_lowercase()).collect();
+    let mut score = 0;
+    let mut positions = Vec::new();
+
+    for word in query.split_whitespace() {
+        let mut from = 0;
+        let mut previous: Option<usize> = None;
+        for c in word.chars().map(|c| c.to_ascii_lowercase()) {
+            let found = from + text[from..].iter().position(|t| *t == c)?;
+            score += 1;
+            if previous == Some(found.wrapping_sub(1)) {
+                score += 5;
+            }
+            if found == 0 || !text[found - 1].is_alphanumeric() {
+                score += 8;
+            }
+            score -= (found - from).min(10) as i32 / 2;
+            positions.push(found);
+            previous = Some(found);
+            from = found + 1;
+        }
+    }
+
+    positions.sort_unstable();
+    positions.dedup();
+    Some((score, positions))
+}
@@ -0,0 +1,151 @@
+use crate::config::Config;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Shown when a file the configurator loaded changes on disk while the TUI
+/// has unsaved edits
+#[derive(Debug, Clone, Default)]
+pub struct ConflictPromptComponent {
+    pub is_open: bool,
+    /// The file that changed
+    pub file: String,
+    /// The configuration as it is on disk now
+    theirs: Option<Config>,
+    diff: Vec<String>,
+    pub show_diff: bool,
+    scroll: usize,
+}
+
+impl ConflictPromptComponent {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, file: &str, mine: &Config, theirs: Config) {
+        let mine_text = toml::to_string_pretty(mine).unwrap_or_default();
+        let theirs_text = toml::to_string_pretty(&theirs).unwrap_or_default();
+        self.diff = similar::TextDiff::from_lines(&mine_text, &theirs_text)
+            .unified_diff()
+            .context_radius(2)
+            .header("mine (unsaved)", &format!("{} (on disk)", file))
+            .to_string()
+            .lines()
+            .map(str::to_string)
+            .collect();
+
+        self.is_open = true;
+        self.file = file.to_string();
+        self.theirs = Some(theirs);
+        self.show_diff = false;
+        self.scroll = 0;
+    }
+
+    /// Close the prompt, keeping the TUI's edits
+    pub fn close(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Close the prompt and return the configuration on disk
+    pub fn take_theirs(&mut self) -> Option<Config> {
+        let theirs = self.theirs.take();
+        self.close();
+        theirs
+    }
+
+    pub fn toggle_diff(&mut self) {
+        self.show_diff = !self.show_diff;
+        self.scroll = 0;
+    }
+
+    pub fn scroll(&mut self, delta: i32) {
+        let max = self.diff.len().saturating_sub(1) as i32;
+        self.scroll = (self.scroll as i32 + delta).clamp(0, max) as usize;
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect) {
+        if !self.is_open {
+            return;
+        }
+
+        let popup_width = if self.show_diff { 100 } else { 64 }.min(area.width.saturating_sub(4));
+        let popup_height = if self.show_diff { 30 } else { 9 }.min(area.height.saturating_sub(2));
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(popup_width)) / 2,
+            y: (area.height.saturating_sub(popup_height)) / 2,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        f.render_widget(Clear, popup_area);
+
+        let popup_block = Block::default()
+            .borders(Borders::ALL)
+            .title("Changed on Disk")
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = popup_block.inner(popup_area);
+        f.render_widget(popup_block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),    // Message or diff
+                Constraint::Length(3), // Actions
+            ])
+            .split(inner);
+
+        if self.show_diff {
+            let lines: Vec<Line> = self
+                .diff
+                .iter()
+                .skip(self.scroll)
+                .map(|line| {
+                    let color = if line.starts_with("@@") {
+                        Color::Cyan
+                    } else if line.starts_with('+') {
+                        Color::Green
+                    } else if line.starts_with('-') {
+                        Color::Red
+                    } else {
+                        Color::Gray
+                    };
+                    Line::from(Span::styled(line.as_str(), Style::default().fg(color)))
+                })
+                .collect();
+            f.render_widget(
+                Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(format!(
+                    "Diff {}/{}",
+                    self.scroll + 1,
+                    self.diff.len().max(1)
+                ))),
+                chunks[0],
+            );
+        } else {
+            let message = vec![
+                Line::from(Span::styled(
+                    self.file.as_str(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+                Line::from("was changed by another program, and you have unsaved edits."),
+            ];
+            f.render_widget(
+                Paragraph::new(message).wrap(Wrap { trim: false }),
+                chunks[0],
+            );
+        }
+
+        let actions = if self.show_diff {
+            "[K] Keep Mine  [T] Take Theirs  [D] Hide Diff  [↑↓] Scroll"
+        } else {
+            "[K] Keep Mine  [T] Take Theirs  [D] Diff"
+        };
+        f.render_widget(
+            Paragraph::new(actions).block(Block::default().borders(Borders::ALL)),
+            chunks[1],
+        );
+    }
+}
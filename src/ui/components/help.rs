@@ -1,3 +1,4 @@
+use crate::ui::keymap::{join_keys, Action, Binding, Keymap};
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
@@ -6,31 +7,83 @@ use ratatui::{
     Frame,
 };
 
-#[derive(Default)]
-pub struct HelpComponent;
+/// Main screen shortcuts, each listing every key bound to its actions
+const MAIN_ITEMS: &[(&[Action], &str)] = &[
+    (&[Action::SwitchPanel], "Switch Panel"),
+    (&[Action::Toggle], "Toggle/Edit"),
+    (&[Action::MoveSegmentUp, Action::MoveSegmentDown], "Reorder"),
+    (&[], "Theme"),
+    (&[Action::NextTheme], "Switch Theme"),
+    (&[Action::ResetTheme], "Reset"),
+    (&[Action::Undo], "Undo"),
+    (&[Action::Redo], "Redo"),
+    (&[Action::EditSeparator], "Edit Separator"),
+    (&[Action::ToggleAlign], "Align"),
+    (&[Action::LivePreview], "Live Preview"),
+    (&[Action::CommandPalette], "Commands"),
+    (&[Action::SaveConfig], "Save Config"),
+    (&[Action::WriteTheme], "Write Theme"),
+    (&[Action::SaveThemeAs], "Save Theme"),
+    (&[Action::Quit], "Quit"),
+];
+
+pub struct HelpComponent {
+    main_items: Vec<(String, &'static str)>,
+}
+
+impl Default for HelpComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl HelpComponent {
     pub fn new() -> Self {
-        Self
+        let mut help = Self {
+            main_items: Vec::new(),
+        };
+        help.set_keymap(&Keymap::default());
+        help
     }
 
-    pub fn render(
+    /// Show the keys of `keymap` for the main screen; unbound actions are left out
+    pub fn set_keymap(&mut self, keymap: &Keymap) {
+        self.main_items = MAIN_ITEMS
+            .iter()
+            .filter_map(|(actions, description)| {
+                let keys: Vec<_> = if actions.is_empty() {
+                    // The "Theme" item: keys bound to a specific theme
+                    keymap
+                        .theme_keys()
+                        .into_iter()
+                        .map(|(key, _)| key)
+                        .collect()
+                } else {
+                    actions
+                        .iter()
+                        .flat_map(|action| keymap.keys_for(&Binding::Action(*action)))
+                        .collect()
+                };
+                (!keys.is_empty()).then(|| (format!("[{}]", join_keys(&keys)), *description))
+            })
+            .collect();
+    }
+
+    /// Shortcuts shown for the current screen or popup
+    pub fn items(
         &self,
-        f: &mut Frame,
-        area: Rect,
-        status_message: Option<&str>,
         color_picker_open: bool,
         icon_selector_open: bool,
-    ) {
-        let help_items = if color_picker_open {
-            vec![
+    ) -> Vec<(String, &'static str)> {
+        let fixed: &[(&str, &'static str)] = if color_picker_open {
+            &[
                 ("[↑↓]", "Navigate"),
                 ("[Tab]", "Mode"),
                 ("[Enter]", "Select"),
                 ("[Esc]", "Cancel"),
             ]
         } else if icon_selector_open {
-            vec![
+            &[
                 ("[↑↓]", "Navigate"),
                 ("[Tab]", "Style"),
                 ("[C]", "Custom"),
@@ -38,20 +91,24 @@ impl HelpComponent {
                 ("[Esc]", "Cancel"),
             ]
         } else {
-            vec![
-                ("[Tab]", "Switch Panel"),
-                ("[Enter]", "Toggle/Edit"),
-                ("[Shift+↑↓]", "Reorder"),
-                ("[1-4]", "Theme"),
-                ("[P]", "Switch Theme"),
-                ("[R]", "Reset"),
-                ("[E]", "Edit Separator"),
-                ("[S]", "Save Config"),
-                ("[W]", "Write Theme"),
-                ("[Ctrl+S]", "Save Theme"),
-                ("[Esc]", "Quit"),
-            ]
+            return self.main_items.clone();
         };
+        fixed
+            .iter()
+            .map(|(key, description)| (key.to_string(), *description))
+            .collect()
+    }
+
+    pub fn render(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        status_message: Option<&str>,
+        color_picker_open: bool,
+        icon_selector_open: bool,
+        dirty: bool,
+    ) {
+        let help_items = self.items(color_picker_open, icon_selector_open);
 
         let status = status_message.unwrap_or("");
 
@@ -80,7 +137,7 @@ impl HelpComponent {
 
                 // Add highlighted key and description
                 current_line_spans.push(Span::styled(
-                    *key,
+                    key.as_str(),
                     Style::default()
                         .fg(Color::Yellow)
                         .add_modifier(Modifier::BOLD),
@@ -99,7 +156,7 @@ impl HelpComponent {
 
                 // Start new line with this item
                 current_line_spans.push(Span::styled(
-                    *key,
+                    key.as_str(),
                     Style::default()
                         .fg(Color::Yellow)
                         .add_modifier(Modifier::BOLD),
@@ -127,8 +184,25 @@ impl HelpComponent {
         }
 
         let help_text = Text::from(lines);
+        // Unsaved edits, compared with the file the config was loaded from
+        let title = if dirty {
+            Line::from(vec![
+                Span::raw("Help "),
+                Span::styled(
+                    "● Modified",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ])
+        } else {
+            Line::from(vec![
+                Span::raw("Help "),
+                Span::styled("✓ Saved", Style::default().fg(Color::Green)),
+            ])
+        };
         let help_paragraph = Paragraph::new(help_text)
-            .block(Block::default().borders(Borders::ALL).title("Help"))
+            .block(Block::default().borders(Borders::ALL).title(title))
             .wrap(ratatui::widgets::Wrap { trim: false });
         f.render_widget(help_paragraph, area);
     }
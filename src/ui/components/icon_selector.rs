@@ -334,6 +334,17 @@ impl IconSelectorComponent {
     }
 }
 
+/// Every icon the selector offers, as (style, icon, name)
+pub fn icon_catalog() -> Vec<(IconStyle, &'static str, &'static str)> {
+    let plain = get_plain_icons()
+        .into_iter()
+        .map(|info| (IconStyle::Plain, info.icon, info.name));
+    let nerd = get_nerd_font_icons()
+        .into_iter()
+        .map(|info| (IconStyle::NerdFont, info.icon, info.name));
+    plain.chain(nerd).collect()
+}
+
 #[derive(Debug, Clone)]
 struct IconInfo {
     icon: &'static str,
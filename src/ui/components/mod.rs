@@ -1,10 +1,14 @@
 pub mod color_picker;
+pub mod command_palette;
+pub mod conflict_prompt;
 pub mod editor;
 pub mod help;
 pub mod icon_selector;
 pub mod name_input;
+pub mod options_editor;
 pub mod preview;
 pub mod segment_list;
 pub mod separator_editor;
 pub mod settings;
+pub mod text_style_editor;
 pub mod theme_selector;
@@ -0,0 +1,291 @@
+use crate::config::options::{option_specs, OptionKind, OptionSpec};
+use crate::config::SegmentConfig;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+use serde_json::Value;
+
+/// Inline editor for a segment's options, drawn inside the settings panel.
+/// Lists the options the segment understands, then any unknown keys found in
+/// its `options` table (read-only).
+#[derive(Debug, Clone, Default)]
+pub struct OptionsEditorComponent {
+    pub is_open: bool,
+    pub selected: usize,
+    /// Text typed for a number or string option, and the value to restore on Esc
+    editing: Option<(String, Option<Value>)>,
+}
+
+impl OptionsEditorComponent {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self) {
+        self.is_open = true;
+        self.selected = 0;
+        self.editing = None;
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+        self.editing = None;
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.editing.is_some()
+    }
+
+    /// Key of the option being typed into
+    pub fn editing_key(&self, segment: &SegmentConfig) -> Option<&'static str> {
+        self.editing.as_ref()?;
+        self.spec(segment).map(|spec| spec.key)
+    }
+
+    pub fn move_selection(&mut self, delta: i32, segment: &SegmentConfig) {
+        let count = option_specs(segment.id).len() as i32;
+        if count > 0 {
+            self.selected = (self.selected as i32 + delta).clamp(0, count - 1) as usize;
+        }
+    }
+
+    fn spec(&self, segment: &SegmentConfig) -> Option<&'static OptionSpec> {
+        option_specs(segment.id).get(self.selected)
+    }
+
+    /// Enter/Space: toggle a boolean, cycle a choice, or start typing a
+    /// number or string. Returns a status message when something changed.
+    pub fn activate(&mut self, segment: &mut SegmentConfig) -> Option<String> {
+        let spec = self.spec(segment)?;
+        match spec.kind {
+            OptionKind::Bool { .. } => {
+                let value = !current_value(segment, spec).as_bool().unwrap_or(false);
+                Some(set_option(segment, spec, value.into()))
+            }
+            OptionKind::Choice { .. } => self.adjust(1, segment),
+            OptionKind::Integer { .. } | OptionKind::String { .. } => {
+                let buffer = match current_value(segment, spec) {
+                    Value::String(s) => s,
+                    other => other.to_string(),
+                };
+                self.editing = Some((buffer, segment.options.get(spec.key).cloned()));
+                None
+            }
+        }
+    }
+
+    /// Left/Right: previous/next choice, or decrement/increment a number
+    pub fn adjust(&mut self, delta: i64, segment: &mut SegmentConfig) -> Option<String> {
+        let spec = self.spec(segment)?;
+        let value = match spec.kind {
+            OptionKind::Choice { choices, .. } => {
+                let current = current_value(segment, spec);
+                let index = choices
+                    .iter()
+                    .position(|c| current.as_str().is_some_and(|v| c.eq_ignore_ascii_case(v)))
+                    .unwrap_or(0) as i64;
+                let next = (index + delta).rem_euclid(choices.len() as i64) as usize;
+                Value::from(choices[next])
+            }
+            OptionKind::Integer { .. } => {
+                let current = current_value(segment, spec).as_u64().unwrap_or(0);
+                Value::from(current.saturating_add_signed(delta))
+            }
+            OptionKind::Bool { .. } | OptionKind::String { .. } => return None,
+        };
+        Some(set_option(segment, spec, value))
+    }
+
+    /// Set the selected option back to its documented default
+    pub fn reset_selected(&mut self, segment: &mut SegmentConfig) -> Option<String> {
+        let spec = self.spec(segment)?;
+        Some(set_option(segment, spec, spec.default_value()))
+    }
+
+    /// Type into the option being edited; valid input applies immediately
+    pub fn input_char(&mut self, c: char, segment: &mut SegmentConfig) {
+        let Some(spec) = self.spec(segment) else {
+            return;
+        };
+        let Some((buffer, _)) = self.editing.as_mut() else {
+            return;
+        };
+        if matches!(spec.kind, OptionKind::Integer { .. }) && !c.is_ascii_digit() {
+            return;
+        }
+        buffer.push(c);
+        self.apply_buffer(segment);
+    }
+
+    pub fn backspace(&mut self, segment: &mut SegmentConfig) {
+        if let Some((buffer, _)) = self.editing.as_mut() {
+            buffer.pop();
+            self.apply_buffer(segment);
+        }
+    }
+
+    fn apply_buffer(&self, segment: &mut SegmentConfig) {
+        let (Some(spec), Some((buffer, _))) = (self.spec(segment), self.editing.as_ref()) else {
+            return;
+        };
+        if let Some(value) = parse_input(spec, buffer) {
+            segment.options.insert(spec.key.to_string(), value);
+        }
+    }
+
+    /// Enter while typing: keep the typed value, or restore the old one if
+    /// the input isn't valid
+    pub fn finish_edit(&mut self, segment: &mut SegmentConfig) -> Option<String> {
+        let spec = self.spec(segment)?;
+        let (buffer, original) = self.editing.take()?;
+        match parse_input(spec, &buffer) {
+            Some(value) => Some(set_option(segment, spec, value)),
+            None => {
+                restore(segment, spec, original);
+                Some(format!("{}: expected {}", spec.key, spec.type_name()))
+            }
+        }
+    }
+
+    /// Esc while typing: restore the value from before editing
+    pub fn cancel_edit(&mut self, segment: &mut SegmentConfig) {
+        if let (Some(spec), Some((_, original))) = (self.spec(segment), self.editing.take()) {
+            restore(segment, spec, original);
+        }
+    }
+
+    /// Rows drawn under the Options field of the settings panel
+    pub fn lines(&self, segment: &SegmentConfig) -> Vec<Line<'static>> {
+        let specs = option_specs(segment.id);
+        let mut lines = Vec::new();
+
+        if specs.is_empty() && segment.options.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "     (no options)",
+                Style::default().fg(Color::DarkGray),
+            )));
+            return lines;
+        }
+
+        for (i, spec) in specs.iter().enumerate() {
+            let selected = self.is_open && i == self.selected;
+            let value = match (&self.editing, selected) {
+                (Some((buffer, _)), true) => format!("{}▏", buffer),
+                _ => format_value(&current_value(segment, spec)),
+            };
+            let marker = if selected { "   ▶ " } else { "     " };
+            let key_style = if selected {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let value_style = if selected && self.editing.is_some() {
+                Style::default().fg(Color::Black).bg(Color::Yellow)
+            } else {
+                Style::default().fg(Color::Cyan)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(marker.to_string(), Style::default().fg(Color::Cyan)),
+                Span::styled(format!("{} = ", spec.key), key_style),
+                Span::styled(value, value_style),
+                Span::styled(
+                    format!(
+                        "  ({}, default {})",
+                        short_type(spec),
+                        format_value(&spec.default_value())
+                    ),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+
+        let mut unknown: Vec<&String> = segment
+            .options
+            .keys()
+            .filter(|key| !specs.iter().any(|spec| spec.key == key.as_str()))
+            .collect();
+        unknown.sort();
+        for key in unknown {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "     {} = {}  (unknown option)",
+                    key,
+                    format_value(&segment.options[key])
+                ),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+
+        if let Some(spec) = specs.get(self.selected).filter(|_| self.is_open) {
+            let hint = if self.editing.is_some() {
+                "[Enter] Apply  [Esc] Cancel"
+            } else {
+                match spec.kind {
+                    OptionKind::Bool { .. } => "[Enter] Toggle  [D] Default  [Esc] Done",
+                    OptionKind::Choice { .. } => "[Enter/←→] Cycle  [D] Default  [Esc] Done",
+                    OptionKind::Integer { .. } => "[Enter] Edit  [←→] ∓1  [D] Default  [Esc] Done",
+                    OptionKind::String { .. } => "[Enter] Edit  [D] Default  [Esc] Done",
+                }
+            };
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("     {}", spec.description),
+                Style::default().fg(Color::Gray),
+            )));
+            lines.push(Line::from(Span::styled(
+                format!("     {}", hint),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+
+        lines
+    }
+}
+
+fn current_value(segment: &SegmentConfig, spec: &OptionSpec) -> Value {
+    segment
+        .options
+        .get(spec.key)
+        .cloned()
+        .unwrap_or_else(|| spec.default_value())
+}
+
+fn set_option(segment: &mut SegmentConfig, spec: &OptionSpec, value: Value) -> String {
+    let message = format!("{} = {}", spec.key, format_value(&value));
+    segment.options.insert(spec.key.to_string(), value);
+    message
+}
+
+fn restore(segment: &mut SegmentConfig, spec: &OptionSpec, original: Option<Value>) {
+    match original {
+        Some(value) => segment.options.insert(spec.key.to_string(), value),
+        None => segment.options.remove(spec.key),
+    };
+}
+
+fn parse_input(spec: &OptionSpec, input: &str) -> Option<Value> {
+    match spec.kind {
+        OptionKind::Integer { .. } => input.parse::<u64>().ok().map(Value::from),
+        OptionKind::String { .. } => Some(Value::from(input)),
+        OptionKind::Bool { .. } | OptionKind::Choice { .. } => None,
+    }
+}
+
+fn short_type(spec: &OptionSpec) -> String {
+    match spec.kind {
+        OptionKind::Bool { .. } => "bool".to_string(),
+        OptionKind::Integer { .. } => "number".to_string(),
+        OptionKind::String { .. } => "text".to_string(),
+        OptionKind::Choice { choices, .. } => choices.join("|"),
+    }
+}
+
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => format!("\"{}\"", s),
+        other => other.to_string(),
+    }
+}
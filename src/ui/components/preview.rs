@@ -1,5 +1,7 @@
-use crate::config::{Config, SegmentId};
-use crate::core::segments::SegmentData;
+use crate::config::{Config, SegmentConfig, SegmentId};
+use crate::core::fixtures::Fixture;
+use crate::core::segments::{DirectoryMode, DirectorySegment, LanguageSegment, SegmentData};
+use crate::core::statusline::collect_segments_with_responses;
 use crate::core::StatusLineGenerator;
 use ratatui::{
     layout::Rect,
@@ -12,6 +14,44 @@ use std::collections::HashMap;
 pub struct PreviewComponent {
     preview_cache: String,
     preview_text: Text<'static>,
+    /// Real segment data, when the live preview is on
+    live: Option<LiveData>,
+}
+
+/// Segment data collected for the live preview. Collecting runs git and
+/// reads the transcript, so it is redone only when segment options change.
+struct LiveData {
+    fixture: Fixture,
+    collected_for: Vec<(SegmentId, HashMap<String, serde_json::Value>)>,
+    data: HashMap<SegmentId, SegmentData>,
+}
+
+impl LiveData {
+    fn segments(&mut self, config: &Config) -> Vec<(SegmentConfig, SegmentData)> {
+        let key: Vec<_> = config
+            .segments
+            .iter()
+            .map(|segment| (segment.id, segment.options.clone()))
+            .collect();
+        if key != self.collected_for {
+            self.data = collect_segments_with_responses(
+                config,
+                &self.fixture.input,
+                &self.fixture.responses,
+            )
+            .into_iter()
+            .map(|(segment, data)| (segment.id, data))
+            .collect();
+            self.collected_for = key;
+        }
+
+        config
+            .segments
+            .iter()
+            .filter(|segment| segment.enabled)
+            .filter_map(|segment| Some((segment.clone(), self.data.get(&segment.id)?.clone())))
+            .collect()
+    }
 }
 
 impl Default for PreviewComponent {
@@ -25,19 +65,56 @@ impl PreviewComponent {
         Self {
             preview_cache: String::new(),
             preview_text: Text::default(),
+            live: None,
         }
     }
 
+    pub fn is_live(&self) -> bool {
+        self.live.is_some()
+    }
+
+    /// Switch between sample data and real data from the current directory,
+    /// the newest transcript and cached API responses. Returns a description
+    /// of what is shown.
+    pub fn toggle_live(&mut self) -> String {
+        if self.live.take().is_some() {
+            return "Preview: sample data".to_string();
+        }
+
+        let fixture = Fixture::live();
+        let transcript = match fixture.input.transcript_path.as_str() {
+            "" => "no transcript".to_string(),
+            path => std::path::Path::new(path)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.to_string()),
+        };
+        let message = format!(
+            "Preview: live data from {} ({})",
+            fixture.input.workspace.current_dir, transcript
+        );
+        self.live = Some(LiveData {
+            fixture,
+            collected_for: Vec::new(),
+            data: HashMap::new(),
+        });
+        message
+    }
+
     pub fn update_preview(&mut self, config: &Config) {
         self.update_preview_with_width(config, 80); // Default width
     }
 
     pub fn update_preview_with_width(&mut self, config: &Config, width: u16) {
-        // Generate mock segments data directly for preview
-        let segments_data = self.generate_mock_segments_data(config);
+        let segments_data = match self.live.as_mut() {
+            Some(live) => live.segments(config),
+            None => self.generate_mock_segments_data(config),
+        };
 
         // Generate both string and TUI text versions
-        let renderer = StatusLineGenerator::new(config.clone());
+        let renderer = StatusLineGenerator::new(config.clone())
+            .with_extended_underline(false)
+            .with_hyperlinks(false);
 
         // Keep string version for compatibility (if needed elsewhere)
         self.preview_cache = renderer.generate(segments_data.clone());
@@ -72,7 +149,15 @@ impl PreviewComponent {
 
     pub fn render(&self, f: &mut Frame, area: Rect) {
         let preview = Paragraph::new(self.preview_text.clone())
-            .block(Block::default().borders(Borders::ALL).title("Preview"))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(if self.is_live() {
+                        "Preview (live)"
+                    } else {
+                        "Preview"
+                    }),
+            )
             .wrap(ratatui::widgets::Wrap { trim: false });
         f.render_widget(preview, area);
     }
@@ -83,10 +168,7 @@ impl PreviewComponent {
 
     /// Generate mock segments data for preview display
     /// This creates perfect preview data without depending on real environment
-    fn generate_mock_segments_data(
-        &self,
-        config: &Config,
-    ) -> Vec<(crate::config::SegmentConfig, SegmentData)> {
+    fn generate_mock_segments_data(&self, config: &Config) -> Vec<(SegmentConfig, SegmentData)> {
         let mut segments_data = Vec::new();
 
         for segment_config in &config.segments {
@@ -104,27 +186,55 @@ impl PreviewComponent {
                         map
                     },
                 },
-                SegmentId::Directory => SegmentData {
-                    primary: "CubenceLine".to_string(),
-                    secondary: "".to_string(),
-                    metadata: {
-                        let mut map = HashMap::new();
-                        map.insert("current_dir".to_string(), "~/CubenceLine".to_string());
-                        map
-                    },
-                },
-                SegmentId::Git => SegmentData {
-                    primary: "master".to_string(),
-                    secondary: "✓".to_string(),
-                    metadata: {
-                        let mut map = HashMap::new();
-                        map.insert("branch".to_string(), "master".to_string());
-                        map.insert("status".to_string(), "Clean".to_string());
-                        map.insert("ahead".to_string(), "0".to_string());
-                        map.insert("behind".to_string(), "0".to_string());
-                        map
-                    },
-                },
+                SegmentId::Directory => {
+                    // A project under the home directory, so every mode has
+                    // something to show without asking git
+                    let project = dirs::home_dir()
+                        .unwrap_or_default()
+                        .join("projects")
+                        .join("CubenceLine");
+                    let current = project.to_string_lossy();
+                    let segment = DirectorySegment::new()
+                        .with_mode(
+                            option_str(segment_config, "mode")
+                                .and_then(DirectoryMode::parse)
+                                .unwrap_or_default(),
+                        )
+                        .with_max_components(
+                            segment_config
+                                .options
+                                .get("max_components")
+                                .and_then(|v| v.as_u64())
+                                .unwrap_or(0) as usize,
+                        );
+                    SegmentData {
+                        primary: segment.format_path(&current, project.to_str()),
+                        secondary: "".to_string(),
+                        metadata: {
+                            let mut map = HashMap::new();
+                            map.insert("current_dir".to_string(), current.to_string());
+                            map
+                        },
+                    }
+                }
+                SegmentId::Git => {
+                    let show_sha = option_bool(segment_config, "show_sha").unwrap_or(false);
+                    SegmentData {
+                        primary: "master".to_string(),
+                        secondary: if show_sha { "✓ a1b2c3d" } else { "✓" }.to_string(),
+                        metadata: {
+                            let mut map = HashMap::new();
+                            map.insert("branch".to_string(), "master".to_string());
+                            map.insert("status".to_string(), "Clean".to_string());
+                            map.insert("ahead".to_string(), "0".to_string());
+                            map.insert("behind".to_string(), "0".to_string());
+                            if show_sha {
+                                map.insert("sha".to_string(), "a1b2c3d".to_string());
+                            }
+                            map
+                        },
+                    }
+                }
                 SegmentId::ContextWindow => SegmentData {
                     primary: "78.2%".to_string(),
                     secondary: "· 156.4k".to_string(),
@@ -190,6 +300,25 @@ impl PreviewComponent {
                         map
                     },
                 },
+                SegmentId::Language => SegmentData {
+                    primary: if option_bool(segment_config, "show_version").unwrap_or(true) {
+                        "Rust 1.89"
+                    } else {
+                        "Rust"
+                    }
+                    .to_string(),
+                    secondary: "".to_string(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("language".to_string(), "Rust".to_string());
+                        map.insert("version".to_string(), "1.89".to_string());
+                        if !LanguageSegment::icon_is_configured(&segment_config.icon) {
+                            map.insert("dynamic_icon".to_string(), "\u{e7a8}".to_string());
+                            map.insert("dynamic_icon_plain".to_string(), "🦀".to_string());
+                        }
+                        map
+                    },
+                },
             };
 
             segments_data.push((segment_config.clone(), mock_data));
@@ -198,3 +327,11 @@ impl PreviewComponent {
         segments_data
     }
 }
+
+fn option_bool(segment: &SegmentConfig, key: &str) -> Option<bool> {
+    segment.options.get(key).and_then(|v| v.as_bool())
+}
+
+fn option_str<'a>(segment: &'a SegmentConfig, key: &str) -> Option<&'a str> {
+    segment.options.get(key).and_then(|v| v.as_str())
+}
@@ -1,6 +1,6 @@
 use crate::config::{Config, SegmentId};
 use ratatui::{
-    layout::Rect,
+    layout::{Position, Rect},
     style::{Color, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem},
@@ -19,27 +19,53 @@ pub enum FieldSelection {
     Icon,
     IconColor,
     TextColor,
+    SecondaryColor,
     BackgroundColor,
     TextStyle,
     Options,
 }
 
 #[derive(Default)]
-pub struct SegmentListComponent;
+pub struct SegmentListComponent {
+    /// Where the list was last drawn, for mouse clicks and drags
+    area: Rect,
+}
 
 impl SegmentListComponent {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Index of the segment drawn at a screen position
+    pub fn segment_at(&self, column: u16, row: u16, count: usize) -> Option<usize> {
+        let inner = self.inner();
+        if !inner.contains(Position::new(column, row)) {
+            return None;
+        }
+        let index = (row - inner.y) as usize;
+        (index < count).then_some(index)
+    }
+
+    /// Index of the segment nearest to a screen row, for dragging past the
+    /// ends of the list
+    pub fn nearest_segment(&self, row: u16, count: usize) -> usize {
+        let index = row.saturating_sub(self.inner().y) as usize;
+        index.min(count.saturating_sub(1))
+    }
+
+    fn inner(&self) -> Rect {
+        Block::default().borders(Borders::ALL).inner(self.area)
     }
 
     pub fn render(
-        &self,
+        &mut self,
         f: &mut Frame,
         area: Rect,
         config: &Config,
         selected_segment: usize,
         selected_panel: &Panel,
     ) {
+        self.area = area;
         let items: Vec<ListItem> = config
             .segments
             .iter()
@@ -47,6 +73,7 @@ impl SegmentListComponent {
             .map(|(i, segment)| {
                 let is_selected = i == selected_segment && *selected_panel == Panel::SegmentList;
                 let enabled_marker = if segment.enabled { "●" } else { "○" };
+                let align_marker = if segment.align.is_left() { "" } else { " ⇥" };
                 let segment_name = match segment.id {
                     SegmentId::Model => "Model",
                     SegmentId::Directory => "Directory",
@@ -58,17 +85,24 @@ impl SegmentListComponent {
                     SegmentId::Session => "Session",
                     SegmentId::OutputStyle => "Output Style",
                     SegmentId::Update => "Update",
+                    SegmentId::Language => "Language",
                 };
 
                 if is_selected {
                     // Selected item with colored cursor
                     ListItem::new(Line::from(vec![
                         Span::styled("▶ ", Style::default().fg(Color::Cyan)),
-                        Span::raw(format!("{} {}", enabled_marker, segment_name)),
+                        Span::raw(format!(
+                            "{} {}{}",
+                            enabled_marker, segment_name, align_marker
+                        )),
                     ]))
                 } else {
                     // Non-selected item
-                    ListItem::new(format!("  {} {}", enabled_marker, segment_name))
+                    ListItem::new(format!(
+                        "  {} {}{}",
+                        enabled_marker, segment_name, align_marker
+                    ))
                 }
             })
             .collect();
@@ -18,6 +18,8 @@ pub struct SeparatorPreset {
     pub name: String,
     pub value: String,
     pub description: String,
+    /// Start and end caps applied together with this separator
+    pub caps: Option<(String, String)>,
 }
 
 impl Default for SeparatorEditorComponent {
@@ -42,26 +44,49 @@ impl SeparatorEditorComponent {
                 name: "Pipe".to_string(),
                 value: " | ".to_string(),
                 description: "Classic pipe separator".to_string(),
+                caps: None,
             },
             SeparatorPreset {
                 name: "Thin".to_string(),
                 value: " │ ".to_string(),
                 description: "Thin vertical line".to_string(),
+                caps: None,
             },
             SeparatorPreset {
                 name: "Arrow".to_string(),
                 value: "\u{e0b0}".to_string(),
                 description: "Powerline arrow (seamless transition)".to_string(),
+                caps: None,
+            },
+            SeparatorPreset {
+                name: "Rounded".to_string(),
+                value: "\u{e0b4}".to_string(),
+                description: "Rounded Powerline with end caps".to_string(),
+                caps: Some(("\u{e0b6}".to_string(), "\u{e0b4}".to_string())),
+            },
+            SeparatorPreset {
+                name: "Slanted".to_string(),
+                value: "\u{e0bc}".to_string(),
+                description: "Slanted Powerline with end caps".to_string(),
+                caps: Some(("\u{e0ba}".to_string(), "\u{e0bc}".to_string())),
+            },
+            SeparatorPreset {
+                name: "Flame".to_string(),
+                value: "\u{e0c0}".to_string(),
+                description: "Flame Powerline with end caps".to_string(),
+                caps: Some(("\u{e0c2}".to_string(), "\u{e0c0}".to_string())),
             },
             SeparatorPreset {
                 name: "Space".to_string(),
                 value: "  ".to_string(),
                 description: "Double space".to_string(),
+                caps: None,
             },
             SeparatorPreset {
                 name: "Dot".to_string(),
                 value: " • ".to_string(),
                 description: "Middle dot".to_string(),
+                caps: None,
             },
         ]
     }
@@ -119,13 +144,20 @@ impl SeparatorEditorComponent {
         self.input.clone()
     }
 
+    /// Caps of the selected preset; `None` when the separator was typed by hand
+    pub fn get_preset_caps(&self) -> Option<Option<(String, String)>> {
+        self.selected_preset
+            .and_then(|i| self.presets.get(i))
+            .map(|preset| preset.caps.clone())
+    }
+
     pub fn render(&self, f: &mut Frame, area: Rect) {
         if !self.is_open {
             return;
         }
 
         // Calculate exact size needed
-        let popup_height = 15;
+        let popup_height = 18;
         let popup_width = 60;
         let popup_area = Rect {
             x: (area.width.saturating_sub(popup_width)) / 2,
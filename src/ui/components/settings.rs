@@ -1,5 +1,7 @@
+use super::options_editor::OptionsEditorComponent;
 use super::segment_list::{FieldSelection, Panel};
-use crate::config::{Config, SegmentId, StyleMode};
+use super::text_style_editor::describe_attributes;
+use crate::config::{AnsiColor, Config, SegmentId, StyleMode};
 use ratatui::{
     layout::Rect,
     style::{Color, Style},
@@ -9,11 +11,14 @@ use ratatui::{
 };
 
 #[derive(Default)]
-pub struct SettingsComponent;
+pub struct SettingsComponent {
+    /// Editor for the selected segment's options, shown under the Options field
+    pub options_editor: OptionsEditorComponent,
+}
 
 impl SettingsComponent {
     pub fn new() -> Self {
-        Self
+        Self::default()
     }
 
     pub fn render(
@@ -25,6 +30,7 @@ impl SettingsComponent {
         selected_panel: &Panel,
         selected_field: &FieldSelection,
     ) {
+        let options_editor = &self.options_editor;
         if let Some(segment) = config.segments.get(selected_segment) {
             let segment_name = match segment.id {
                 SegmentId::Model => "Model",
@@ -37,162 +43,30 @@ impl SettingsComponent {
                 SegmentId::Session => "Session",
                 SegmentId::OutputStyle => "Output Style",
                 SegmentId::Update => "Update",
+                SegmentId::Language => "Language",
             };
             let current_icon = match config.style.mode {
                 StyleMode::Plain => &segment.icon.plain,
                 StyleMode::NerdFont | StyleMode::Powerline => &segment.icon.nerd_font,
             };
-            // Convert AnsiColor to ratatui Color
-            let icon_ratatui_color = match &segment.colors.icon {
-                Some(crate::config::AnsiColor::Color16 { c16 }) => match c16 {
-                    0 => Color::Black,
-                    1 => Color::Red,
-                    2 => Color::Green,
-                    3 => Color::Yellow,
-                    4 => Color::Blue,
-                    5 => Color::Magenta,
-                    6 => Color::Cyan,
-                    7 => Color::White,
-                    8 => Color::DarkGray,
-                    9 => Color::LightRed,
-                    10 => Color::LightGreen,
-                    11 => Color::LightYellow,
-                    12 => Color::LightBlue,
-                    13 => Color::LightMagenta,
-                    14 => Color::LightCyan,
-                    15 => Color::Gray,
-                    _ => Color::White,
-                },
-                Some(crate::config::AnsiColor::Color256 { c256 }) => Color::Indexed(*c256),
-                Some(crate::config::AnsiColor::Rgb { r, g, b }) => Color::Rgb(*r, *g, *b),
-                None => Color::White,
-            };
-            let text_ratatui_color = match &segment.colors.text {
-                Some(crate::config::AnsiColor::Color16 { c16 }) => match c16 {
-                    0 => Color::Black,
-                    1 => Color::Red,
-                    2 => Color::Green,
-                    3 => Color::Yellow,
-                    4 => Color::Blue,
-                    5 => Color::Magenta,
-                    6 => Color::Cyan,
-                    7 => Color::White,
-                    8 => Color::DarkGray,
-                    9 => Color::LightRed,
-                    10 => Color::LightGreen,
-                    11 => Color::LightYellow,
-                    12 => Color::LightBlue,
-                    13 => Color::LightMagenta,
-                    14 => Color::LightCyan,
-                    15 => Color::Gray,
-                    _ => Color::White,
-                },
-                Some(crate::config::AnsiColor::Color256 { c256 }) => Color::Indexed(*c256),
-                Some(crate::config::AnsiColor::Rgb { r, g, b }) => Color::Rgb(*r, *g, *b),
-                None => Color::White,
-            };
-            let icon_color_desc = match &segment.colors.icon {
-                Some(crate::config::AnsiColor::Color16 { c16 }) => match c16 {
-                    0 => "Black".to_string(),
-                    1 => "Red".to_string(),
-                    2 => "Green".to_string(),
-                    3 => "Yellow".to_string(),
-                    4 => "Blue".to_string(),
-                    5 => "Magenta".to_string(),
-                    6 => "Cyan".to_string(),
-                    7 => "White".to_string(),
-                    8 => "Dark Gray".to_string(),
-                    9 => "Light Red".to_string(),
-                    10 => "Light Green".to_string(),
-                    11 => "Light Yellow".to_string(),
-                    12 => "Light Blue".to_string(),
-                    13 => "Light Magenta".to_string(),
-                    14 => "Light Cyan".to_string(),
-                    15 => "Gray".to_string(),
-                    _ => format!("ANSI {}", c16),
-                },
-                Some(crate::config::AnsiColor::Color256 { c256 }) => format!("256:{}", c256),
-                Some(crate::config::AnsiColor::Rgb { r, g, b }) => {
-                    format!("RGB({},{},{})", r, g, b)
-                }
-                None => "Default".to_string(),
-            };
-            let text_color_desc = match &segment.colors.text {
-                Some(crate::config::AnsiColor::Color16 { c16 }) => match c16 {
-                    0 => "Black".to_string(),
-                    1 => "Red".to_string(),
-                    2 => "Green".to_string(),
-                    3 => "Yellow".to_string(),
-                    4 => "Blue".to_string(),
-                    5 => "Magenta".to_string(),
-                    6 => "Cyan".to_string(),
-                    7 => "White".to_string(),
-                    8 => "Dark Gray".to_string(),
-                    9 => "Light Red".to_string(),
-                    10 => "Light Green".to_string(),
-                    11 => "Light Yellow".to_string(),
-                    12 => "Light Blue".to_string(),
-                    13 => "Light Magenta".to_string(),
-                    14 => "Light Cyan".to_string(),
-                    15 => "Gray".to_string(),
-                    _ => format!("ANSI {}", c16),
-                },
-                Some(crate::config::AnsiColor::Color256 { c256 }) => format!("256:{}", c256),
-                Some(crate::config::AnsiColor::Rgb { r, g, b }) => {
-                    format!("RGB({},{},{})", r, g, b)
-                }
-                None => "Default".to_string(),
-            };
-            let background_ratatui_color = match &segment.colors.background {
-                Some(crate::config::AnsiColor::Color16 { c16 }) => match c16 {
-                    0 => Color::Black,
-                    1 => Color::Red,
-                    2 => Color::Green,
-                    3 => Color::Yellow,
-                    4 => Color::Blue,
-                    5 => Color::Magenta,
-                    6 => Color::Cyan,
-                    7 => Color::White,
-                    8 => Color::DarkGray,
-                    9 => Color::LightRed,
-                    10 => Color::LightGreen,
-                    11 => Color::LightYellow,
-                    12 => Color::LightBlue,
-                    13 => Color::LightMagenta,
-                    14 => Color::LightCyan,
-                    15 => Color::Gray,
-                    _ => Color::White,
-                },
-                Some(crate::config::AnsiColor::Color256 { c256 }) => Color::Indexed(*c256),
-                Some(crate::config::AnsiColor::Rgb { r, g, b }) => Color::Rgb(*r, *g, *b),
-                None => Color::White,
-            };
-            let background_color_desc = match &segment.colors.background {
-                Some(crate::config::AnsiColor::Color16 { c16 }) => match c16 {
-                    0 => "Black".to_string(),
-                    1 => "Red".to_string(),
-                    2 => "Green".to_string(),
-                    3 => "Yellow".to_string(),
-                    4 => "Blue".to_string(),
-                    5 => "Magenta".to_string(),
-                    6 => "Cyan".to_string(),
-                    7 => "White".to_string(),
-                    8 => "Dark Gray".to_string(),
-                    9 => "Light Red".to_string(),
-                    10 => "Light Green".to_string(),
-                    11 => "Light Yellow".to_string(),
-                    12 => "Light Blue".to_string(),
-                    13 => "Light Magenta".to_string(),
-                    14 => "Light Cyan".to_string(),
-                    15 => "Gray".to_string(),
-                    _ => format!("ANSI {}", c16),
-                },
-                Some(crate::config::AnsiColor::Color256 { c256 }) => format!("256:{}", c256),
-                Some(crate::config::AnsiColor::Rgb { r, g, b }) => {
-                    format!("RGB({},{},{})", r, g, b)
-                }
-                None => "None".to_string(),
+            let icon_ratatui_color = to_ratatui_color(&segment.colors.icon);
+            let text_ratatui_color = to_ratatui_color(&segment.colors.text);
+            let background_ratatui_color = to_ratatui_color(&segment.colors.background);
+            let icon_color_desc = describe_color(&segment.colors.icon, "Default");
+            let text_color_desc = describe_color(&segment.colors.text, "Default");
+            let background_color_desc = describe_color(&segment.colors.background, "None");
+            // Secondary text falls back to the text color
+            let secondary_color = segment
+                .colors
+                .secondary
+                .as_ref()
+                .or(segment.colors.text.as_ref());
+            let secondary_ratatui_color = to_ratatui_color(&secondary_color.cloned());
+            let secondary_color_desc = match &segment.colors.secondary {
+                Some(_) => describe_color(&segment.colors.secondary, "Default"),
+                None => "Same as text".to_string(),
             };
+            let token_count = segment.colors.tokens.len();
             let create_field_line = |field: FieldSelection, content: Vec<Span<'static>>| {
                 let is_selected = *selected_panel == Panel::Settings && *selected_field == field;
                 let mut spans = vec![];
@@ -209,7 +83,7 @@ impl SettingsComponent {
                 spans.extend(content);
                 Line::from(spans)
             };
-            let lines = vec![
+            let mut lines = vec![
                 Line::from(format!("{} Segment", segment_name)),
                 create_field_line(
                     FieldSelection::Enabled,
@@ -242,6 +116,21 @@ impl SettingsComponent {
                         Span::styled("██".to_string(), Style::default().fg(text_ratatui_color)),
                     ],
                 ),
+                create_field_line(
+                    FieldSelection::SecondaryColor,
+                    vec![
+                        Span::raw(format!("├─ Secondary Color: {} ", secondary_color_desc)),
+                        Span::styled(
+                            "██".to_string(),
+                            Style::default().fg(secondary_ratatui_color),
+                        ),
+                        Span::raw(if token_count > 0 {
+                            format!(" (+{} token colors)", token_count)
+                        } else {
+                            String::new()
+                        }),
+                    ],
+                ),
                 create_field_line(
                     FieldSelection::BackgroundColor,
                     vec![
@@ -259,22 +148,28 @@ impl SettingsComponent {
                 create_field_line(
                     FieldSelection::TextStyle,
                     vec![Span::raw(format!(
-                        "├─ Text Style: Bold {}",
-                        if segment.styles.text_bold {
-                            "[✓]"
+                        "├─ Text Style: {}{}",
+                        describe_attributes(&segment.styles.text_attributes()),
+                        if segment.styles.secondary.is_some() || !segment.styles.icon.is_plain() {
+                            " (+ icon/secondary)"
                         } else {
-                            "[ ]"
+                            ""
                         }
                     ))],
                 ),
                 create_field_line(
                     FieldSelection::Options,
                     vec![Span::raw(format!(
-                        "└─ Options: {} items",
-                        segment.options.len()
+                        "└─ Options{}",
+                        if options_editor.is_open {
+                            " (editing)"
+                        } else {
+                            ""
+                        }
                     ))],
                 ),
             ];
+            lines.extend(options_editor.lines(segment));
             let text = Text::from(lines);
             let settings_block = Block::default()
                 .borders(Borders::ALL)
@@ -300,3 +195,60 @@ impl SettingsComponent {
         }
     }
 }
+
+/// Convert AnsiColor to ratatui Color
+fn to_ratatui_color(color: &Option<AnsiColor>) -> Color {
+    match color {
+        Some(AnsiColor::Color16 { c16 }) => match c16 {
+            0 => Color::Black,
+            1 => Color::Red,
+            2 => Color::Green,
+            3 => Color::Yellow,
+            4 => Color::Blue,
+            5 => Color::Magenta,
+            6 => Color::Cyan,
+            7 => Color::White,
+            8 => Color::DarkGray,
+            9 => Color::LightRed,
+            10 => Color::LightGreen,
+            11 => Color::LightYellow,
+            12 => Color::LightBlue,
+            13 => Color::LightMagenta,
+            14 => Color::LightCyan,
+            15 => Color::Gray,
+            _ => Color::White,
+        },
+        Some(AnsiColor::Color256 { c256 }) => Color::Indexed(*c256),
+        Some(AnsiColor::Rgb { r, g, b }) => Color::Rgb(*r, *g, *b),
+        None => Color::White,
+    }
+}
+
+fn describe_color(color: &Option<AnsiColor>, unset: &str) -> String {
+    match color {
+        Some(AnsiColor::Color16 { c16 }) => match c16 {
+            0 => "Black".to_string(),
+            1 => "Red".to_string(),
+            2 => "Green".to_string(),
+            3 => "Yellow".to_string(),
+            4 => "Blue".to_string(),
+            5 => "Magenta".to_string(),
+            6 => "Cyan".to_string(),
+            7 => "White".to_string(),
+            8 => "Dark Gray".to_string(),
+            9 => "Light Red".to_string(),
+            10 => "Light Green".to_string(),
+            11 => "Light Yellow".to_string(),
+            12 => "Light Blue".to_string(),
+            13 => "Light Magenta".to_string(),
+            14 => "Light Cyan".to_string(),
+            15 => "Gray".to_string(),
+            _ => format!("ANSI {}", c16),
+        },
+        Some(AnsiColor::Color256 { c256 }) => format!("256:{}", c256),
+        Some(AnsiColor::Rgb { r, g, b }) => {
+            format!("RGB({},{},{})", r, g, b)
+        }
+        None => unset.to_string(),
+    }
+}
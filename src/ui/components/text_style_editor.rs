@@ -0,0 +1,364 @@
+use crate::config::{AnsiColor, TextAttributes, TextStyleConfig};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Which part of a segment the attributes apply to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StyleTarget {
+    Text,
+    Secondary,
+    Icon,
+}
+
+impl StyleTarget {
+    fn next(&self) -> Self {
+        match self {
+            StyleTarget::Text => StyleTarget::Secondary,
+            StyleTarget::Secondary => StyleTarget::Icon,
+            StyleTarget::Icon => StyleTarget::Text,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            StyleTarget::Text => "Text",
+            StyleTarget::Secondary => "Secondary",
+            StyleTarget::Icon => "Icon",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum StyleRow {
+    Inherit,
+    Bold,
+    Italic,
+    Dim,
+    Underline,
+    UnderlineColor,
+    Reverse,
+    Strikethrough,
+}
+
+const ATTRIBUTE_ROWS: [StyleRow; 7] = [
+    StyleRow::Bold,
+    StyleRow::Italic,
+    StyleRow::Dim,
+    StyleRow::Underline,
+    StyleRow::UnderlineColor,
+    StyleRow::Reverse,
+    StyleRow::Strikethrough,
+];
+
+const COLOR_NAMES: [&str; 16] = [
+    "Black",
+    "Red",
+    "Green",
+    "Yellow",
+    "Blue",
+    "Magenta",
+    "Cyan",
+    "White",
+    "Dark Gray",
+    "Light Red",
+    "Light Green",
+    "Light Yellow",
+    "Light Blue",
+    "Light Magenta",
+    "Light Cyan",
+    "Gray",
+];
+
+#[derive(Debug, Clone)]
+pub struct TextStyleEditorComponent {
+    pub is_open: bool,
+    pub target: StyleTarget,
+    pub selected_row: usize,
+}
+
+impl Default for TextStyleEditorComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextStyleEditorComponent {
+    pub fn new() -> Self {
+        Self {
+            is_open: false,
+            target: StyleTarget::Text,
+            selected_row: 0,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.is_open = true;
+        self.target = StyleTarget::Text;
+        self.selected_row = 0;
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+    }
+
+    pub fn cycle_target(&mut self) {
+        self.target = self.target.next();
+        self.selected_row = 0;
+    }
+
+    pub fn move_selection(&mut self, delta: i32) {
+        let row_count = self.rows().len() as i32;
+        self.selected_row = (self.selected_row as i32 + delta).clamp(0, row_count - 1) as usize;
+    }
+
+    fn rows(&self) -> Vec<StyleRow> {
+        let mut rows = Vec::new();
+        if self.target == StyleTarget::Secondary {
+            rows.push(StyleRow::Inherit);
+        }
+        rows.extend(ATTRIBUTE_ROWS);
+        rows
+    }
+
+    /// Current attributes of the selected target
+    fn attributes(&self, styles: &TextStyleConfig) -> TextAttributes {
+        match self.target {
+            StyleTarget::Text => styles.text_attributes(),
+            StyleTarget::Secondary => styles.secondary_attributes(),
+            StyleTarget::Icon => styles.icon.clone(),
+        }
+    }
+
+    /// Toggle or cycle the selected attribute, returning a status message
+    pub fn toggle_selected(&mut self, styles: &mut TextStyleConfig) -> String {
+        let Some(row) = self.rows().get(self.selected_row).copied() else {
+            return String::new();
+        };
+
+        if row == StyleRow::Inherit {
+            styles.secondary = match styles.secondary {
+                Some(_) => None,
+                None => Some(styles.text_attributes()),
+            };
+            return format!(
+                "Secondary text {}",
+                if styles.secondary.is_none() {
+                    "inherits text style"
+                } else {
+                    "has its own style"
+                }
+            );
+        }
+
+        let mut attributes = self.attributes(styles);
+        let status = match row {
+            StyleRow::Bold => {
+                attributes.bold = !attributes.bold;
+                format!("Bold {}", on_off(attributes.bold))
+            }
+            StyleRow::Italic => {
+                attributes.italic = !attributes.italic;
+                format!("Italic {}", on_off(attributes.italic))
+            }
+            StyleRow::Dim => {
+                attributes.dim = !attributes.dim;
+                format!("Dim {}", on_off(attributes.dim))
+            }
+            StyleRow::Underline => {
+                attributes.underline = attributes.underline.next();
+                format!("Underline: {}", attributes.underline.as_str())
+            }
+            StyleRow::UnderlineColor => {
+                attributes.underline_color = next_underline_color(&attributes.underline_color);
+                format!(
+                    "Underline color: {}",
+                    describe_color(&attributes.underline_color)
+                )
+            }
+            StyleRow::Reverse => {
+                attributes.reverse = !attributes.reverse;
+                format!("Reverse {}", on_off(attributes.reverse))
+            }
+            StyleRow::Strikethrough => {
+                attributes.strikethrough = !attributes.strikethrough;
+                format!("Strikethrough {}", on_off(attributes.strikethrough))
+            }
+            StyleRow::Inherit => unreachable!(),
+        };
+
+        match self.target {
+            StyleTarget::Text => {
+                // Keep bold in the legacy `text_bold` field
+                styles.text_bold = attributes.bold;
+                attributes.bold = false;
+                styles.text = attributes;
+            }
+            StyleTarget::Secondary => styles.secondary = Some(attributes),
+            StyleTarget::Icon => styles.icon = attributes,
+        }
+
+        format!("{} {}", self.target.name(), status.to_lowercase())
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect, styles: &TextStyleConfig) {
+        if !self.is_open {
+            return;
+        }
+
+        let popup_height = 16;
+        let popup_width = 50;
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(popup_width)) / 2,
+            y: (area.height.saturating_sub(popup_height)) / 2,
+            width: popup_width.min(area.width),
+            height: popup_height.min(area.height),
+        };
+
+        f.render_widget(Clear, popup_area);
+
+        let popup_block = Block::default().borders(Borders::ALL).title("Text Style");
+        let inner = popup_block.inner(popup_area);
+        f.render_widget(popup_block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Targets
+                Constraint::Min(8),    // Attributes
+                Constraint::Length(3), // Actions
+            ])
+            .split(inner);
+
+        // Target tabs
+        let tabs: Vec<Span> = [StyleTarget::Text, StyleTarget::Secondary, StyleTarget::Icon]
+            .iter()
+            .flat_map(|target| {
+                let style = if *target == self.target {
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                };
+                [
+                    Span::styled(format!(" {} ", target.name()), style),
+                    Span::raw(" "),
+                ]
+            })
+            .collect();
+        f.render_widget(Paragraph::new(Line::from(tabs)), chunks[0]);
+
+        let attributes = self.attributes(styles);
+        let inherited = self.target == StyleTarget::Secondary && styles.secondary.is_none();
+        let lines: Vec<Line> = self
+            .rows()
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let (label, value) = match row {
+                    StyleRow::Inherit => ("Inherit from text", checkbox(inherited)),
+                    StyleRow::Bold => ("Bold", checkbox(attributes.bold)),
+                    StyleRow::Italic => ("Italic", checkbox(attributes.italic)),
+                    StyleRow::Dim => ("Dim", checkbox(attributes.dim)),
+                    StyleRow::Underline => ("Underline", attributes.underline.as_str().to_string()),
+                    StyleRow::UnderlineColor => (
+                        "Underline color",
+                        describe_color(&attributes.underline_color),
+                    ),
+                    StyleRow::Reverse => ("Reverse", checkbox(attributes.reverse)),
+                    StyleRow::Strikethrough => {
+                        ("Strikethrough", checkbox(attributes.strikethrough))
+                    }
+                };
+                let marker = if i == self.selected_row { "▶ " } else { "  " };
+                let style = if i == self.selected_row {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default()
+                };
+                Line::from(Span::styled(
+                    format!("{}{:<18}{}", marker, label, value),
+                    style,
+                ))
+            })
+            .collect();
+        f.render_widget(
+            Paragraph::new(lines).block(Block::default().borders(Borders::TOP)),
+            chunks[1],
+        );
+
+        f.render_widget(
+            Paragraph::new("[Enter] Toggle  [Tab] Target  [Esc] Done")
+                .block(Block::default().borders(Borders::ALL)),
+            chunks[2],
+        );
+    }
+}
+
+/// Short summary of the attributes, e.g. "bold, italic, curly underline"
+pub fn describe_attributes(attributes: &TextAttributes) -> String {
+    let mut parts = Vec::new();
+    if attributes.bold {
+        parts.push("bold".to_string());
+    }
+    if attributes.italic {
+        parts.push("italic".to_string());
+    }
+    if attributes.dim {
+        parts.push("dim".to_string());
+    }
+    if !attributes.underline.is_none() {
+        parts.push(format!("{} underline", attributes.underline.as_str()));
+    }
+    if attributes.reverse {
+        parts.push("reverse".to_string());
+    }
+    if attributes.strikethrough {
+        parts.push("strikethrough".to_string());
+    }
+
+    if parts.is_empty() {
+        "normal".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+fn checkbox(value: bool) -> String {
+    if value { "[✓]" } else { "[ ]" }.to_string()
+}
+
+fn on_off(value: bool) -> &'static str {
+    if value {
+        "enabled"
+    } else {
+        "disabled"
+    }
+}
+
+/// Cycle None -> the 16 standard colors -> None; custom colors restart the cycle
+fn next_underline_color(color: &Option<AnsiColor>) -> Option<AnsiColor> {
+    match color {
+        None => Some(AnsiColor::Color16 { c16: 0 }),
+        Some(AnsiColor::Color16 { c16 }) if *c16 < 15 => Some(AnsiColor::Color16 { c16: c16 + 1 }),
+        Some(AnsiColor::Color16 { .. }) => None,
+        Some(_) => Some(AnsiColor::Color16 { c16: 0 }),
+    }
+}
+
+fn describe_color(color: &Option<AnsiColor>) -> String {
+    match color {
+        None => "Text color".to_string(),
+        Some(AnsiColor::Color16 { c16 }) => COLOR_NAMES
+            .get(*c16 as usize)
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| format!("ANSI {}", c16)),
+        Some(AnsiColor::Color256 { c256 }) => format!("256:{}", c256),
+        Some(AnsiColor::Rgb { r, g, b }) => format!("RGB({},{},{})", r, g, b),
+    }
+}
@@ -1,19 +1,30 @@
 use crate::config::Config;
 use ratatui::{
-    layout::Rect,
+    layout::{Position, Rect},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
 
 #[derive(Default)]
-pub struct ThemeSelectorComponent;
+pub struct ThemeSelectorComponent {
+    /// Where each theme name was last drawn, for mouse clicks
+    areas: Vec<(Rect, String)>,
+}
 
 impl ThemeSelectorComponent {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// The theme drawn at a screen position
+    pub fn theme_at(&self, column: u16, row: u16) -> Option<&str> {
+        self.areas
+            .iter()
+            .find(|(area, _)| area.contains(Position::new(column, row)))
+            .map(|(_, theme)| theme.as_str())
     }
 
-    pub fn render(&self, f: &mut Frame, area: Rect, config: &Config) {
+    pub fn render(&mut self, f: &mut Frame, area: Rect, config: &Config) {
         let is_modified = config.is_modified_from_theme();
         let modified_indicator = if is_modified { "*" } else { "" };
 
@@ -27,6 +38,7 @@ impl ThemeSelectorComponent {
         let mut lines = Vec::new();
         let mut current_line = String::new();
         let mut first_line = true;
+        self.areas.clear();
 
         for (i, theme) in available_themes.iter().enumerate() {
             let marker = if config.theme == *theme {
@@ -41,13 +53,24 @@ impl ThemeSelectorComponent {
             // Check if this part fits in current line
             let would_fit = current_line.len() + part_with_sep.len() <= content_width as usize;
 
-            if would_fit || first_line {
+            let offset = if would_fit || first_line {
+                let offset = current_line.chars().count() + separator.len();
                 current_line.push_str(&part_with_sep);
                 first_line = false;
+                offset
             } else {
                 // Start new line
                 lines.push(current_line);
-                current_line = theme_part; // No indent for continuation lines
+                current_line = theme_part.clone(); // No indent for continuation lines
+                0
+            };
+
+            let x = area.x + 1 + offset as u16;
+            let y = area.y + 1 + lines.len() as u16;
+            if x < area.right().saturating_sub(1) && y < area.bottom().saturating_sub(1) {
+                let width = (theme_part.chars().count() as u16).min(area.right() - 1 - x);
+                self.areas
+                    .push((Rect::new(x, y, width, 1), theme.to_string()));
             }
         }
 
@@ -0,0 +1,63 @@
+// Undo/redo history of the configurator's edits
+
+use crate::config::Config;
+
+/// Oldest snapshots are dropped beyond this many
+const MAX_SNAPSHOTS: usize = 100;
+
+#[derive(Debug, Default)]
+pub struct History {
+    undo: Vec<Config>,
+    redo: Vec<Config>,
+    /// Group of the last recorded edit, while further edits may join it
+    group: Option<String>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remember the configuration before an edit. A new edit discards
+    /// anything that was undone.
+    pub fn record(&mut self, before: Config) {
+        self.group = None;
+        self.undo.push(before);
+        if self.undo.len() > MAX_SNAPSHOTS {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    /// Like `record`, but consecutive edits in the same `group` (e.g. each
+    /// character typed into one field) become a single step
+    pub fn record_in_group(&mut self, before: Config, group: &str) {
+        if self.group.as_deref() == Some(group) && !self.undo.is_empty() {
+            self.redo.clear();
+            return;
+        }
+        self.record(before);
+        self.group = Some(group.to_string());
+    }
+
+    /// Let the next edit start a new step even if it is in the same group
+    pub fn end_group(&mut self) {
+        self.group = None;
+    }
+
+    /// The configuration before the last edit, given the current one
+    pub fn undo(&mut self, current: Config) -> Option<Config> {
+        self.group = None;
+        let previous = self.undo.pop()?;
+        self.redo.push(current);
+        Some(previous)
+    }
+
+    /// The configuration the last undo went back from, given the current one
+    pub fn redo(&mut self, current: Config) -> Option<Config> {
+        self.group = None;
+        let next = self.redo.pop()?;
+        self.undo.push(current);
+        Some(next)
+    }
+}
@@ -0,0 +1,395 @@
+// Key bindings of the configurator's main screen, configurable through the
+// `[keymap]` table of config.toml
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Something the main screen can do, named in the keymap by `name()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Up,
+    Down,
+    MoveSegmentUp,
+    MoveSegmentDown,
+    Toggle,
+    SwitchPanel,
+    NextTheme,
+    ResetTheme,
+    Undo,
+    Redo,
+    EditSeparator,
+    ToggleAlign,
+    LivePreview,
+    CommandPalette,
+    SaveConfig,
+    WriteTheme,
+    SaveThemeAs,
+    Quit,
+}
+
+impl Action {
+    pub const ALL: [Action; 18] = [
+        Action::Up,
+        Action::Down,
+        Action::MoveSegmentUp,
+        Action::MoveSegmentDown,
+        Action::Toggle,
+        Action::SwitchPanel,
+        Action::NextTheme,
+        Action::ResetTheme,
+        Action::Undo,
+        Action::Redo,
+        Action::EditSeparator,
+        Action::ToggleAlign,
+        Action::LivePreview,
+        Action::CommandPalette,
+        Action::SaveConfig,
+        Action::WriteTheme,
+        Action::SaveThemeAs,
+        Action::Quit,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Up => "up",
+            Action::Down => "down",
+            Action::MoveSegmentUp => "move_segment_up",
+            Action::MoveSegmentDown => "move_segment_down",
+            Action::Toggle => "toggle",
+            Action::SwitchPanel => "switch_panel",
+            Action::NextTheme => "next_theme",
+            Action::ResetTheme => "reset_theme",
+            Action::Undo => "undo",
+            Action::Redo => "redo",
+            Action::EditSeparator => "edit_separator",
+            Action::ToggleAlign => "toggle_align",
+            Action::LivePreview => "live_preview",
+            Action::CommandPalette => "command_palette",
+            Action::SaveConfig => "save_config",
+            Action::WriteTheme => "write_theme",
+            Action::SaveThemeAs => "save_theme_as",
+            Action::Quit => "quit",
+        }
+    }
+
+    /// Label shown in the command palette
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::Up => "Move selection up",
+            Action::Down => "Move selection down",
+            Action::MoveSegmentUp => "Move segment up",
+            Action::MoveSegmentDown => "Move segment down",
+            Action::Toggle => "Toggle or edit the selected item",
+            Action::SwitchPanel => "Switch panel",
+            Action::NextTheme => "Switch to the next theme",
+            Action::ResetTheme => "Reset the theme to its defaults",
+            Action::Undo => "Undo",
+            Action::Redo => "Redo",
+            Action::EditSeparator => "Edit separator",
+            Action::ToggleAlign => "Align segment left/right",
+            Action::LivePreview => "Toggle live preview",
+            Action::CommandPalette => "Command palette",
+            Action::SaveConfig => "Save config",
+            Action::WriteTheme => "Write to the current theme",
+            Action::SaveThemeAs => "Save as new theme",
+            Action::Quit => "Quit",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|action| action.name() == name)
+    }
+}
+
+/// What a key does: an action, switching to a theme (`theme:<name>`), or
+/// nothing (`none`, to remove a default binding)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Binding {
+    Action(Action),
+    Theme(String),
+    Unbound,
+}
+
+impl Binding {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        if value == "none" {
+            return Ok(Binding::Unbound);
+        }
+        if let Some(theme) = value.strip_prefix("theme:") {
+            return match theme.trim() {
+                "" => Err("missing theme name after 'theme:'".to_string()),
+                theme => Ok(Binding::Theme(theme.to_string())),
+            };
+        }
+        Action::parse(value).map(Binding::Action).ok_or_else(|| {
+            let names: Vec<&str> = Action::ALL.iter().map(|action| action.name()).collect();
+            format!(
+                "unknown action '{}' (expected theme:<name>, none or one of: {})",
+                value,
+                names.join(", ")
+            )
+        })
+    }
+}
+
+/// A key with its modifiers. Letters are case-insensitive, so Shift only
+/// counts for keys other than characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let modifiers =
+            modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        match code {
+            KeyCode::Char(c) => Self {
+                code: KeyCode::Char(c.to_ascii_lowercase()),
+                modifiers: modifiers - KeyModifiers::SHIFT,
+            },
+            // Terminals report Shift+Tab as BackTab; Shift is part of the key
+            KeyCode::Tab | KeyCode::BackTab
+                if code == KeyCode::BackTab || modifiers.contains(KeyModifiers::SHIFT) =>
+            {
+                Self {
+                    code: KeyCode::BackTab,
+                    modifiers: modifiers - KeyModifiers::SHIFT,
+                }
+            }
+            code => Self { code, modifiers },
+        }
+    }
+
+    pub fn from_event(key: &KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+
+    /// Parse `ctrl+p`, `shift+up`, `u`, `f5`, `space` and the like
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        // A lone "+" is the plus key, not a separator
+        let (mods, key) = match spec.rsplit_once('+') {
+            Some((mods, "")) => (mods.strip_suffix('+').unwrap_or(mods), "+"),
+            Some((mods, key)) => (mods, key),
+            None => ("", spec),
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for modifier in mods.split('+').filter(|m| !m.is_empty()) {
+            modifiers |= match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "option" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                other => return Err(format!("unknown modifier '{}' in '{}'", other, spec)),
+            };
+        }
+
+        let lower = key.to_ascii_lowercase();
+        let code = match lower.as_str() {
+            "esc" | "escape" => KeyCode::Esc,
+            "enter" | "return" => KeyCode::Enter,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "space" => KeyCode::Char(' '),
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "insert" | "ins" => KeyCode::Insert,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            _ => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => match lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                        Some(n @ 1..=12) => KeyCode::F(n),
+                        _ => return Err(format!("unknown key '{}' in '{}'", key, spec)),
+                    },
+                }
+            }
+        };
+
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c.to_ascii_uppercase()),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+/// Built-in bindings, in the order the help bar lists them
+const DEFAULT_BINDINGS: &[(&str, &str)] = &[
+    ("up", "up"),
+    ("down", "down"),
+    ("shift+up", "move_segment_up"),
+    ("shift+down", "move_segment_down"),
+    ("enter", "toggle"),
+    ("tab", "switch_panel"),
+    ("1", "theme:default"),
+    ("2", "theme:minimal"),
+    ("3", "theme:gruvbox"),
+    ("4", "theme:nord"),
+    ("5", "theme:powerline-dark"),
+    ("6", "theme:powerline-light"),
+    ("7", "theme:powerline-rose-pine"),
+    ("8", "theme:powerline-tokyo-night"),
+    ("9", "theme:cometix"),
+    ("p", "next_theme"),
+    ("r", "reset_theme"),
+    ("u", "undo"),
+    ("ctrl+r", "redo"),
+    ("e", "edit_separator"),
+    ("a", "toggle_align"),
+    ("v", "live_preview"),
+    ("ctrl+p", "command_palette"),
+    ("s", "save_config"),
+    ("w", "write_theme"),
+    ("ctrl+s", "save_theme_as"),
+    ("esc", "quit"),
+];
+
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(KeyChord, Binding)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new(&HashMap::new())
+    }
+}
+
+impl Keymap {
+    /// The default bindings with `overrides` (key → action) applied. Entries
+    /// that don't parse are skipped; `--check` reports them.
+    pub fn new(overrides: &HashMap<String, String>) -> Self {
+        let mut bindings: Vec<(KeyChord, Binding)> = DEFAULT_BINDINGS
+            .iter()
+            .filter_map(|(key, value)| {
+                Some((KeyChord::parse(key).ok()?, Binding::parse(value).ok()?))
+            })
+            .collect();
+
+        let mut overrides: Vec<(&String, &String)> = overrides.iter().collect();
+        overrides.sort();
+        for (key, value) in overrides {
+            let (Ok(chord), Ok(binding)) = (KeyChord::parse(key), Binding::parse(value)) else {
+                continue;
+            };
+            match bindings.iter_mut().find(|(bound, _)| *bound == chord) {
+                Some(existing) => existing.1 = binding,
+                None => bindings.push((chord, binding)),
+            }
+        }
+
+        Self { bindings }
+    }
+
+    pub fn lookup(&self, key: &KeyEvent) -> Option<&Binding> {
+        let chord = KeyChord::from_event(key);
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == chord)
+            .map(|(_, binding)| binding)
+            .filter(|binding| **binding != Binding::Unbound)
+    }
+
+    /// Keys bound to `binding`, in binding order
+    pub fn keys_for(&self, binding: &Binding) -> Vec<KeyChord> {
+        self.bindings
+            .iter()
+            .filter(|(_, bound)| bound == binding)
+            .map(|(chord, _)| *chord)
+            .collect()
+    }
+
+    /// Keys bound to any theme, with the theme each switches to
+    pub fn theme_keys(&self) -> Vec<(KeyChord, &str)> {
+        self.bindings
+            .iter()
+            .filter_map(|(chord, binding)| match binding {
+                Binding::Theme(theme) => Some((*chord, theme.as_str())),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Problems with the `[keymap]` table, as (key, message)
+pub fn validate(overrides: &HashMap<String, String>) -> Vec<(String, String)> {
+    let mut keys: Vec<&String> = overrides.keys().collect();
+    keys.sort();
+    keys.into_iter()
+        .filter_map(|key| {
+            KeyChord::parse(key)
+                .and(Binding::parse(&overrides[key]))
+                .err()
+                .map(|message| (key.clone(), message))
+        })
+        .collect()
+}
+
+/// Label for several keys: `1-4` for a run of digits, `Shift+↑↓` for two
+/// keys with the same modifiers, otherwise `A/B`
+pub fn join_keys(keys: &[KeyChord]) -> String {
+    let labels: Vec<String> = keys.iter().map(|key| key.to_string()).collect();
+
+    let digits: Vec<u32> = labels
+        .iter()
+        .filter_map(|label| label.parse::<u32>().ok().filter(|d| *d < 10))
+        .collect();
+    if digits.len() == labels.len()
+        && digits.len() > 2
+        && digits.windows(2).all(|w| w[1] == w[0] + 1)
+    {
+        return format!("{}-{}", digits[0], digits[digits.len() - 1]);
+    }
+
+    if let [a, b] = keys {
+        if a.modifiers == b.modifiers {
+            let (la, lb) = (&labels[0], &labels[1]);
+            let prefix = la.rsplit_once('+').map(|(p, _)| p.len() + 1).unwrap_or(0);
+            let (ra, rb) = (&la[prefix..], &lb[prefix..]);
+            if ra.chars().count() == 1 && rb.chars().count() == 1 {
+                return format!("{}{}{}", &la[..prefix], ra, rb);
+            }
+        }
+    }
+
+    labels.join("/")
+}
@@ -5,11 +5,17 @@ pub mod components;
 #[cfg(feature = "tui")]
 pub mod events;
 #[cfg(feature = "tui")]
+pub mod history;
+#[cfg(feature = "tui")]
+pub mod keymap;
+#[cfg(feature = "tui")]
 pub mod layout;
 #[cfg(feature = "tui")]
 pub mod main_menu;
 #[cfg(feature = "tui")]
 pub mod themes;
+#[cfg(feature = "tui")]
+pub mod watcher;
 
 #[cfg(feature = "tui")]
 pub use app::App;
@@ -0,0 +1,553 @@
+// Convert color schemes from other prompt/terminal ecosystems into themes
+
+use super::presets::ThemePresets;
+use crate::config::{AnsiColor, ColorConfig, Config, SegmentId};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Source formats understood by `ccline theme import`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportFormat {
+    /// Starship `starship.toml` with a `[palettes.<name>]` table
+    Starship,
+    /// Oh My Posh JSON theme
+    OhMyPosh,
+    /// base16/base24 YAML scheme
+    Base16,
+    /// Windows Terminal color scheme JSON
+    WindowsTerminal,
+    /// Alacritty TOML color scheme
+    Alacritty,
+}
+
+impl ImportFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportFormat::Starship => "Starship palette",
+            ImportFormat::OhMyPosh => "Oh My Posh theme",
+            ImportFormat::Base16 => "base16 scheme",
+            ImportFormat::WindowsTerminal => "Windows Terminal scheme",
+            ImportFormat::Alacritty => "Alacritty scheme",
+        }
+    }
+}
+
+const ANSI_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+// Common non-ANSI palette names (Catppuccin, Rosé Pine, Gruvbox, ...) mapped onto ANSI slots
+const SLOT_ALIASES: &[(&str, &[&str])] = &[
+    ("background", &["bg", "base", "bg0", "bg_dark", "surface"]),
+    ("foreground", &["fg", "text", "fg0"]),
+    ("red", &["maroon", "love"]),
+    ("yellow", &["gold", "peach", "orange"]),
+    ("blue", &["sapphire", "pine"]),
+    ("magenta", &["purple", "mauve", "pink", "iris"]),
+    ("cyan", &["aqua", "teal", "sky", "foam"]),
+];
+
+// Palette slot used as the accent (background) color of each segment
+const SEGMENT_SLOTS: &[(SegmentId, &str)] = &[
+    (SegmentId::Model, "cyan"),
+    (SegmentId::Directory, "blue"),
+    (SegmentId::Git, "green"),
+    (SegmentId::ContextWindow, "magenta"),
+    (SegmentId::Usage, "yellow"),
+    (SegmentId::Cubence, "bright_blue"),
+    (SegmentId::Cost, "yellow"),
+    (SegmentId::Session, "bright_magenta"),
+    (SegmentId::OutputStyle, "bright_cyan"),
+    (SegmentId::Update, "red"),
+    (SegmentId::Language, "bright_red"),
+];
+
+/// Colors extracted from a source scheme, before they are mapped onto segments
+#[derive(Debug, Default)]
+struct Palette {
+    /// Canonical slots: `background`, `foreground`, ANSI names and `bright_*` variants
+    slots: HashMap<String, AnsiColor>,
+    /// Unrecognised named colors, used to fill empty slots by hue
+    extra: Vec<AnsiColor>,
+    /// Colors the source assigns to specific segments (foreground, background)
+    segments: HashMap<SegmentId, (Option<AnsiColor>, Option<AnsiColor>)>,
+}
+
+impl Palette {
+    fn insert_named(&mut self, name: &str, color: AnsiColor) {
+        match canonical_slot(name) {
+            Some(slot) => {
+                self.slots.entry(slot).or_insert(color);
+            }
+            None => self.extra.push(color),
+        }
+    }
+
+    fn set_segment(&mut self, id: SegmentId, fg: Option<AnsiColor>, bg: Option<AnsiColor>) {
+        if fg.is_some() || bg.is_some() {
+            self.segments.entry(id).or_insert((fg, bg));
+        }
+    }
+
+    /// Assign leftover colors to missing ANSI slots by hue
+    fn fill_by_hue(&mut self) {
+        for color in std::mem::take(&mut self.extra) {
+            if let Some(slot) = hue_slot(&color) {
+                self.slots.entry(slot.to_string()).or_insert(color);
+            }
+        }
+    }
+
+    fn get(&self, slot: &str) -> Option<&AnsiColor> {
+        self.slots
+            .get(slot)
+            .or_else(|| self.slots.get(slot.trim_start_matches("bright_")))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.slots.is_empty() && self.segments.is_empty()
+    }
+}
+
+/// Detect the format of a color scheme file from its extension and contents
+pub fn detect_format(path: &Path, content: &str) -> Result<ImportFormat, String> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    match extension.as_str() {
+        "json" | "omp" => {
+            let value: serde_json::Value = serde_json::from_str(content)
+                .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+            if value.get("blocks").is_some() {
+                Ok(ImportFormat::OhMyPosh)
+            } else if value.get("red").is_some() || value.get("brightRed").is_some() {
+                Ok(ImportFormat::WindowsTerminal)
+            } else {
+                Err("Unrecognised JSON: expected an Oh My Posh theme (`blocks`) or a Windows Terminal scheme (`red`, `brightRed`, ...)".to_string())
+            }
+        }
+        "toml" => {
+            let value: toml::Value = toml::from_str(content)
+                .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+            if value.get("palettes").is_some() {
+                Ok(ImportFormat::Starship)
+            } else if value.get("colors").is_some() {
+                Ok(ImportFormat::Alacritty)
+            } else {
+                Err("Unrecognised TOML: expected a Starship config with `[palettes.<name>]` or an Alacritty `[colors]` scheme".to_string())
+            }
+        }
+        "yaml" | "yml" => Ok(ImportFormat::Base16),
+        _ => Err(format!(
+            "Unsupported file type '{}': expected .toml (Starship, Alacritty), .json (Oh My Posh, Windows Terminal) or .yaml (base16)",
+            path.display()
+        )),
+    }
+}
+
+/// Import a color scheme file as a complete theme named `theme_name`
+pub fn import_theme(
+    path: &Path,
+    theme_name: &str,
+) -> Result<(Config, ImportFormat), Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let format = detect_format(path, &content)?;
+
+    let mut palette = match format {
+        ImportFormat::Starship => parse_starship(&content)?,
+        ImportFormat::OhMyPosh => parse_oh_my_posh(&content)?,
+        ImportFormat::Base16 => parse_base16(&content),
+        ImportFormat::WindowsTerminal => parse_windows_terminal(&content)?,
+        ImportFormat::Alacritty => parse_alacritty(&content)?,
+    };
+    palette.fill_by_hue();
+
+    if palette.is_empty() {
+        return Err(format!(
+            "No colors found in {} ({})",
+            path.display(),
+            format.as_str()
+        )
+        .into());
+    }
+
+    Ok((build_config(&palette, theme_name), format))
+}
+
+/// Derive a theme name from the imported file name
+pub fn theme_name_from_path(path: &Path) -> String {
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("imported");
+    sanitize_theme_name(stem)
+}
+
+/// Lowercase `name` and replace anything but ASCII letters and digits with
+/// `-`, so it can't leave the themes directory
+pub fn sanitize_theme_name(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .trim_matches('-')
+        .to_string()
+}
+
+fn build_config(palette: &Palette, theme_name: &str) -> Config {
+    // Nord's layout (colored backgrounds, dark text) suits any imported palette
+    let mut config = ThemePresets::get_nord();
+    config.theme = theme_name.to_string();
+
+    let contrast = palette
+        .get("background")
+        .or_else(|| palette.get("black"))
+        .cloned()
+        .unwrap_or(AnsiColor::Color16 { c16: 0 });
+
+    for segment in &mut config.segments {
+        let accent = SEGMENT_SLOTS
+            .iter()
+            .find(|(id, _)| *id == segment.id)
+            .and_then(|(_, slot)| palette.get(slot))
+            .cloned();
+
+        let (fg, bg) = match palette.segments.get(&segment.id) {
+            Some((fg, Some(bg))) => (fg.clone().unwrap_or(contrast.clone()), bg.clone()),
+            // Foreground-only styles (e.g. Starship's `style = "bold cyan"`) become the accent
+            Some((Some(fg), None)) => (contrast.clone(), fg.clone()),
+            _ => match accent {
+                Some(accent) => (contrast.clone(), accent),
+                None => continue,
+            },
+        };
+
+        segment.colors = ColorConfig {
+            icon: Some(fg.clone()),
+            text: Some(fg),
+            background: Some(bg),
+            ..Default::default()
+        };
+    }
+
+    config
+}
+
+/// Parse a color value: `#rrggbb`, `#rgb`, `0xrrggbb`, a 256-color index or an ANSI name
+pub fn parse_color(value: &str) -> Option<AnsiColor> {
+    let value = value.trim().trim_matches('"').trim_matches('\'');
+
+    let hex = value
+        .strip_prefix('#')
+        .or_else(|| value.strip_prefix("0x"))
+        .unwrap_or(value);
+    if hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        match hex.len() {
+            6 => {
+                return Some(AnsiColor::Rgb {
+                    r: u8::from_str_radix(&hex[0..2], 16).ok()?,
+                    g: u8::from_str_radix(&hex[2..4], 16).ok()?,
+                    b: u8::from_str_radix(&hex[4..6], 16).ok()?,
+                })
+            }
+            3 if value.starts_with('#') => {
+                let channel =
+                    |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|v| v * 17);
+                return Some(AnsiColor::Rgb {
+                    r: channel(0)?,
+                    g: channel(1)?,
+                    b: channel(2)?,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    if let Ok(index) = value.parse::<u8>() {
+        return Some(AnsiColor::Color256 { c256: index });
+    }
+
+    let slot = canonical_slot(value)?;
+    let (bright, base) = match slot.strip_prefix("bright_") {
+        Some(base) => (true, base),
+        None => (false, slot.as_str()),
+    };
+    let index = ANSI_NAMES.iter().position(|name| *name == base)? as u8;
+    Some(AnsiColor::Color16 {
+        c16: if bright { index + 8 } else { index },
+    })
+}
+
+/// Normalise a color name (`brightRed`, `bright-purple`, `Mauve`) to a palette slot
+fn canonical_slot(name: &str) -> Option<String> {
+    let name = name.trim().to_lowercase().replace(['-', ' '], "_");
+    let (bright, base) = match name.strip_prefix("bright") {
+        Some(rest) => (true, rest.trim_start_matches('_').to_string()),
+        None => (false, name),
+    };
+
+    let base =
+        if ANSI_NAMES.contains(&base.as_str()) || base == "background" || base == "foreground" {
+            base
+        } else {
+            SLOT_ALIASES
+                .iter()
+                .find(|(_, aliases)| aliases.contains(&base.as_str()))
+                .map(|(slot, _)| slot.to_string())?
+        };
+
+    if bright && ANSI_NAMES.contains(&base.as_str()) {
+        Some(format!("bright_{}", base))
+    } else {
+        Some(base)
+    }
+}
+
+/// Classify an RGB color into an ANSI hue slot; greys and 16/256 indices are skipped
+fn hue_slot(color: &AnsiColor) -> Option<&'static str> {
+    let AnsiColor::Rgb { r, g, b } = color else {
+        return None;
+    };
+    let (r, g, b) = (*r as f32 / 255.0, *g as f32 / 255.0, *b as f32 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    if max == 0.0 || delta / max < 0.2 {
+        return None;
+    }
+
+    let hue = if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+
+    Some(match hue {
+        h if !(15.0..345.0).contains(&h) => "red",
+        h if h < 70.0 => "yellow",
+        h if h < 165.0 => "green",
+        h if h < 200.0 => "cyan",
+        h if h < 260.0 => "blue",
+        _ => "magenta",
+    })
+}
+
+fn parse_starship(content: &str) -> Result<Palette, Box<dyn std::error::Error>> {
+    let value: toml::Value = toml::from_str(content)?;
+    let palettes = value
+        .get("palettes")
+        .and_then(|p| p.as_table())
+        .ok_or("Starship config has no [palettes] table")?;
+
+    // Use the active palette if one is selected, otherwise the first defined
+    let selected = value.get("palette").and_then(|p| p.as_str());
+    let colors = match selected {
+        Some(name) => palettes
+            .get(name)
+            .ok_or_else(|| format!("Palette '{}' is selected but not defined", name))?,
+        None => palettes
+            .values()
+            .next()
+            .ok_or("Starship config has an empty [palettes] table")?,
+    };
+
+    let mut palette = Palette::default();
+    let mut named = HashMap::new();
+    if let Some(colors) = colors.as_table() {
+        for (name, color) in colors {
+            if let Some(color) = color.as_str().and_then(parse_color) {
+                named.insert(name.clone(), color.clone());
+                palette.insert_named(name, color);
+            }
+        }
+    }
+
+    // Module styles may reference palette colors, e.g. `style = "bold fg:mauve bg:base"`
+    let modules: &[(&str, SegmentId)] = &[
+        ("directory", SegmentId::Directory),
+        ("git_branch", SegmentId::Git),
+        ("cmd_duration", SegmentId::Session),
+        ("time", SegmentId::Session),
+        ("rust", SegmentId::Language),
+        ("nodejs", SegmentId::Language),
+        ("python", SegmentId::Language),
+        ("golang", SegmentId::Language),
+    ];
+    for (module, id) in modules {
+        if let Some(style) = value
+            .get(module)
+            .and_then(|m| m.get("style"))
+            .and_then(|s| s.as_str())
+        {
+            let (fg, bg) = parse_starship_style(style, &named);
+            palette.set_segment(*id, fg, bg);
+        }
+    }
+
+    Ok(palette)
+}
+
+fn parse_starship_style(
+    style: &str,
+    named: &HashMap<String, AnsiColor>,
+) -> (Option<AnsiColor>, Option<AnsiColor>) {
+    let resolve = |name: &str| named.get(name).cloned().or_else(|| parse_color(name));
+    let mut fg = None;
+    let mut bg = None;
+
+    for token in style.split_whitespace() {
+        if let Some(color) = token.strip_prefix("bg:") {
+            bg = resolve(color);
+        } else if let Some(color) = token.strip_prefix("fg:") {
+            fg = resolve(color);
+        } else if !matches!(
+            token,
+            "bold"
+                | "italic"
+                | "underline"
+                | "dimmed"
+                | "inverted"
+                | "blink"
+                | "hidden"
+                | "strikethrough"
+                | "none"
+        ) {
+            fg = resolve(token);
+        }
+    }
+
+    (fg, bg)
+}
+
+fn parse_oh_my_posh(content: &str) -> Result<Palette, Box<dyn std::error::Error>> {
+    let value: serde_json::Value = serde_json::from_str(content)?;
+    let mut palette = Palette::default();
+
+    let mut named = HashMap::new();
+    if let Some(colors) = value.get("palette").and_then(|p| p.as_object()) {
+        for (name, color) in colors {
+            if let Some(color) = color.as_str().and_then(parse_color) {
+                named.insert(name.clone(), color.clone());
+                palette.insert_named(name, color);
+            }
+        }
+    }
+
+    // `p:name` references the palette; `transparent`/`parentBackground` etc. have no color
+    let resolve = |color: Option<&serde_json::Value>| -> Option<AnsiColor> {
+        let color = color?.as_str()?;
+        match color.strip_prefix("p:") {
+            Some(name) => named.get(name).cloned(),
+            None => parse_color(color),
+        }
+    };
+
+    let blocks = value
+        .get("blocks")
+        .and_then(|b| b.as_array())
+        .ok_or("Oh My Posh theme has no `blocks` array")?;
+    for segment in blocks
+        .iter()
+        .filter_map(|block| block.get("segments").and_then(|s| s.as_array()))
+        .flatten()
+    {
+        let fg = resolve(segment.get("foreground"));
+        let bg = resolve(segment.get("background"));
+
+        let segment_type = segment.get("type").and_then(|t| t.as_str()).unwrap_or("");
+        let id = match segment_type {
+            "path" => Some(SegmentId::Directory),
+            "git" => Some(SegmentId::Git),
+            "claude" => Some(SegmentId::Model),
+            "executiontime" | "time" => Some(SegmentId::Session),
+            "rust" | "node" | "python" | "go" | "java" | "ruby" | "php" | "dotnet" | "deno"
+            | "dart" | "zig" | "elixir" | "swift" => Some(SegmentId::Language),
+            _ => None,
+        };
+
+        match id {
+            Some(id) => palette.set_segment(id, fg, bg),
+            None => palette.extra.extend(bg.into_iter().chain(fg)),
+        }
+    }
+
+    Ok(palette)
+}
+
+fn parse_base16(content: &str) -> Palette {
+    let mut palette = Palette::default();
+
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        // Strip trailing comments; hex values may be quoted with a leading `#`
+        let value = value.split(" #").next().unwrap_or("");
+
+        let slot = match key.trim().trim_matches('"') {
+            "base00" => "background",
+            "base05" => "foreground",
+            "base08" => "red",
+            "base09" => "bright_red",
+            "base0A" => "yellow",
+            "base0B" => "green",
+            "base0C" => "cyan",
+            "base0D" => "blue",
+            "base0E" => "magenta",
+            "base0F" => "bright_yellow",
+            _ => continue,
+        };
+
+        if let Some(color) = parse_color(value) {
+            palette.slots.insert(slot.to_string(), color);
+        }
+    }
+
+    palette
+}
+
+fn parse_windows_terminal(content: &str) -> Result<Palette, Box<dyn std::error::Error>> {
+    let value: serde_json::Value = serde_json::from_str(content)?;
+    let mut palette = Palette::default();
+
+    if let Some(colors) = value.as_object() {
+        for (name, color) in colors {
+            if let Some(color) = color.as_str().and_then(parse_color) {
+                if canonical_slot(name).is_some() {
+                    palette.insert_named(name, color);
+                }
+            }
+        }
+    }
+
+    Ok(palette)
+}
+
+fn parse_alacritty(content: &str) -> Result<Palette, Box<dyn std::error::Error>> {
+    let value: toml::Value = toml::from_str(content)?;
+    let colors = value
+        .get("colors")
+        .ok_or("Alacritty scheme has no [colors]")?;
+    let mut palette = Palette::default();
+
+    for (group, prefix) in [("primary", ""), ("normal", ""), ("bright", "bright_")] {
+        if let Some(table) = colors.get(group).and_then(|g| g.as_table()) {
+            for (name, color) in table {
+                if let Some(color) = color.as_str().and_then(parse_color) {
+                    if canonical_slot(name).is_some() {
+                        palette.insert_named(&format!("{}{}", prefix, name), color);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(palette)
+}
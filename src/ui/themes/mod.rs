@@ -1,3 +1,4 @@
+pub mod import;
 pub mod presets;
 pub mod theme_cometix;
 pub mod theme_default;
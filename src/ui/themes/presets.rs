@@ -1,6 +1,7 @@
 // Theme presets for TUI configuration
 
-use crate::config::{Config, StyleConfig, StyleMode};
+use crate::config::migrate::CURRENT_VERSION;
+use crate::config::{ColorSupport, Config, StyleConfig, StyleMode};
 
 // Import all theme modules
 use super::{
@@ -10,30 +11,89 @@ use super::{
 
 pub struct ThemePresets;
 
+/// Names of the themes compiled into the binary
+pub const BUILTIN_THEMES: [&str; 9] = [
+    "cometix",
+    "default",
+    "minimal",
+    "gruvbox",
+    "nord",
+    "powerline-dark",
+    "powerline-light",
+    "powerline-rose-pine",
+    "powerline-tokyo-night",
+];
+
+// Guards against runaway `extends` chains in hand-written theme files
+const MAX_EXTENDS_DEPTH: usize = 8;
+
 impl ThemePresets {
+    /// Get a theme by name, falling back to the default theme if it doesn't resolve
     pub fn get_theme(theme_name: &str) -> Config {
-        // First try to load from file
-        if let Ok(config) = Self::load_theme_from_file(theme_name) {
-            return config;
+        Self::resolve_theme(theme_name).unwrap_or_else(|_| Self::get_default())
+    }
+
+    /// Resolve a theme by name: theme file first, then built-in themes
+    pub fn resolve_theme(theme_name: &str) -> Result<Config, Box<dyn std::error::Error>> {
+        let theme_path = Self::get_themes_path().join(format!("{}.toml", theme_name));
+        if theme_path.exists() {
+            return Self::load_theme_from_file(theme_name);
         }
 
-        // Fallback to built-in themes
+        Self::get_builtin_theme(theme_name).ok_or_else(|| {
+            format!(
+                "Unknown theme '{}'. Available themes: {}",
+                theme_name,
+                Self::list_available_themes().join(", ")
+            )
+            .into()
+        })
+    }
+
+    /// Get a theme compiled into the binary, ignoring theme files
+    pub fn get_builtin_theme(theme_name: &str) -> Option<Config> {
         match theme_name {
-            "cometix" => Self::get_cometix(),
-            "default" => Self::get_default(),
-            "gruvbox" => Self::get_gruvbox(),
-            "minimal" => Self::get_minimal(),
-            "nord" => Self::get_nord(),
-            "powerline-dark" => Self::get_powerline_dark(),
-            "powerline-light" => Self::get_powerline_light(),
-            "powerline-rose-pine" => Self::get_powerline_rose_pine(),
-            "powerline-tokyo-night" => Self::get_powerline_tokyo_night(),
-            _ => Self::get_default(),
+            "cometix" => Some(Self::get_cometix()),
+            "default" => Some(Self::get_default()),
+            "gruvbox" => Some(Self::get_gruvbox()),
+            "minimal" => Some(Self::get_minimal()),
+            "nord" => Some(Self::get_nord()),
+            "powerline-dark" => Some(Self::get_powerline_dark()),
+            "powerline-light" => Some(Self::get_powerline_light()),
+            "powerline-rose-pine" => Some(Self::get_powerline_rose_pine()),
+            "powerline-tokyo-night" => Some(Self::get_powerline_tokyo_night()),
+            _ => None,
         }
     }
 
-    /// Load theme from file system
+    /// Load theme from file system, resolving `extends` chains
     pub fn load_theme_from_file(theme_name: &str) -> Result<Config, Box<dyn std::error::Error>> {
+        let value = Self::load_theme_value(theme_name, &mut Vec::new())?;
+        let mut config: Config = value
+            .try_into()
+            .map_err(|e| format!("Invalid theme '{}': {}", theme_name, e))?;
+
+        // Ensure the theme field matches the requested theme
+        config.theme = theme_name.to_string();
+
+        Ok(config)
+    }
+
+    /// Read the `extends` key of a theme file, if any
+    pub fn get_theme_parent(theme_name: &str) -> Option<String> {
+        let theme_path = Self::get_themes_path().join(format!("{}.toml", theme_name));
+        let content = std::fs::read_to_string(theme_path).ok()?;
+        let value: toml::Value = toml::from_str(&content).ok()?;
+        value
+            .get("extends")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    }
+
+    fn load_theme_value(
+        theme_name: &str,
+        chain: &mut Vec<String>,
+    ) -> Result<toml::Value, Box<dyn std::error::Error>> {
         let themes_dir = Self::get_themes_path();
         let theme_path = themes_dir.join(format!("{}.toml", theme_name));
 
@@ -42,21 +102,66 @@ impl ThemePresets {
         }
 
         let content = std::fs::read_to_string(&theme_path)?;
-        let mut config: Config = toml::from_str(&content)?;
+        let mut value: toml::Value = toml::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {}", theme_path.display(), e))?;
 
-        // Ensure the theme field matches the requested theme
-        config.theme = theme_name.to_string();
+        let parent = match value.as_table_mut().and_then(|t| t.remove("extends")) {
+            Some(toml::Value::String(parent)) => parent,
+            Some(_) => {
+                return Err(format!(
+                    "Invalid theme '{}': `extends` must be a theme name",
+                    theme_name
+                )
+                .into())
+            }
+            None => return Ok(value),
+        };
 
-        Ok(config)
+        chain.push(theme_name.to_string());
+        let mut base = Self::load_parent_value(&parent, chain)
+            .map_err(|e| format!("Theme '{}' extends '{}': {}", theme_name, parent, e))?;
+        chain.pop();
+
+        crate::config::merge::merge_values(&mut base, value);
+        Ok(base)
     }
 
-    /// Get the themes directory path (~/.claude/ccline/themes/)
-    fn get_themes_path() -> std::path::PathBuf {
-        if let Some(home) = dirs::home_dir() {
-            home.join(".claude").join("ccline").join("themes")
-        } else {
-            std::path::PathBuf::from(".claude/ccline/themes")
+    fn load_parent_value(
+        parent: &str,
+        chain: &mut Vec<String>,
+    ) -> Result<toml::Value, Box<dyn std::error::Error>> {
+        let parent_path = Self::get_themes_path().join(format!("{}.toml", parent));
+
+        // A theme file extending its own built-in namesake (e.g. nord.toml with
+        // `extends = "nord"`) inherits from the compiled-in preset
+        if chain.iter().any(|name| name == parent) || !parent_path.exists() {
+            if let Some(builtin) = Self::get_builtin_theme(parent) {
+                return Ok(toml::Value::try_from(builtin)?);
+            }
+
+            if chain.iter().any(|name| name == parent) {
+                chain.push(parent.to_string());
+                return Err(format!("circular `extends` chain: {}", chain.join(" -> ")).into());
+            }
+
+            return Err(format!(
+                "Unknown theme '{}'. Available themes: {}",
+                parent,
+                Self::list_available_themes().join(", ")
+            )
+            .into());
         }
+
+        if chain.len() >= MAX_EXTENDS_DEPTH {
+            return Err(format!("`extends` chain is deeper than {}", MAX_EXTENDS_DEPTH).into());
+        }
+
+        Self::load_theme_value(parent, chain)
+    }
+
+    /// Get the themes directory path (`themes/` in the config directory)
+    pub fn get_themes_path() -> std::path::PathBuf {
+        crate::config::paths::themes_dir()
     }
 
     /// Save current config as a new theme
@@ -70,8 +175,13 @@ impl ThemePresets {
         // Create a copy of config with the correct theme name
         let mut theme_config = config.clone();
         theme_config.theme = theme_name.to_string();
+        // Key bindings belong to config.toml, not to a look
+        theme_config.keymap.clear();
 
-        let content = toml::to_string_pretty(&theme_config)?;
+        let content = crate::config::schema::with_directive(
+            crate::config::schema::SchemaKind::Theme,
+            toml::to_string_pretty(&theme_config)?,
+        );
         std::fs::write(&theme_path, content)?;
 
         Ok(())
@@ -79,25 +189,21 @@ impl ThemePresets {
 
     /// List all available themes (built-in + custom)
     pub fn list_available_themes() -> Vec<String> {
-        let mut themes = vec![
-            "cometix".to_string(),
-            "default".to_string(),
-            "minimal".to_string(),
-            "gruvbox".to_string(),
-            "nord".to_string(),
-            "powerline-dark".to_string(),
-            "powerline-light".to_string(),
-            "powerline-rose-pine".to_string(),
-            "powerline-tokyo-night".to_string(),
-        ];
-
-        // Add custom themes from file system
+        let mut themes: Vec<String> = BUILTIN_THEMES.iter().map(|t| t.to_string()).collect();
+        themes.extend(Self::list_user_themes());
+        themes
+    }
+
+    /// List theme files that don't shadow a built-in theme
+    pub fn list_user_themes() -> Vec<String> {
+        let mut themes = Vec::new();
+
         if let Ok(themes_dir) = std::fs::read_dir(Self::get_themes_path()) {
             for entry in themes_dir.flatten() {
                 if let Some(name) = entry.file_name().to_str() {
                     if name.ends_with(".toml") {
                         let theme_name = name.trim_end_matches(".toml").to_string();
-                        if !themes.contains(&theme_name) {
+                        if !BUILTIN_THEMES.contains(&theme_name.as_str()) {
                             themes.push(theme_name);
                         }
                     }
@@ -105,6 +211,7 @@ impl ThemePresets {
             }
         }
 
+        themes.sort();
         themes
     }
 
@@ -124,9 +231,17 @@ impl ThemePresets {
 
     pub fn get_cometix() -> Config {
         Config {
+            version: CURRENT_VERSION,
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: " | ".to_string(),
+                color_support: ColorSupport::Auto,
+                start_cap: None,
+                end_cap: None,
+                thin_separator: None,
+                fill: None,
+                width: None,
+                hyperlinks: false,
             },
             segments: vec![
                 theme_cometix::model_segment(),
@@ -138,16 +253,26 @@ impl ThemePresets {
                 theme_cometix::cost_segment(),
                 theme_cometix::session_segment(),
                 theme_cometix::output_style_segment(),
+                theme_cometix::language_segment(),
             ],
             theme: "cometix".to_string(),
+            keymap: Default::default(),
         }
     }
 
     pub fn get_default() -> Config {
         Config {
+            version: CURRENT_VERSION,
             style: StyleConfig {
                 mode: StyleMode::Plain,
                 separator: " | ".to_string(),
+                color_support: ColorSupport::Auto,
+                start_cap: None,
+                end_cap: None,
+                thin_separator: None,
+                fill: None,
+                width: None,
+                hyperlinks: false,
             },
             segments: vec![
                 theme_default::model_segment(),
@@ -159,16 +284,26 @@ impl ThemePresets {
                 theme_default::cost_segment(),
                 theme_default::session_segment(),
                 theme_default::output_style_segment(),
+                theme_default::language_segment(),
             ],
             theme: "default".to_string(),
+            keymap: Default::default(),
         }
     }
 
     pub fn get_minimal() -> Config {
         Config {
+            version: CURRENT_VERSION,
             style: StyleConfig {
                 mode: StyleMode::Plain,
                 separator: " │ ".to_string(),
+                color_support: ColorSupport::Auto,
+                start_cap: None,
+                end_cap: None,
+                thin_separator: None,
+                fill: None,
+                width: None,
+                hyperlinks: false,
             },
             segments: vec![
                 theme_minimal::model_segment(),
@@ -180,16 +315,26 @@ impl ThemePresets {
                 theme_minimal::cost_segment(),
                 theme_minimal::session_segment(),
                 theme_minimal::output_style_segment(),
+                theme_minimal::language_segment(),
             ],
             theme: "minimal".to_string(),
+            keymap: Default::default(),
         }
     }
 
     pub fn get_gruvbox() -> Config {
         Config {
+            version: CURRENT_VERSION,
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: " | ".to_string(),
+                color_support: ColorSupport::Auto,
+                start_cap: None,
+                end_cap: None,
+                thin_separator: None,
+                fill: None,
+                width: None,
+                hyperlinks: false,
             },
             segments: vec![
                 theme_gruvbox::model_segment(),
@@ -201,16 +346,26 @@ impl ThemePresets {
                 theme_gruvbox::cost_segment(),
                 theme_gruvbox::session_segment(),
                 theme_gruvbox::output_style_segment(),
+                theme_gruvbox::language_segment(),
             ],
             theme: "gruvbox".to_string(),
+            keymap: Default::default(),
         }
     }
 
     pub fn get_nord() -> Config {
         Config {
+            version: CURRENT_VERSION,
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                color_support: ColorSupport::Auto,
+                start_cap: None,
+                end_cap: None,
+                thin_separator: None,
+                fill: None,
+                width: None,
+                hyperlinks: false,
             },
             segments: vec![
                 theme_nord::model_segment(),
@@ -222,16 +377,26 @@ impl ThemePresets {
                 theme_nord::cost_segment(),
                 theme_nord::session_segment(),
                 theme_nord::output_style_segment(),
+                theme_nord::language_segment(),
             ],
             theme: "nord".to_string(),
+            keymap: Default::default(),
         }
     }
 
     pub fn get_powerline_dark() -> Config {
         Config {
+            version: CURRENT_VERSION,
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                color_support: ColorSupport::Auto,
+                start_cap: None,
+                end_cap: None,
+                thin_separator: None,
+                fill: None,
+                width: None,
+                hyperlinks: false,
             },
             segments: vec![
                 theme_powerline_dark::model_segment(),
@@ -243,16 +408,26 @@ impl ThemePresets {
                 theme_powerline_dark::cost_segment(),
                 theme_powerline_dark::session_segment(),
                 theme_powerline_dark::output_style_segment(),
+                theme_powerline_dark::language_segment(),
             ],
             theme: "powerline-dark".to_string(),
+            keymap: Default::default(),
         }
     }
 
     pub fn get_powerline_light() -> Config {
         Config {
+            version: CURRENT_VERSION,
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                color_support: ColorSupport::Auto,
+                start_cap: None,
+                end_cap: None,
+                thin_separator: None,
+                fill: None,
+                width: None,
+                hyperlinks: false,
             },
             segments: vec![
                 theme_powerline_light::model_segment(),
@@ -264,16 +439,26 @@ impl ThemePresets {
                 theme_powerline_light::cost_segment(),
                 theme_powerline_light::session_segment(),
                 theme_powerline_light::output_style_segment(),
+                theme_powerline_light::language_segment(),
             ],
             theme: "powerline-light".to_string(),
+            keymap: Default::default(),
         }
     }
 
     pub fn get_powerline_rose_pine() -> Config {
         Config {
+            version: CURRENT_VERSION,
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                color_support: ColorSupport::Auto,
+                start_cap: None,
+                end_cap: None,
+                thin_separator: None,
+                fill: None,
+                width: None,
+                hyperlinks: false,
             },
             segments: vec![
                 theme_powerline_rose_pine::model_segment(),
@@ -285,16 +470,26 @@ impl ThemePresets {
                 theme_powerline_rose_pine::cost_segment(),
                 theme_powerline_rose_pine::session_segment(),
                 theme_powerline_rose_pine::output_style_segment(),
+                theme_powerline_rose_pine::language_segment(),
             ],
             theme: "powerline-rose-pine".to_string(),
+            keymap: Default::default(),
         }
     }
 
     pub fn get_powerline_tokyo_night() -> Config {
         Config {
+            version: CURRENT_VERSION,
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                color_support: ColorSupport::Auto,
+                start_cap: None,
+                end_cap: None,
+                thin_separator: None,
+                fill: None,
+                width: None,
+                hyperlinks: false,
             },
             segments: vec![
                 theme_powerline_tokyo_night::model_segment(),
@@ -306,8 +501,10 @@ impl ThemePresets {
                 theme_powerline_tokyo_night::cost_segment(),
                 theme_powerline_tokyo_night::session_segment(),
                 theme_powerline_tokyo_night::output_style_segment(),
+                theme_powerline_tokyo_night::language_segment(),
             ],
             theme: "powerline-tokyo-night".to_string(),
+            keymap: Default::default(),
         }
     }
 }
@@ -1,5 +1,5 @@
 use crate::config::{
-    AnsiColor, ColorConfig, IconConfig, SegmentConfig, SegmentId, TextStyleConfig,
+    AnsiColor, ColorConfig, IconConfig, SegmentAlign, SegmentConfig, SegmentId, TextStyleConfig,
 };
 use crate::utils::subscription::SubscriptionApiClient;
 use std::collections::HashMap;
@@ -16,8 +16,14 @@ pub fn model_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 14 }),
             text: Some(AnsiColor::Color16 { c16: 14 }),
             background: None,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        separator: None,
+        align: SegmentAlign::Left,
         options: HashMap::new(),
     }
 }
@@ -34,9 +40,26 @@ pub fn directory_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 11 }),
             text: Some(AnsiColor::Color16 { c16: 10 }),
             background: None,
+            ..Default::default()
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        separator: None,
+        align: SegmentAlign::Left,
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert(
+                "mode".to_string(),
+                serde_json::Value::String("name".to_string()),
+            );
+            opts.insert(
+                "max_components".to_string(),
+                serde_json::Value::Number(0.into()),
+            );
+            opts
         },
-        styles: TextStyleConfig { text_bold: true },
-        options: HashMap::new(),
     }
 }
 
@@ -52,8 +75,14 @@ pub fn git_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 12 }),
             text: Some(AnsiColor::Color16 { c16: 12 }),
             background: None,
+            ..Default::default()
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
+        separator: None,
+        align: SegmentAlign::Left,
         options: {
             let mut opts = HashMap::new();
             opts.insert("show_sha".to_string(), serde_json::Value::Bool(false));
@@ -74,8 +103,14 @@ pub fn context_window_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 13 }),
             text: Some(AnsiColor::Color16 { c16: 13 }),
             background: None,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        separator: None,
+        align: SegmentAlign::Left,
         options: HashMap::new(),
     }
 }
@@ -92,8 +127,14 @@ pub fn cost_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 3 }),
             text: Some(AnsiColor::Color16 { c16: 3 }),
             background: None,
+            ..Default::default()
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
+        separator: None,
+        align: SegmentAlign::Left,
         options: HashMap::new(),
     }
 }
@@ -110,8 +151,14 @@ pub fn session_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 2 }),
             text: Some(AnsiColor::Color16 { c16: 2 }),
             background: None,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        separator: None,
+        align: SegmentAlign::Left,
         options: HashMap::new(),
     }
 }
@@ -128,8 +175,14 @@ pub fn output_style_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 6 }),
             text: Some(AnsiColor::Color16 { c16: 6 }),
             background: None,
+            ..Default::default()
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
+        separator: None,
+        align: SegmentAlign::Left,
         options: HashMap::new(),
     }
 }
@@ -146,8 +199,11 @@ pub fn usage_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 14 }),
             text: Some(AnsiColor::Color16 { c16: 14 }),
             background: None,
+            ..Default::default()
         },
         styles: TextStyleConfig::default(),
+        separator: None,
+        align: SegmentAlign::Left,
         options: {
             let mut opts = HashMap::new();
             opts.insert(
@@ -176,8 +232,11 @@ pub fn cubence_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 10 }),
             text: Some(AnsiColor::Color16 { c16: 10 }),
             background: None,
+            ..Default::default()
         },
         styles: TextStyleConfig::default(),
+        separator: None,
+        align: SegmentAlign::Left,
         options: {
             let mut opts = HashMap::new();
             opts.insert(
@@ -193,3 +252,36 @@ pub fn cubence_segment() -> SegmentConfig {
         },
     }
 }
+
+pub fn language_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Language,
+        enabled: false,
+        // Empty, so the detected language's icon shows
+        icon: IconConfig {
+            plain: String::new(),
+            nerd_font: String::new(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Color16 { c16: 6 }),
+            text: Some(AnsiColor::Color16 { c16: 6 }),
+            background: None,
+            ..Default::default()
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        separator: None,
+        align: SegmentAlign::Left,
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert(
+                "cache_duration".to_string(),
+                serde_json::Value::Number(300.into()),
+            );
+            opts.insert("show_version".to_string(), serde_json::Value::Bool(true));
+            opts
+        },
+    }
+}
@@ -1,5 +1,5 @@
 use crate::config::{
-    AnsiColor, ColorConfig, IconConfig, SegmentConfig, SegmentId, TextStyleConfig,
+    AnsiColor, ColorConfig, IconConfig, SegmentAlign, SegmentConfig, SegmentId, TextStyleConfig,
 };
 use crate::utils::subscription::SubscriptionApiClient;
 use std::collections::HashMap;
@@ -16,8 +16,11 @@ pub fn model_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 14 }), // Cyan
             text: Some(AnsiColor::Color16 { c16: 14 }),
             background: None,
+            ..Default::default()
         },
         styles: TextStyleConfig::default(),
+        separator: None,
+        align: SegmentAlign::Left,
         options: HashMap::new(),
     }
 }
@@ -34,9 +37,23 @@ pub fn directory_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 11 }), // Yellow
             text: Some(AnsiColor::Color16 { c16: 10 }), // Green
             background: None,
+            ..Default::default()
         },
         styles: TextStyleConfig::default(),
-        options: HashMap::new(),
+        separator: None,
+        align: SegmentAlign::Left,
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert(
+                "mode".to_string(),
+                serde_json::Value::String("name".to_string()),
+            );
+            opts.insert(
+                "max_components".to_string(),
+                serde_json::Value::Number(0.into()),
+            );
+            opts
+        },
     }
 }
 
@@ -52,8 +69,11 @@ pub fn git_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 12 }), // Blue
             text: Some(AnsiColor::Color16 { c16: 12 }),
             background: None,
+            ..Default::default()
         },
         styles: TextStyleConfig::default(),
+        separator: None,
+        align: SegmentAlign::Left,
         options: {
             let mut opts = HashMap::new();
             opts.insert("show_sha".to_string(), serde_json::Value::Bool(false));
@@ -74,8 +94,11 @@ pub fn context_window_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 13 }), // Magenta
             text: Some(AnsiColor::Color16 { c16: 13 }),
             background: None,
+            ..Default::default()
         },
         styles: TextStyleConfig::default(),
+        separator: None,
+        align: SegmentAlign::Left,
         options: HashMap::new(),
     }
 }
@@ -92,8 +115,11 @@ pub fn usage_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 14 }), // Cyan
             text: Some(AnsiColor::Color16 { c16: 14 }),
             background: None,
+            ..Default::default()
         },
         styles: TextStyleConfig::default(),
+        separator: None,
+        align: SegmentAlign::Left,
         options: {
             let mut opts = HashMap::new();
             opts.insert(
@@ -122,8 +148,11 @@ pub fn cubence_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 10 }), // Green
             text: Some(AnsiColor::Color16 { c16: 10 }),
             background: None,
+            ..Default::default()
         },
         styles: TextStyleConfig::default(),
+        separator: None,
+        align: SegmentAlign::Left,
         options: {
             let mut opts = HashMap::new();
             opts.insert(
@@ -152,8 +181,11 @@ pub fn cost_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 3 }), // Yellow
             text: Some(AnsiColor::Color16 { c16: 3 }),
             background: None,
+            ..Default::default()
         },
         styles: TextStyleConfig::default(),
+        separator: None,
+        align: SegmentAlign::Left,
         options: HashMap::new(),
     }
 }
@@ -170,8 +202,11 @@ pub fn session_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 2 }), // Green
             text: Some(AnsiColor::Color16 { c16: 2 }),
             background: None,
+            ..Default::default()
         },
         styles: TextStyleConfig::default(),
+        separator: None,
+        align: SegmentAlign::Left,
         options: HashMap::new(),
     }
 }
@@ -188,8 +223,41 @@ pub fn output_style_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 6 }), // Cyan
             text: Some(AnsiColor::Color16 { c16: 6 }),
             background: None,
+            ..Default::default()
         },
         styles: TextStyleConfig::default(),
+        separator: None,
+        align: SegmentAlign::Left,
         options: HashMap::new(),
     }
 }
+
+pub fn language_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Language,
+        enabled: false,
+        // Empty, so the detected language's icon shows
+        icon: IconConfig {
+            plain: String::new(),
+            nerd_font: String::new(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Color16 { c16: 6 }), // Cyan
+            text: Some(AnsiColor::Color16 { c16: 6 }),
+            background: None,
+            ..Default::default()
+        },
+        styles: TextStyleConfig::default(),
+        separator: None,
+        align: SegmentAlign::Left,
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert(
+                "cache_duration".to_string(),
+                serde_json::Value::Number(300.into()),
+            );
+            opts.insert("show_version".to_string(), serde_json::Value::Bool(true));
+            opts
+        },
+    }
+}
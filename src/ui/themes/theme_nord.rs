@@ -1,5 +1,5 @@
 use crate::config::{
-    AnsiColor, ColorConfig, IconConfig, SegmentConfig, SegmentId, TextStyleConfig,
+    AnsiColor, ColorConfig, IconConfig, SegmentAlign, SegmentConfig, SegmentId, TextStyleConfig,
 };
 use crate::utils::subscription::SubscriptionApiClient;
 use std::collections::HashMap;
@@ -28,8 +28,11 @@ pub fn model_segment() -> SegmentConfig {
                 g: 192,
                 b: 208,
             }),
+            ..Default::default()
         },
         styles: TextStyleConfig::default(),
+        separator: None,
+        align: SegmentAlign::Left,
         options: HashMap::new(),
     }
 }
@@ -58,9 +61,23 @@ pub fn directory_segment() -> SegmentConfig {
                 g: 190,
                 b: 140,
             }),
+            ..Default::default()
         },
         styles: TextStyleConfig::default(),
-        options: HashMap::new(),
+        separator: None,
+        align: SegmentAlign::Left,
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert(
+                "mode".to_string(),
+                serde_json::Value::String("name".to_string()),
+            );
+            opts.insert(
+                "max_components".to_string(),
+                serde_json::Value::Number(0.into()),
+            );
+            opts
+        },
     }
 }
 
@@ -88,8 +105,11 @@ pub fn git_segment() -> SegmentConfig {
                 g: 161,
                 b: 193,
             }),
+            ..Default::default()
         },
         styles: TextStyleConfig::default(),
+        separator: None,
+        align: SegmentAlign::Left,
         options: {
             let mut opts = HashMap::new();
             opts.insert("show_sha".to_string(), serde_json::Value::Bool(false));
@@ -122,8 +142,11 @@ pub fn context_window_segment() -> SegmentConfig {
                 g: 142,
                 b: 173,
             }),
+            ..Default::default()
         },
         styles: TextStyleConfig::default(),
+        separator: None,
+        align: SegmentAlign::Left,
         options: HashMap::new(),
     }
 }
@@ -152,8 +175,11 @@ pub fn cost_segment() -> SegmentConfig {
                 g: 203,
                 b: 139,
             }), // Nord yellow background
+            ..Default::default()
         },
         styles: TextStyleConfig::default(),
+        separator: None,
+        align: SegmentAlign::Left,
         options: HashMap::new(),
     }
 }
@@ -182,8 +208,11 @@ pub fn session_segment() -> SegmentConfig {
                 g: 190,
                 b: 140,
             }), // Nord green background
+            ..Default::default()
         },
         styles: TextStyleConfig::default(),
+        separator: None,
+        align: SegmentAlign::Left,
         options: HashMap::new(),
     }
 }
@@ -212,8 +241,11 @@ pub fn output_style_segment() -> SegmentConfig {
                 g: 192,
                 b: 208,
             }), // Nord cyan background
+            ..Default::default()
         },
         styles: TextStyleConfig::default(),
+        separator: None,
+        align: SegmentAlign::Left,
         options: HashMap::new(),
     }
 }
@@ -230,8 +262,11 @@ pub fn usage_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 14 }),
             text: Some(AnsiColor::Color16 { c16: 14 }),
             background: None,
+            ..Default::default()
         },
         styles: TextStyleConfig::default(),
+        separator: None,
+        align: SegmentAlign::Left,
         options: {
             let mut opts = HashMap::new();
             opts.insert(
@@ -272,8 +307,11 @@ pub fn cubence_segment() -> SegmentConfig {
                 g: 190,
                 b: 140,
             }),
+            ..Default::default()
         },
         styles: TextStyleConfig::default(),
+        separator: None,
+        align: SegmentAlign::Left,
         options: {
             let mut opts = HashMap::new();
             opts.insert(
@@ -289,3 +327,45 @@ pub fn cubence_segment() -> SegmentConfig {
         },
     }
 }
+
+pub fn language_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Language,
+        enabled: false,
+        // Empty, so the detected language's icon shows
+        icon: IconConfig {
+            plain: String::new(),
+            nerd_font: String::new(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 136,
+                g: 192,
+                b: 208,
+            }), // Nord cyan background
+            ..Default::default()
+        },
+        styles: TextStyleConfig::default(),
+        separator: None,
+        align: SegmentAlign::Left,
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert(
+                "cache_duration".to_string(),
+                serde_json::Value::Number(300.into()),
+            );
+            opts.insert("show_version".to_string(), serde_json::Value::Bool(true));
+            opts
+        },
+    }
+}
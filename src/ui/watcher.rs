@@ -0,0 +1,57 @@
+// Polls the configurator's files for edits made outside the TUI
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// What a file looked like when last seen: modification time and size, or
+/// `None` when it didn't exist
+type Stamp = Option<(SystemTime, u64)>;
+
+#[derive(Debug, Default)]
+pub struct FileWatcher {
+    stamps: HashMap<PathBuf, Stamp>,
+}
+
+impl FileWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Track exactly `paths`. Files not tracked before are taken as seen, so
+    /// switching themes doesn't count as a change.
+    pub fn watch(&mut self, paths: &[PathBuf]) {
+        self.stamps.retain(|path, _| paths.contains(path));
+        for path in paths {
+            self.stamps
+                .entry(path.clone())
+                .or_insert_with(|| stamp(path));
+        }
+    }
+
+    /// Files changed since the last call, or since `mark_seen`
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for (path, seen) in self.stamps.iter_mut() {
+            let current = stamp(path);
+            if current != *seen {
+                *seen = current;
+                changed.push(path.clone());
+            }
+        }
+        changed.sort();
+        changed
+    }
+
+    /// Take every file as it is now as seen, e.g. after the TUI wrote it
+    pub fn mark_seen(&mut self) {
+        for (path, seen) in self.stamps.iter_mut() {
+            *seen = stamp(path);
+        }
+    }
+}
+
+fn stamp(path: &Path) -> Stamp {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
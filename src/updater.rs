@@ -38,6 +38,9 @@ pub struct UpdateState {
     pub last_check: Option<DateTime<Utc>>,
     pub current_version: String,
     pub latest_version: Option<String>,
+    /// Release page of `latest_version`
+    #[serde(default)]
+    pub release_url: Option<String>,
     pub update_pid: Option<u32>,
 }
 
@@ -73,16 +76,11 @@ impl UpdateState {
         }
     }
 
-    /// Load update state from config directory and trigger auto-check if needed
+    /// Load update state from the cache directory and trigger auto-check if needed
     pub fn load() -> Self {
         #[cfg(feature = "self-update")]
         {
-            let config_dir = dirs::home_dir()
-                .unwrap_or_default()
-                .join(".claude")
-                .join("ccline");
-
-            let state_file = config_dir.join(".update_state.json");
+            let state_file = crate::config::paths::cache_dir().join(".update_state.json");
 
             let mut state = if let Ok(content) = std::fs::read_to_string(&state_file) {
                 if let Ok(state) = serde_json::from_str::<UpdateState>(&content) {
@@ -132,6 +130,7 @@ impl UpdateState {
                                 };
                             }
                             state.latest_version = Some(release.version());
+                            state.release_url = Some(release.html_url.clone());
                         }
                         Ok(None) => {
                             state.status = UpdateStatus::Idle;
@@ -188,17 +187,13 @@ impl UpdateState {
         false
     }
 
-    /// Save update state to config directory
+    /// Save update state to the cache directory
     pub fn save(&self) -> Result<(), std::io::Error> {
         #[cfg(feature = "self-update")]
         {
-            let config_dir = dirs::home_dir()
-                .unwrap_or_default()
-                .join(".claude")
-                .join("ccline");
-
-            std::fs::create_dir_all(&config_dir)?;
-            let state_file = config_dir.join(".update_state.json");
+            let cache_dir = crate::config::paths::cache_dir();
+            std::fs::create_dir_all(&cache_dir)?;
+            let state_file = cache_dir.join(".update_state.json");
 
             let content = serde_json::to_string_pretty(self)?;
             std::fs::write(&state_file, content)?;
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Deserialize, Serialize)]
 struct ClaudeSettings {
@@ -12,37 +12,51 @@ struct ClaudeEnv {
     anthropic_auth_token: Option<String>,
     #[serde(rename = "ANTHROPIC_BASE_URL")]
     anthropic_base_url: Option<String>,
+    #[serde(rename = "HTTPS_PROXY")]
+    https_proxy: Option<String>,
+    #[serde(rename = "HTTP_PROXY")]
+    http_proxy: Option<String>,
 }
 
 /// Get OAuth token from ~/.claude/settings.json
 pub fn get_oauth_token() -> Option<String> {
-    let settings_path = get_settings_path()?;
-
-    if !settings_path.exists() {
-        return None;
-    }
-
-    let content = std::fs::read_to_string(&settings_path).ok()?;
-    let settings: ClaudeSettings = serde_json::from_str(&content).ok()?;
+    get_oauth_token_from(&get_settings_path()?)
+}
 
-    settings.env.and_then(|env| env.anthropic_auth_token)
+/// Get OAuth token from the given Claude settings file
+pub fn get_oauth_token_from(settings_path: &Path) -> Option<String> {
+    read_env(settings_path)?.anthropic_auth_token
 }
 
 /// Get Anthropic base URL from ~/.claude/settings.json
 pub fn get_anthropic_base_url() -> Option<String> {
-    let settings_path = get_settings_path()?;
-
-    if !settings_path.exists() {
-        return None;
-    }
+    get_anthropic_base_url_from(&get_settings_path()?)
+}
 
-    let content = std::fs::read_to_string(&settings_path).ok()?;
-    let settings: ClaudeSettings = serde_json::from_str(&content).ok()?;
+/// Get Anthropic base URL from the given Claude settings file
+pub fn get_anthropic_base_url_from(settings_path: &Path) -> Option<String> {
+    read_env(settings_path)?.anthropic_base_url
+}
 
-    settings.env.and_then(|env| env.anthropic_base_url)
+/// Get the proxy from the given Claude settings file (HTTPS_PROXY first, then
+/// HTTP_PROXY), falling back to the same environment variables
+pub fn get_proxy_from(settings_path: &Path) -> Option<String> {
+    read_env(settings_path)
+        .and_then(|env| env.https_proxy.or(env.http_proxy))
+        .or_else(|| {
+            std::env::var("HTTPS_PROXY")
+                .or_else(|_| std::env::var("HTTP_PROXY"))
+                .ok()
+        })
 }
 
-fn get_settings_path() -> Option<PathBuf> {
+pub fn get_settings_path() -> Option<PathBuf> {
     let home = dirs::home_dir()?;
     Some(home.join(".claude").join("settings.json"))
 }
+
+fn read_env(settings_path: &Path) -> Option<ClaudeEnv> {
+    let content = std::fs::read_to_string(settings_path).ok()?;
+    let settings: ClaudeSettings = serde_json::from_str(&content).ok()?;
+    settings.env
+}
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Local, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use std::time::Duration;
 
 use super::credentials;
@@ -48,7 +49,15 @@ struct SubscriptionInfoCache {
     cached_at: String,
 }
 
-pub struct SubscriptionApiClient;
+/// Client for the Cubence subscription and health endpoints. The base URL,
+/// Claude settings file and cache directory default to the user's setup and
+/// can be overridden, e.g. to point at a local test server.
+#[derive(Debug, Clone, Default)]
+pub struct SubscriptionApiClient {
+    base_url: Option<String>,
+    settings_path: Option<PathBuf>,
+    cache_dir: Option<PathBuf>,
+}
 
 impl SubscriptionApiClient {
     const DEFAULT_BASE_URL: &'static str = "https://cubence.com/api";
@@ -56,66 +65,85 @@ impl SubscriptionApiClient {
     const HEALTH_PATH: &'static str = "/health";
     const CACHE_FILE: &'static str = ".subscription_info_cache.json";
 
-    fn get_proxy_from_settings() -> Option<String> {
-        // First try to read from Claude settings file
-        if let Ok(home) = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE")) {
-            let settings_path = format!("{}/.claude/settings.json", home);
-            if let Ok(content) = std::fs::read_to_string(&settings_path) {
-                if let Ok(settings) = serde_json::from_str::<serde_json::Value>(&content) {
-                    // Try HTTPS_PROXY first, then HTTP_PROXY from settings file
-                    if let Some(proxy) = settings
-                        .get("env")
-                        .and_then(|env| env.get("HTTPS_PROXY"))
-                        .or_else(|| settings.get("env").and_then(|env| env.get("HTTP_PROXY")))
-                        .and_then(|v| v.as_str())
-                        .map(|s| s.to_string())
-                    {
-                        return Some(proxy);
-                    }
-                }
-            }
-        }
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-        // Fallback to environment variables if not found in settings file
-        std::env::var("HTTPS_PROXY")
-            .or_else(|_| std::env::var("HTTP_PROXY"))
-            .ok()
+    /// Use this base URL instead of ANTHROPIC_BASE_URL from settings.json
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Read the token, base URL and proxy from this settings file instead of
+    /// ~/.claude/settings.json
+    pub fn with_settings_path(mut self, settings_path: impl Into<PathBuf>) -> Self {
+        self.settings_path = Some(settings_path.into());
+        self
+    }
+
+    /// Keep the response cache in this directory instead of the cache directory
+    pub fn with_cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(cache_dir.into());
+        self
     }
 
     pub fn default_url() -> String {
-        let base_url = credentials::get_anthropic_base_url()
-            .unwrap_or_else(|| Self::DEFAULT_BASE_URL.to_string());
-        Self::build_subscription_url(&base_url)
+        Self::new().subscription_url()
     }
 
-    fn build_subscription_url(base_url: &str) -> String {
+    fn settings_path(&self) -> Option<PathBuf> {
+        self.settings_path
+            .clone()
+            .or_else(credentials::get_settings_path)
+    }
+
+    pub fn base_url(&self) -> String {
+        self.base_url
+            .clone()
+            .or_else(|| {
+                self.settings_path()
+                    .and_then(|path| credentials::get_anthropic_base_url_from(&path))
+            })
+            .unwrap_or_else(|| Self::DEFAULT_BASE_URL.to_string())
+    }
+
+    pub fn subscription_url(&self) -> String {
         format!(
             "{}{}",
-            base_url.trim_end_matches('/'),
+            self.base_url().trim_end_matches('/'),
             Self::SUBSCRIPTION_PATH
         )
     }
 
-    pub fn fetch(api_url: &str, timeout_secs: u64) -> Result<SubscriptionInfo, String> {
-        let token = credentials::get_oauth_token()
-            .ok_or_else(|| "Failed to get OAuth token".to_string())?;
-
+    fn build_agent(&self, timeout_secs: u64) -> ureq::Agent {
         let mut agent_builder = ureq::AgentBuilder::new()
             .timeout(Duration::from_secs(timeout_secs))
             .timeout_read(Duration::from_secs(timeout_secs))
             .timeout_write(Duration::from_secs(timeout_secs));
 
         // Configure proxy from Claude settings if available
-        if let Some(proxy_url) = Self::get_proxy_from_settings() {
+        if let Some(proxy_url) = self
+            .settings_path()
+            .and_then(|path| credentials::get_proxy_from(&path))
+        {
             if let Ok(proxy) = ureq::Proxy::new(&proxy_url) {
                 agent_builder = agent_builder.proxy(proxy);
             }
         }
 
-        let agent = agent_builder.build();
+        agent_builder.build()
+    }
 
-        let response = agent
-            .get(api_url)
+    pub fn fetch(&self, timeout_secs: u64) -> Result<SubscriptionInfo, String> {
+        let token = self
+            .settings_path()
+            .and_then(|path| credentials::get_oauth_token_from(&path))
+            .ok_or_else(|| "Failed to get OAuth token".to_string())?;
+
+        let response = self
+            .build_agent(timeout_secs)
+            .get(&self.subscription_url())
             .set("Authorization", &format!("Bearer {}", token))
             .set("Content-Type", "application/json")
             .call()
@@ -131,28 +159,33 @@ impl SubscriptionApiClient {
     }
 
     pub fn get_with_cache(
-        api_url: &str,
+        &self,
         timeout_secs: u64,
         cache_duration_secs: u64,
     ) -> Result<SubscriptionInfo, String> {
         // Try memory/disk cache first
-        if let Some(cache) = Self::load_cache() {
+        if let Some(cache) = self.load_cache() {
             if Self::is_cache_valid(&cache, cache_duration_secs) {
                 return Ok(cache.info);
             }
         }
 
-        let info = Self::fetch(api_url, timeout_secs)?;
+        let info = self.fetch(timeout_secs)?;
 
         let cache = SubscriptionInfoCache {
             info: info.clone(),
             cached_at: Utc::now().to_rfc3339(),
         };
-        Self::save_cache(&cache);
+        self.save_cache(&cache);
 
         Ok(info)
     }
 
+    /// The last cached subscription info, however old
+    pub fn cached_info(&self) -> Option<SubscriptionInfo> {
+        self.load_cache().map(|cache| cache.info)
+    }
+
     pub fn format_reset_time(reset_at: Option<i64>) -> Option<String> {
         let ts = reset_at?;
         let datetime = Local.timestamp_opt(ts, 0).single()?;
@@ -163,13 +196,16 @@ impl SubscriptionApiClient {
         (units as f64) / 1_000_000.0
     }
 
-    fn get_cache_path() -> Option<std::path::PathBuf> {
-        let home = dirs::home_dir()?;
-        Some(home.join(".claude").join("ccline").join(Self::CACHE_FILE))
+    fn get_cache_path(&self) -> Option<PathBuf> {
+        let dir = self
+            .cache_dir
+            .clone()
+            .unwrap_or_else(crate::config::paths::cache_dir);
+        Some(dir.join(Self::CACHE_FILE))
     }
 
-    fn load_cache() -> Option<SubscriptionInfoCache> {
-        let cache_path = Self::get_cache_path()?;
+    fn load_cache(&self) -> Option<SubscriptionInfoCache> {
+        let cache_path = self.get_cache_path()?;
         if !cache_path.exists() {
             return None;
         }
@@ -178,8 +214,8 @@ impl SubscriptionApiClient {
         serde_json::from_str(&content).ok()
     }
 
-    fn save_cache(cache: &SubscriptionInfoCache) {
-        if let Some(path) = Self::get_cache_path() {
+    fn save_cache(&self, cache: &SubscriptionInfoCache) {
+        if let Some(path) = self.get_cache_path() {
             if let Some(parent) = path.parent() {
                 let _ = std::fs::create_dir_all(parent);
             }
@@ -205,22 +241,13 @@ impl SubscriptionApiClient {
     }
 
     /// Check health endpoint and return latency in milliseconds
-    pub fn check_health_latency(base_url: &str, timeout_secs: u64) -> Result<u64, String> {
-        let health_url = format!("{}{}", base_url.trim_end_matches('/'), Self::HEALTH_PATH);
-
-        let mut agent_builder = ureq::AgentBuilder::new()
-            .timeout(Duration::from_secs(timeout_secs))
-            .timeout_read(Duration::from_secs(timeout_secs))
-            .timeout_write(Duration::from_secs(timeout_secs));
-
-        // Configure proxy from Claude settings if available
-        if let Some(proxy_url) = Self::get_proxy_from_settings() {
-            if let Ok(proxy) = ureq::Proxy::new(&proxy_url) {
-                agent_builder = agent_builder.proxy(proxy);
-            }
-        }
-
-        let agent = agent_builder.build();
+    pub fn check_health_latency(&self, timeout_secs: u64) -> Result<u64, String> {
+        let health_url = format!(
+            "{}{}",
+            self.base_url().trim_end_matches('/'),
+            Self::HEALTH_PATH
+        );
+        let agent = self.build_agent(timeout_secs);
 
         // Measure request latency
         let start = std::time::Instant::now();
@@ -0,0 +1,418 @@
+//! The Cubence and usage API clients against a local stub server.
+
+use cubenceline::config::{ColorSupport, Config, InputData, SegmentId};
+use cubenceline::core::segments::{CubenceSegment, Segment, SegmentData, UsageSegment};
+use cubenceline::core::StatusLineGenerator;
+use cubenceline::utils::subscription::SubscriptionApiClient;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+const SUBSCRIPTION_INFO: &str = r#"{
+    "normal_balance": { "amount_dollar": 12.5, "amount_units": 12500000 },
+    "subscription_window": {
+        "five_hour": { "limit": 0, "remaining": 0, "used": 0 },
+        "weekly": { "limit": 0, "remaining": 0, "used": 0 }
+    },
+    "timestamp": 1760000000
+}"#;
+
+const USAGE: &str = r#"{
+    "five_hour": { "utilization": 42.0, "resets_at": null },
+    "seven_day": { "utilization": 10.0, "resets_at": "2025-11-02T15:00:00Z" }
+}"#;
+
+const MALFORMED: &str = r#"{"five_hour": "#;
+
+/// How the stub server answers every request
+#[derive(Clone, Copy)]
+struct Reply {
+    status: u16,
+    body: &'static str,
+    delay: Duration,
+}
+
+impl Reply {
+    fn ok(body: &'static str) -> Self {
+        Self::status(200, body)
+    }
+
+    fn status(status: u16, body: &'static str) -> Self {
+        Self {
+            status,
+            body,
+            delay: Duration::ZERO,
+        }
+    }
+
+    fn slow(body: &'static str) -> Self {
+        Self {
+            delay: Duration::from_secs(3),
+            ..Self::ok(body)
+        }
+    }
+}
+
+/// A local HTTP server answering `/health` with 200 and everything else with
+/// the given reply
+struct StubServer {
+    url: String,
+    hits: Arc<AtomicUsize>,
+}
+
+impl StubServer {
+    fn start(reply: Reply) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let hits = Arc::new(AtomicUsize::new(0));
+
+        let counter = hits.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let counter = counter.clone();
+                std::thread::spawn(move || handle(stream, reply, &counter));
+            }
+        });
+
+        Self { url, hits }
+    }
+
+    /// Requests other than health checks
+    fn hits(&self) -> usize {
+        self.hits.load(Ordering::SeqCst)
+    }
+}
+
+fn handle(stream: TcpStream, reply: Reply, hits: &AtomicUsize) {
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    // Skip the headers; the clients never send a body
+    let mut line = String::new();
+    while reader.read_line(&mut line).map(|n| n > 2).unwrap_or(false) {
+        line.clear();
+    }
+
+    let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+    let reply = if path.ends_with("/health") {
+        Reply::ok("ok")
+    } else {
+        hits.fetch_add(1, Ordering::SeqCst);
+        reply
+    };
+
+    std::thread::sleep(reply.delay);
+    let response = format!(
+        "HTTP/1.1 {} Stub\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        reply.status,
+        reply.body.len(),
+        reply.body
+    );
+    let _ = (&stream).write_all(response.as_bytes());
+}
+
+/// A scratch directory holding a settings.json with a token, and an empty cache dir
+struct Sandbox {
+    dir: PathBuf,
+}
+
+impl Sandbox {
+    fn new(name: &str) -> Self {
+        let dir =
+            std::env::temp_dir().join(format!("cubenceline-api-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("cache")).unwrap();
+        std::fs::write(
+            dir.join("settings.json"),
+            r#"{ "env": { "ANTHROPIC_AUTH_TOKEN": "test-token" } }"#,
+        )
+        .unwrap();
+        Self { dir }
+    }
+
+    fn settings_path(&self) -> PathBuf {
+        self.dir.join("settings.json")
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.dir.join("cache")
+    }
+
+    fn client(&self, server: &StubServer) -> SubscriptionApiClient {
+        SubscriptionApiClient::new()
+            .with_base_url(&server.url)
+            .with_settings_path(self.settings_path())
+            .with_cache_dir(self.cache_dir())
+    }
+
+    fn cubence(&self, server: &StubServer) -> CubenceSegment {
+        CubenceSegment::new()
+            .with_base_url(&server.url)
+            .with_settings_path(self.settings_path())
+            .with_cache_dir(self.cache_dir())
+            .with_timeout(1)
+    }
+
+    fn usage(&self, server: &StubServer) -> UsageSegment {
+        UsageSegment::new()
+            .with_base_url(&server.url)
+            .with_settings_path(self.settings_path())
+            .with_cache_dir(self.cache_dir())
+            .with_user_agent("claude-code")
+            .with_timeout(1)
+    }
+}
+
+impl Drop for Sandbox {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn input() -> InputData {
+    serde_json::from_str(
+        r#"{
+            "model": { "id": "claude-sonnet-4-5", "display_name": "Sonnet 4.5" },
+            "workspace": { "current_dir": "/tmp" },
+            "transcript_path": "/nonexistent.jsonl"
+        }"#,
+    )
+    .unwrap()
+}
+
+fn render(id: SegmentId, data: SegmentData) -> String {
+    let config = Config::default();
+    let mut segment = config
+        .segments
+        .iter()
+        .find(|segment| segment.id == id)
+        .cloned()
+        .unwrap();
+    segment.enabled = true;
+
+    StatusLineGenerator::new(config)
+        .with_color_support(ColorSupport::None)
+        .generate(vec![(segment, data)])
+}
+
+#[test]
+fn subscription_fetch_parses_info() {
+    let server = StubServer::start(Reply::ok(SUBSCRIPTION_INFO));
+    let sandbox = Sandbox::new("subscription-ok");
+
+    let info = sandbox.client(&server).fetch(1).unwrap();
+
+    assert_eq!(info.normal_balance.amount_dollar, 12.5);
+    assert_eq!(info.timestamp, 1760000000);
+}
+
+#[test]
+fn subscription_fetch_reports_http_errors() {
+    let sandbox = Sandbox::new("subscription-status");
+
+    let unauthorized = StubServer::start(Reply::status(401, r#"{"error":"unauthorized"}"#));
+    let error = sandbox.client(&unauthorized).fetch(1).unwrap_err();
+    assert!(error.starts_with("API request failed"), "{}", error);
+    assert!(error.contains("401"), "{}", error);
+
+    let server_error = StubServer::start(Reply::status(500, "{}"));
+    let error = sandbox.client(&server_error).fetch(1).unwrap_err();
+    assert!(error.contains("500"), "{}", error);
+}
+
+#[test]
+fn subscription_fetch_reports_malformed_json() {
+    let server = StubServer::start(Reply::ok(MALFORMED));
+    let sandbox = Sandbox::new("subscription-malformed");
+
+    let error = sandbox.client(&server).fetch(1).unwrap_err();
+
+    assert!(error.starts_with("Failed to parse response"), "{}", error);
+}
+
+#[test]
+fn subscription_fetch_times_out() {
+    let server = StubServer::start(Reply::slow(SUBSCRIPTION_INFO));
+    let sandbox = Sandbox::new("subscription-slow");
+
+    let error = sandbox.client(&server).fetch(1).unwrap_err();
+
+    assert!(error.starts_with("API request failed"), "{}", error);
+}
+
+#[test]
+fn subscription_fetch_needs_a_token() {
+    let server = StubServer::start(Reply::ok(SUBSCRIPTION_INFO));
+    let sandbox = Sandbox::new("subscription-no-token");
+    std::fs::write(sandbox.settings_path(), r#"{ "env": {} }"#).unwrap();
+
+    let error = sandbox.client(&server).fetch(1).unwrap_err();
+
+    assert_eq!(error, "Failed to get OAuth token");
+    assert_eq!(server.hits(), 0);
+}
+
+#[test]
+fn subscription_cache_skips_the_network() {
+    let server = StubServer::start(Reply::ok(SUBSCRIPTION_INFO));
+    let sandbox = Sandbox::new("subscription-cache");
+    let client = sandbox.client(&server);
+
+    client.get_with_cache(1, 300).unwrap();
+    let cached = client.get_with_cache(1, 300).unwrap();
+
+    assert_eq!(cached.normal_balance.amount_dollar, 12.5);
+    assert_eq!(server.hits(), 1);
+}
+
+#[test]
+fn health_check_measures_latency() {
+    let server = StubServer::start(Reply::ok(SUBSCRIPTION_INFO));
+    let sandbox = Sandbox::new("health");
+
+    assert!(sandbox.client(&server).check_health_latency(1).is_ok());
+
+    let unreachable = SubscriptionApiClient::new()
+        .with_base_url("http://127.0.0.1:9")
+        .with_settings_path(sandbox.settings_path());
+    let error = unreachable.check_health_latency(1).unwrap_err();
+    assert!(error.starts_with("Health check failed"), "{}", error);
+}
+
+#[test]
+fn cubence_segment_renders_balance_and_latency() {
+    let server = StubServer::start(Reply::ok(SUBSCRIPTION_INFO));
+    let sandbox = Sandbox::new("cubence-ok");
+
+    let data = sandbox.cubence(&server).collect(&input()).unwrap();
+
+    assert!(
+        data.primary.starts_with("Cubence - 余额[$12.50] 延迟["),
+        "{}",
+        data.primary
+    );
+    assert!(data.metadata.contains_key("latency_ms"));
+}
+
+#[test]
+fn cubence_segment_renders_api_errors() {
+    let sandbox = Sandbox::new("cubence-errors");
+
+    for (reply, expected) in [
+        (Reply::status(401, "{}"), "401"),
+        (Reply::status(500, "{}"), "500"),
+        (Reply::ok(MALFORMED), "Failed to parse response"),
+        (Reply::slow(SUBSCRIPTION_INFO), "API request failed"),
+    ] {
+        let server = StubServer::start(reply);
+        let data = sandbox.cubence(&server).collect(&input()).unwrap();
+
+        assert_eq!(data.metadata["error"], "fetch_failed");
+        let line = render(SegmentId::Cubence, data);
+        assert!(line.contains("Cubence - API Error: "), "{}", line);
+        assert!(line.contains(expected), "{}", line);
+    }
+}
+
+#[test]
+fn usage_segment_renders_utilization() {
+    let server = StubServer::start(Reply::ok(USAGE));
+    let sandbox = Sandbox::new("usage-ok");
+
+    let data = sandbox.usage(&server).collect(&input()).unwrap();
+
+    assert_eq!(data.primary, "42%");
+    assert_eq!(data.metadata["seven_day_utilization"], "10");
+}
+
+#[test]
+fn usage_segment_renders_api_errors_without_cache() {
+    let sandbox = Sandbox::new("usage-errors");
+
+    for reply in [
+        Reply::status(401, "{}"),
+        Reply::status(500, "{}"),
+        Reply::ok(MALFORMED),
+        Reply::slow(USAGE),
+    ] {
+        let server = StubServer::start(reply);
+        let data = sandbox.usage(&server).collect(&input()).unwrap();
+
+        assert_eq!(data.metadata["error"], "api_error");
+        assert!(render(SegmentId::Usage, data).contains("API Error"));
+        assert_eq!(server.hits(), 1);
+    }
+}
+
+#[test]
+fn usage_segment_falls_back_to_stale_cache() {
+    let server = StubServer::start(Reply::status(500, "{}"));
+    let sandbox = Sandbox::new("usage-stale");
+    std::fs::write(
+        sandbox.cache_dir().join(".api_usage_cache.json"),
+        r#"{
+            "five_hour_utilization": 17.0,
+            "seven_day_utilization": 30.0,
+            "resets_at": null,
+            "cached_at": "2020-01-01T00:00:00Z"
+        }"#,
+    )
+    .unwrap();
+
+    let data = sandbox.usage(&server).collect(&input()).unwrap();
+
+    assert_eq!(server.hits(), 1);
+    assert_eq!(data.primary, "17%");
+    assert!(!data.metadata.contains_key("error"));
+}
+
+#[test]
+fn usage_segment_caches_responses() {
+    let server = StubServer::start(Reply::ok(USAGE));
+    let sandbox = Sandbox::new("usage-cache");
+
+    sandbox.usage(&server).collect(&input()).unwrap();
+    let data = sandbox.usage(&server).collect(&input()).unwrap();
+
+    assert_eq!(data.primary, "42%");
+    assert_eq!(server.hits(), 1);
+}
+
+#[test]
+fn usage_segment_without_token() {
+    let server = StubServer::start(Reply::ok(USAGE));
+    let sandbox = Sandbox::new("usage-no-token");
+    std::fs::write(sandbox.settings_path(), "{}").unwrap();
+
+    let data = sandbox.usage(&server).collect(&input()).unwrap();
+
+    assert_eq!(data.primary, "No Token");
+    assert_eq!(server.hits(), 0);
+}
+
+#[test]
+fn cached_data_replays_without_the_network() {
+    let server = StubServer::start(Reply::ok(USAGE));
+    let sandbox = Sandbox::new("cached-replay");
+    assert!(sandbox.usage(&server).cached_response().is_none());
+
+    sandbox.usage(&server).collect(&input()).unwrap();
+    let response = sandbox.usage(&server).cached_response().unwrap();
+    let data = UsageSegment::new()
+        .with_canned_response(response)
+        .collect(&input())
+        .unwrap();
+    assert_eq!(data.primary, "42%");
+
+    let server = StubServer::start(Reply::ok(SUBSCRIPTION_INFO));
+    let client = sandbox.client(&server);
+    client.get_with_cache(1, 300).unwrap();
+    let info = client.cached_info().unwrap();
+    assert_eq!(info.normal_balance.amount_dollar, 12.5);
+    assert_eq!(server.hits(), 1);
+}
@@ -0,0 +1,41 @@
+//! Command-line parsing that depends on more than clap's defaults.
+
+use clap::Parser;
+use cubenceline::cli::Cli;
+use std::path::PathBuf;
+
+fn parse(args: &[&str]) -> Cli {
+    Cli::try_parse_from(std::iter::once("cubenceline").chain(args.iter().copied())).unwrap()
+}
+
+#[test]
+fn a_bare_config_flag_opens_the_configurator() {
+    let cli = parse(&["--config"]);
+    assert!(cli.open_configurator());
+    assert_eq!(cli.config_file().unwrap(), None);
+
+    // The next argument is not taken as the file
+    let cli = parse(&["-c", "schema"]);
+    assert!(cli.open_configurator());
+    assert!(cli.command.is_some());
+
+    let cli = parse(&["--profile", "work", "--config"]);
+    assert!(cli.open_configurator());
+    assert!(cli.config_file().unwrap().unwrap().ends_with("work.toml"));
+}
+
+#[test]
+fn config_files_are_given_with_an_equals_sign() {
+    let cli = parse(&["--config=/tmp/ccline.toml"]);
+    assert!(!cli.open_configurator());
+    assert_eq!(
+        cli.config_file().unwrap(),
+        Some(PathBuf::from("/tmp/ccline.toml"))
+    );
+
+    let cli = parse(&["-c=/tmp/ccline.toml", "--print"]);
+    assert_eq!(
+        cli.config_file().unwrap(),
+        Some(PathBuf::from("/tmp/ccline.toml"))
+    );
+}
@@ -0,0 +1,159 @@
+//! Upgrading config files written by older versions.
+#![cfg(feature = "tui")]
+
+use cubenceline::config::migrate::{self, CURRENT_VERSION};
+use cubenceline::ui::themes::ThemePresets;
+use std::path::PathBuf;
+use toml::Value;
+
+fn segment<'a>(value: &'a Value, id: &str) -> Option<&'a Value> {
+    value["segments"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|segment| segment["id"].as_str() == Some(id))
+}
+
+/// The default theme as an unversioned config
+fn unversioned_default() -> Value {
+    let mut value = Value::try_from(ThemePresets::get_theme("default")).unwrap();
+    value.as_table_mut().unwrap().remove("version");
+    value
+}
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "cubenceline-migrate-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn legacy_segments_table_becomes_a_list() {
+    let value: Value = toml::from_str(
+        r#"
+            theme = "default"
+            [segments]
+            git = false
+            model = true
+        "#,
+    )
+    .unwrap();
+
+    let migrated = migrate::migrate(value).unwrap().expect("needs migrating");
+    assert_eq!(migrated.from, 0);
+    assert_eq!(
+        migrated.value["version"].as_integer(),
+        Some(CURRENT_VERSION.into())
+    );
+    assert_eq!(
+        migrated.value["segments"].as_array().unwrap().len(),
+        ThemePresets::get_theme("default").segments.len()
+    );
+    assert_eq!(
+        segment(&migrated.value, "git").unwrap()["enabled"].as_bool(),
+        Some(false)
+    );
+    assert_eq!(
+        segment(&migrated.value, "model").unwrap()["enabled"].as_bool(),
+        Some(true)
+    );
+    assert!(migrated.changes[0].contains("legacy [segments] table"));
+}
+
+#[test]
+fn missing_segments_and_options_are_added_around_user_values() {
+    let mut value = unversioned_default();
+    let segments = value["segments"].as_array_mut().unwrap();
+    segments.retain(|segment| segment["id"].as_str() != Some("usage"));
+    let directory = segments
+        .iter_mut()
+        .find(|segment| segment["id"].as_str() == Some("directory"))
+        .unwrap();
+    let options = directory["options"].as_table_mut().unwrap();
+    options.remove("max_components");
+    options.insert("mode".to_string(), Value::String("full".to_string()));
+
+    let migrated = migrate::migrate(value).unwrap().expect("needs migrating");
+
+    let ids: Vec<&str> = migrated.value["segments"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|segment| segment["id"].as_str())
+        .collect();
+    assert_eq!(ids.last(), Some(&"usage"), "appended at the end");
+
+    let options = &segment(&migrated.value, "directory").unwrap()["options"];
+    assert_eq!(options["mode"].as_str(), Some("full"), "user value kept");
+    assert_eq!(options["max_components"].as_integer(), Some(0));
+
+    assert!(migrated
+        .changes
+        .contains(&"added option directory.max_components = 0".to_string()));
+    assert!(migrated
+        .changes
+        .iter()
+        .any(|change| change.starts_with("added segment 'usage'")));
+}
+
+#[test]
+fn current_configs_are_left_alone_and_newer_ones_rejected() {
+    let mut value = unversioned_default();
+    let table = value.as_table_mut().unwrap();
+    table.insert(
+        "version".to_string(),
+        Value::Integer(CURRENT_VERSION.into()),
+    );
+    assert!(migrate::migrate(value.clone()).unwrap().is_none());
+
+    let table = value.as_table_mut().unwrap();
+    table.insert(
+        "version".to_string(),
+        Value::Integer((CURRENT_VERSION + 1).into()),
+    );
+    let error = migrate::migrate(value).err().expect("newer version");
+    assert!(error
+        .to_string()
+        .contains("newer than this cubenceline supports"));
+}
+
+#[test]
+fn migrating_a_file_keeps_the_first_backup() {
+    let dir = scratch_dir("backup");
+    let path = dir.join("config.toml");
+    assert_eq!(
+        migrate::backup_path(&path, 0),
+        dir.join("config.toml.v0.bak")
+    );
+
+    let original = toml::to_string(&unversioned_default()).unwrap();
+    std::fs::write(&path, &original).unwrap();
+    migrate::migrate_file(&path)
+        .unwrap()
+        .expect("needs migrating");
+    assert_eq!(
+        std::fs::read_to_string(migrate::backup_path(&path, 0)).unwrap(),
+        original
+    );
+    let migrated: Value = toml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(migrate::config_version(&migrated), CURRENT_VERSION);
+    assert!(migrate::migrate_file(&path).unwrap().is_none());
+
+    // An older backup of the same version stays as it was
+    std::fs::write(&path, &original).unwrap();
+    std::fs::write(migrate::backup_path(&path, 0), "earliest").unwrap();
+    migrate::migrate_file(&path)
+        .unwrap()
+        .expect("needs migrating");
+    assert_eq!(
+        std::fs::read_to_string(migrate::backup_path(&path, 0)).unwrap(),
+        "earliest"
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,96 @@
+//! Loading the config files for rendering, as `ConfigReport` does.
+#![cfg(feature = "tui")]
+
+use cubenceline::config::{ColorSupport, ConfigReport, SegmentId};
+use cubenceline::core::segments::SegmentData;
+use cubenceline::core::StatusLineGenerator;
+use cubenceline::ui::themes::ThemePresets;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// A scratch config directory shared by the tests of this file
+fn config_dir() -> &'static PathBuf {
+    static DIR: OnceLock<PathBuf> = OnceLock::new();
+    DIR.get_or_init(|| {
+        let dir = std::env::temp_dir().join(format!("cubenceline-report-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::env::set_var("CCLINE_CONFIG_DIR", &dir);
+        dir
+    })
+}
+
+#[test]
+fn unversioned_configs_render_as_written() {
+    let dir = config_dir();
+    let mut value = toml::Value::try_from(ThemePresets::get_theme("nord")).unwrap();
+    let table = value.as_table_mut().unwrap();
+    table.remove("version");
+    table["style"]
+        .as_table_mut()
+        .unwrap()
+        .insert("separator".to_string(), " ⟫ ".into());
+    std::fs::write(dir.join("config.toml"), toml::to_string(&value).unwrap()).unwrap();
+
+    let report = ConfigReport::load(None);
+    assert_eq!(report.error_count(), 0);
+    assert!(report
+        .diagnostics
+        .iter()
+        .any(|d| d.message.contains("cubenceline migrate")));
+
+    let config = report.config.expect("config is built despite the warning");
+    assert_eq!(config.theme, "nord");
+    assert_eq!(config.style.separator, " ⟫ ");
+
+    let segments = config
+        .segments
+        .iter()
+        .filter(|segment| matches!(segment.id, SegmentId::Model | SegmentId::Directory))
+        .map(|segment| {
+            let mut segment = segment.clone();
+            segment.enabled = true;
+            let data = SegmentData {
+                primary: "text".to_string(),
+                secondary: String::new(),
+                metadata: Default::default(),
+            };
+            (segment, data)
+        })
+        .collect();
+    let line = StatusLineGenerator::new(config)
+        .with_color_support(ColorSupport::None)
+        .generate(segments);
+    assert!(line.contains(" ⟫ "), "{}", line);
+    assert!(!line.contains("config error"), "{}", line);
+}
+
+#[test]
+fn unknown_themes_are_reported_instead_of_failing() {
+    config_dir();
+    let report = ConfigReport::load_with_theme(Some("nrod"), None);
+    assert_eq!(report.error_count(), 1);
+    assert_eq!(report.diagnostics[0].location, "--theme");
+    assert!(report.diagnostics[0]
+        .message
+        .contains("Unknown theme 'nrod'"));
+
+    let report = ConfigReport::load_with_theme(Some("nord"), None);
+    assert_eq!(report.error_count(), 0);
+    assert_eq!(report.config.unwrap().theme, "nord");
+}
+
+#[test]
+fn broken_project_files_are_counted_with_a_theme() {
+    let project = config_dir().join("project");
+    std::fs::create_dir_all(project.join("src")).unwrap();
+    std::fs::write(project.join(".ccline.toml"), "style = [").unwrap();
+
+    let report = ConfigReport::load_with_theme(Some("nord"), Some(&project.join("src")));
+    assert_eq!(report.error_count(), 1);
+    assert_eq!(
+        report.diagnostics[0].location,
+        project.join(".ccline.toml").display().to_string()
+    );
+    assert!(report.config.is_none(), "rendered with the defaults");
+}
@@ -0,0 +1,151 @@
+//! How the Directory segment renders the working directory in each mode.
+
+use cubenceline::core::segments::{DirectoryMode, DirectorySegment};
+use std::process::Command;
+use std::sync::Once;
+
+/// Render with a fixed home directory, shared by every test of this file
+fn format(mode: DirectoryMode, max: usize, current: &str, project: Option<&str>) -> String {
+    static HOME: Once = Once::new();
+    HOME.call_once(|| std::env::set_var("HOME", "/home/me"));
+
+    DirectorySegment::new()
+        .with_mode(mode)
+        .with_max_components(max)
+        .format_path(current, project)
+}
+
+#[test]
+fn name_shows_the_last_component() {
+    assert_eq!(
+        format(DirectoryMode::Name, 0, "/home/me/work/crate", None),
+        "crate"
+    );
+    assert_eq!(
+        format(DirectoryMode::Name, 0, "C:\\Users\\me\\crate", None),
+        "crate"
+    );
+    assert_eq!(format(DirectoryMode::Name, 0, "/", None), "root");
+}
+
+#[test]
+fn full_keeps_the_absolute_path() {
+    assert_eq!(
+        format(DirectoryMode::Full, 0, "/home/me/work/crate", None),
+        "/home/me/work/crate"
+    );
+    assert_eq!(format(DirectoryMode::Full, 0, "/", None), "/");
+}
+
+#[test]
+fn home_substitutes_a_tilde() {
+    assert_eq!(
+        format(DirectoryMode::Home, 0, "/home/me/work/crate", None),
+        "~/work/crate"
+    );
+    assert_eq!(format(DirectoryMode::Home, 0, "/home/me", None), "~");
+    // Only whole components match the home directory
+    assert_eq!(
+        format(DirectoryMode::Home, 0, "/home/meg/crate", None),
+        "/home/meg/crate"
+    );
+}
+
+#[test]
+fn project_starts_at_the_project_root() {
+    assert_eq!(
+        format(
+            DirectoryMode::Project,
+            0,
+            "/home/me/work/crate/src/core",
+            Some("/home/me/work/crate")
+        ),
+        "crate/src/core"
+    );
+    // A project dir that doesn't hold the current one is ignored
+    assert_eq!(
+        format(
+            DirectoryMode::Project,
+            0,
+            "/nonexistent/dir",
+            Some("/home/me/other")
+        ),
+        "/nonexistent/dir"
+    );
+}
+
+#[test]
+fn project_falls_back_to_the_git_work_tree() {
+    let repo = std::env::temp_dir().join(format!("cubenceline-dir-{}", std::process::id()));
+    let nested = repo.join("src").join("core");
+    std::fs::create_dir_all(&nested).unwrap();
+    let initialized = Command::new("git")
+        .args(["init", "-q"])
+        .current_dir(&repo)
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false);
+
+    if initialized {
+        let repo = repo.canonicalize().unwrap();
+        let nested = repo.join("src").join("core");
+        let name = repo.file_name().unwrap().to_string_lossy().to_string();
+        for _ in 0..2 {
+            assert_eq!(
+                format(DirectoryMode::Project, 0, nested.to_str().unwrap(), None),
+                format!("{}/src/core", name)
+            );
+        }
+    }
+
+    let _ = std::fs::remove_dir_all(&repo);
+}
+
+#[test]
+fn fish_abbreviates_all_but_the_last_component() {
+    assert_eq!(
+        format(DirectoryMode::Fish, 0, "/home/me/work/.config/crate", None),
+        "~/w/.c/crate"
+    );
+    assert_eq!(
+        format(DirectoryMode::Fish, 0, "/usr/local/share", None),
+        "/u/l/share"
+    );
+}
+
+#[test]
+fn max_components_keeps_the_tail() {
+    assert_eq!(
+        format(DirectoryMode::Full, 2, "/home/me/work/crate", None),
+        "…/work/crate"
+    );
+    assert_eq!(
+        format(DirectoryMode::Home, 3, "/home/me/work/crate", None),
+        "~/work/crate"
+    );
+    assert_eq!(
+        format(DirectoryMode::Fish, 2, "/home/me/work/deep/crate", None),
+        "…/d/crate"
+    );
+}
+
+#[test]
+fn windows_drives_are_anchors() {
+    assert_eq!(
+        format(DirectoryMode::Full, 0, "C:\\Users\\me\\crate", None),
+        "C:\\Users\\me\\crate"
+    );
+    assert_eq!(format(DirectoryMode::Full, 0, "C:\\", None), "C:");
+    assert_eq!(
+        format(DirectoryMode::Fish, 0, "D:\\Projects\\Crate", None),
+        "D:\\P\\Crate"
+    );
+    assert_eq!(
+        format(DirectoryMode::Full, 2, "C:\\Users\\me\\crate", None),
+        "…\\me\\crate"
+    );
+    assert_eq!(
+        format(DirectoryMode::Full, 0, "C:/Users/me", None),
+        "C:/Users/me"
+    );
+}
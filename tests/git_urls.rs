@@ -0,0 +1,23 @@
+//! Web links of git remotes and branches.
+
+use cubenceline::core::segments::git::{branch_web_url, remote_web_url};
+
+#[test]
+fn remotes_become_web_urls() {
+    assert_eq!(
+        remote_web_url("git@github.com:owner/repo.git").as_deref(),
+        Some("https://github.com/owner/repo")
+    );
+    assert_eq!(
+        branch_web_url("https://gitlab.com/owner/repo.git", "main").as_deref(),
+        Some("https://gitlab.com/owner/repo/-/tree/main")
+    );
+}
+
+#[test]
+fn branch_names_are_percent_encoded() {
+    assert_eq!(
+        branch_web_url("git@github.com:owner/repo.git", "feature/50% #2 why?").as_deref(),
+        Some("https://github.com/owner/repo/tree/feature/50%25%20%232%20why%3F")
+    );
+}
@@ -0,0 +1,51 @@
+//! Which icon the language segment shows.
+
+use cubenceline::config::{IconConfig, InputData};
+use cubenceline::core::segments::{LanguageSegment, Segment};
+use std::path::PathBuf;
+
+/// A Rust project, with the cache kept next to it
+fn rust_project() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("cubenceline-language-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("Cargo.toml"), "[package]\n").unwrap();
+    std::env::set_var("CCLINE_CONFIG_DIR", &dir);
+    std::env::remove_var("XDG_CACHE_HOME");
+    dir
+}
+
+fn input(dir: &PathBuf) -> InputData {
+    serde_json::from_value(serde_json::json!({
+        "model": { "id": "claude-sonnet-4-5", "display_name": "Sonnet 4.5" },
+        "workspace": { "current_dir": dir },
+        "transcript_path": "/nonexistent.jsonl"
+    }))
+    .unwrap()
+}
+
+#[test]
+fn a_configured_icon_wins_over_the_languages() {
+    let dir = rust_project();
+
+    let data = LanguageSegment::new().collect(&input(&dir)).unwrap();
+    assert_eq!(data.primary, "Rust");
+    assert_eq!(data.metadata["dynamic_icon_plain"], "🦀");
+
+    let icon = IconConfig {
+        plain: "🧰".to_string(),
+        nerd_font: String::new(),
+    };
+    assert!(LanguageSegment::icon_is_configured(&icon));
+    assert!(!LanguageSegment::icon_is_configured(&IconConfig {
+        plain: String::new(),
+        nerd_font: String::new(),
+    }));
+    let data = LanguageSegment::new()
+        .with_dynamic_icon(false)
+        .collect(&input(&dir))
+        .unwrap();
+    assert!(!data.metadata.contains_key("dynamic_icon"));
+    assert!(!data.metadata.contains_key("dynamic_icon_plain"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,136 @@
+//! Golden snapshots of every built-in theme in every style mode.
+//!
+//! Run with `UPDATE_SNAPSHOTS=1 cargo test` to rewrite the files in
+//! `tests/snapshots/` after an intentional rendering change.
+#![cfg(feature = "tui")]
+
+use cubenceline::config::{ColorSupport, Config, SegmentConfig, SegmentId, StyleMode};
+use cubenceline::core::segments::SegmentData;
+use cubenceline::core::StatusLineGenerator;
+use cubenceline::ui::themes::{ThemePresets, BUILTIN_THEMES};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+const STYLE_MODES: [(StyleMode, &str); 3] = [
+    (StyleMode::Plain, "plain"),
+    (StyleMode::NerdFont, "nerd_font"),
+    (StyleMode::Powerline, "powerline"),
+];
+
+fn segment_data(primary: &str, secondary: &str, metadata: &[(&str, &str)]) -> SegmentData {
+    SegmentData {
+        primary: primary.to_string(),
+        secondary: secondary.to_string(),
+        metadata: metadata
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect::<HashMap<_, _>>(),
+    }
+}
+
+/// Fixed data for every segment, independent of the environment
+fn fixed_data(id: SegmentId) -> SegmentData {
+    match id {
+        SegmentId::Model => segment_data("Sonnet 4.5", "", &[]),
+        SegmentId::Directory => segment_data(
+            "cubenceline",
+            "",
+            &[("link", "file:///home/user/projects/cubenceline")],
+        ),
+        SegmentId::Git => segment_data(
+            "main",
+            "● ↑2 ↓1",
+            &[(
+                "link",
+                "https://github.com/Cubence-com/CubenceLine/tree/main",
+            )],
+        ),
+        SegmentId::ContextWindow => segment_data("78.2%", "· 156.4k", &[]),
+        SegmentId::Usage => segment_data("24%", "· 11-2-15", &[("dynamic_icon", "\u{f0aa0}")]),
+        SegmentId::Cubence => segment_data("Cubence - 余额[$899.00] 延迟[120ms]", "", &[]),
+        SegmentId::Cost => segment_data("$0.42", "", &[]),
+        SegmentId::Session => segment_data("3m45s", "+156 -23", &[]),
+        SegmentId::OutputStyle => segment_data("default", "", &[]),
+        SegmentId::Update => segment_data("\u{f06b0} Update v1.2.0!", "", &[]),
+        SegmentId::Language => segment_data(
+            "Rust 1.89",
+            "",
+            &[("dynamic_icon", "\u{e7a8}"), ("dynamic_icon_plain", "🦀")],
+        ),
+    }
+}
+
+fn render(config: &Config) -> String {
+    let segments: Vec<(SegmentConfig, SegmentData)> = config
+        .segments
+        .iter()
+        .map(|segment| (segment.clone(), fixed_data(segment.id)))
+        .collect();
+
+    StatusLineGenerator::new(config.clone())
+        .with_color_support(ColorSupport::Truecolor)
+        .generate(segments)
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{}.ansi", name))
+}
+
+#[test]
+fn builtin_themes_match_snapshots() {
+    let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+    let mut mismatches = Vec::new();
+
+    for theme in BUILTIN_THEMES {
+        for (mode, mode_name) in STYLE_MODES {
+            let mut config = ThemePresets::get_builtin_theme(theme)
+                .unwrap_or_else(|| panic!("built-in theme '{}' is missing", theme));
+            config.style.mode = mode;
+
+            let name = format!("{}_{}", theme, mode_name);
+            let path = snapshot_path(&name);
+            let actual = render(&config);
+
+            if update {
+                std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                std::fs::write(&path, &actual).unwrap();
+                continue;
+            }
+
+            match std::fs::read_to_string(&path) {
+                Ok(expected) if expected == actual => {}
+                Ok(expected) => mismatches.push(format!(
+                    "{}\n  expected: {:?}\n  actual:   {:?}",
+                    name, expected, actual
+                )),
+                Err(_) => mismatches.push(format!("{}: missing snapshot {}", name, path.display())),
+            }
+        }
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "rendering differs from the golden snapshots (rerun with UPDATE_SNAPSHOTS=1 if intended):\n{}",
+        mismatches.join("\n")
+    );
+}
+
+#[test]
+fn plain_color_support_has_no_escapes() {
+    let config = ThemePresets::get_builtin_theme("powerline-dark").unwrap();
+    let segments: Vec<(SegmentConfig, SegmentData)> = config
+        .segments
+        .iter()
+        .map(|segment| (segment.clone(), fixed_data(segment.id)))
+        .collect();
+
+    let output = StatusLineGenerator::new(config)
+        .with_color_support(ColorSupport::None)
+        .generate(segments);
+
+    assert!(!output.contains('\x1b'), "{:?}", output);
+    assert!(output.contains("Sonnet 4.5"));
+}
@@ -0,0 +1,54 @@
+//! Right-aligned segment groups padded out to the line width.
+#![cfg(feature = "tui")]
+
+use cubenceline::config::{ColorSupport, Config, SegmentAlign, SegmentId};
+use cubenceline::core::segments::SegmentData;
+use cubenceline::core::statusline::{strip_ansi, visible_width};
+use cubenceline::core::StatusLineGenerator;
+
+fn render(fill: &str, width: u16) -> String {
+    let mut config = Config::default();
+    config.style.width = Some(width);
+    config.style.fill = Some(fill.to_string());
+
+    let mut segments = Vec::new();
+    for (id, align) in [
+        (SegmentId::Model, SegmentAlign::Left),
+        (SegmentId::Cost, SegmentAlign::Right),
+    ] {
+        let mut segment = config
+            .segments
+            .iter()
+            .find(|segment| segment.id == id)
+            .unwrap()
+            .clone();
+        segment.enabled = true;
+        segment.align = align;
+        let data = SegmentData {
+            primary: "abc".to_string(),
+            secondary: String::new(),
+            metadata: Default::default(),
+        };
+        segments.push((segment, data));
+    }
+
+    StatusLineGenerator::new(config)
+        .with_color_support(ColorSupport::None)
+        .generate(segments)
+}
+
+#[test]
+fn fill_pads_to_the_line_width() {
+    let line = render("─", 60);
+    assert_eq!(visible_width(&line), 60);
+    assert!(strip_ansi(&line).contains("───"));
+}
+
+#[test]
+fn wide_fill_characters_count_two_columns() {
+    for width in [60, 61] {
+        let line = render("中", width);
+        assert_eq!(visible_width(&line), width as usize);
+        assert!(strip_ansi(&line).contains("中中"));
+    }
+}
@@ -0,0 +1,95 @@
+//! The generated JSON Schemas accept what CubenceLine writes and reject what
+//! `--check` reports as errors.
+
+use cubenceline::config::schema::{schema, SchemaKind};
+use cubenceline::config::ModelConfig;
+use cubenceline::ui::themes::{ThemePresets, BUILTIN_THEMES};
+use serde_json::Value;
+
+fn validator(kind: SchemaKind) -> jsonschema::Validator {
+    jsonschema::validator_for(&schema(kind)).expect("schema compiles")
+}
+
+fn toml_to_json(content: &str) -> Value {
+    let value: toml::Value = toml::from_str(content).unwrap();
+    serde_json::to_value(value).unwrap()
+}
+
+fn default_config() -> String {
+    toml::to_string_pretty(&ThemePresets::get_default()).unwrap()
+}
+
+#[test]
+fn builtin_themes_match_config_and_theme_schemas() {
+    let config = validator(SchemaKind::Config);
+    let theme = validator(SchemaKind::Theme);
+    for name in BUILTIN_THEMES {
+        let content = toml::to_string_pretty(&ThemePresets::get_theme(name)).unwrap();
+        let instance = toml_to_json(&content);
+        assert!(config.is_valid(&instance), "{} against config", name);
+        assert!(theme.is_valid(&instance), "{} against theme", name);
+    }
+}
+
+#[test]
+fn unknown_and_mistyped_options_are_rejected() {
+    let config = validator(SchemaKind::Config);
+
+    let unknown = default_config().replace("show_sha = false", "show_sha = false\nsha = true");
+    assert!(!config.is_valid(&toml_to_json(&unknown)));
+
+    let mistyped = default_config().replace("show_sha = false", "show_sha = \"yes\"");
+    assert!(!config.is_valid(&toml_to_json(&mistyped)));
+
+    let bad_choice = default_config().replace("mode = \"name\"", "mode = \"short\"");
+    assert!(!config.is_valid(&toml_to_json(&bad_choice)));
+}
+
+#[test]
+fn c16_is_limited_to_sixteen_colors() {
+    let config = validator(SchemaKind::Config);
+    let content = default_config();
+    let c16 = content.find("c16 = ").expect("default theme uses c16");
+    let end = c16 + content[c16..].find('\n').unwrap();
+    let content = format!("{}c16 = 20{}", &content[..c16], &content[end..]);
+    assert!(!config.is_valid(&toml_to_json(&content)));
+}
+
+#[test]
+fn overrides_only_need_segment_ids() {
+    let partial = toml_to_json(
+        r#"
+        [style]
+        separator = " :: "
+
+        [[segments]]
+        id = "git"
+        [segments.options]
+        show_sha = true
+        "#,
+    );
+    assert!(validator(SchemaKind::Project).is_valid(&partial));
+    assert!(!validator(SchemaKind::Config).is_valid(&partial));
+
+    let without_id = toml_to_json("[[segments]]\nenabled = false\n");
+    assert!(!validator(SchemaKind::Project).is_valid(&without_id));
+}
+
+#[test]
+fn themes_may_extend_another_theme() {
+    let theme = toml_to_json("extends = \"nord\"\n\n[style]\nseparator = \" | \"\n");
+    assert!(validator(SchemaKind::Theme).is_valid(&theme));
+
+    let not_a_name = toml_to_json("extends = 1\n");
+    assert!(!validator(SchemaKind::Theme).is_valid(&not_a_name));
+}
+
+#[test]
+fn models_schema_matches_model_entries() {
+    let models = validator(SchemaKind::Models);
+    let builtin = serde_json::to_value(ModelConfig::default()).unwrap();
+    assert!(models.is_valid(&builtin));
+
+    let missing_limit = toml_to_json("[[models]]\npattern = \"glm\"\ndisplay_name = \"GLM\"\n");
+    assert!(!models.is_valid(&missing_limit));
+}
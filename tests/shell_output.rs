@@ -0,0 +1,24 @@
+use cubenceline::core::output::{to_bash, to_zsh};
+
+const HOSTILE_BRANCH: &str = "\x1b[32m$(id)`id`\x1b[0m";
+
+#[test]
+fn bash_text_cannot_run_commands() {
+    assert_eq!(
+        to_bash(HOSTILE_BRANCH),
+        "\\[\x1b[32m\\]\\\\$(id)\\\\`id\\\\`\\[\x1b[0m\\]"
+    );
+    // Four backslashes decode to `\\`, which expands to `\`; `\\$` stays `$`
+    assert_eq!(to_bash("C:\\$HOME"), "C:\\\\\\\\\\\\$HOME");
+}
+
+#[test]
+fn zsh_text_cannot_run_commands() {
+    assert_eq!(
+        to_zsh(HOSTILE_BRANCH),
+        "%{\x1b[32m%}\\$(id)\\`id\\`%{\x1b[0m%}"
+    );
+    assert_eq!(to_zsh("100% $PWD"), "100%% \\$PWD");
+    // The backslash can't escape the one protecting `$`
+    assert_eq!(to_zsh("C:\\$HOME"), "C:\\\\\\$HOME");
+}
@@ -0,0 +1,131 @@
+//! Importing color schemes from other tools as themes.
+#![cfg(feature = "tui")]
+
+use cubenceline::config::{AnsiColor, Config, SegmentId};
+use cubenceline::ui::themes::import::{
+    import_theme, parse_color, sanitize_theme_name, theme_name_from_path, ImportFormat,
+};
+use std::path::PathBuf;
+
+fn import(file: &str) -> (Config, ImportFormat) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("import")
+        .join(file);
+    import_theme(&path, "imported").unwrap()
+}
+
+/// Text and background color of a segment
+fn colors(config: &Config, id: SegmentId) -> (AnsiColor, AnsiColor) {
+    let segment = config
+        .segments
+        .iter()
+        .find(|segment| segment.id == id)
+        .unwrap();
+    (
+        segment.colors.text.clone().unwrap(),
+        segment.colors.background.clone().unwrap(),
+    )
+}
+
+fn rgb(hex: &str) -> AnsiColor {
+    parse_color(hex).unwrap()
+}
+
+#[test]
+fn starship_module_styles_and_palette_names() {
+    let (config, format) = import("starship.toml");
+    assert_eq!(format, ImportFormat::Starship);
+    assert_eq!(config.theme, "imported");
+
+    // `fg:base bg:mauve` resolves through the selected palette
+    assert_eq!(
+        colors(&config, SegmentId::Directory),
+        (rgb("#1e1e2e"), rgb("#cba6f7"))
+    );
+    // A foreground-only style becomes the background, with dark text
+    assert_eq!(
+        colors(&config, SegmentId::Git),
+        (rgb("#1e1e2e"), rgb("#a6e3a1"))
+    );
+    // `mauve` is the magenta slot
+    assert_eq!(
+        colors(&config, SegmentId::ContextWindow),
+        (rgb("#1e1e2e"), rgb("#cba6f7"))
+    );
+}
+
+#[test]
+fn oh_my_posh_segments_and_palette_references() {
+    let (config, format) = import("theme.omp.json");
+    assert_eq!(format, ImportFormat::OhMyPosh);
+
+    assert_eq!(
+        colors(&config, SegmentId::Directory),
+        (rgb("#ffffff"), rgb("#b16286"))
+    );
+    assert_eq!(
+        colors(&config, SegmentId::Git),
+        (rgb("#282828"), rgb("#98971a"))
+    );
+}
+
+#[test]
+fn base16_slots() {
+    let (config, format) = import("base16.yaml");
+    assert_eq!(format, ImportFormat::Base16);
+
+    assert_eq!(
+        colors(&config, SegmentId::Git),
+        (rgb("#1d1f21"), rgb("#b5bd68"))
+    );
+    assert_eq!(
+        colors(&config, SegmentId::Directory),
+        (rgb("#1d1f21"), rgb("#81a2be"))
+    );
+}
+
+#[test]
+fn windows_terminal_camel_case_names() {
+    let (config, format) = import("windows-terminal.json");
+    assert_eq!(format, ImportFormat::WindowsTerminal);
+
+    assert_eq!(
+        colors(&config, SegmentId::Model),
+        (rgb("#0C0C0C"), rgb("#3A96DD"))
+    );
+    assert_eq!(
+        colors(&config, SegmentId::Cubence),
+        (rgb("#0C0C0C"), rgb("#3B78FF"))
+    );
+}
+
+#[test]
+fn alacritty_normal_and_bright_groups() {
+    let (config, format) = import("alacritty.toml");
+    assert_eq!(format, ImportFormat::Alacritty);
+
+    assert_eq!(
+        colors(&config, SegmentId::Usage),
+        (rgb("#282c34"), rgb("#e5c07b"))
+    );
+    assert_eq!(
+        colors(&config, SegmentId::Session),
+        (rgb("#282c34"), rgb("#c678dd"))
+    );
+    assert_eq!(
+        colors(&config, SegmentId::Git),
+        (rgb("#282c34"), rgb("#98c379"))
+    );
+}
+
+#[test]
+fn theme_names_stay_in_the_themes_directory() {
+    assert_eq!(sanitize_theme_name("../../.bashrc"), "bashrc");
+    assert_eq!(sanitize_theme_name("My Theme/dark"), "my-theme-dark");
+    assert_eq!(sanitize_theme_name(".."), "");
+    assert_eq!(
+        theme_name_from_path(&PathBuf::from("/tmp/Gruvbox Dark.omp.json")),
+        "gruvbox-dark-omp"
+    );
+}
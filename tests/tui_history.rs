@@ -0,0 +1,60 @@
+//! Undo/redo of the configurator's edits.
+#![cfg(feature = "tui")]
+
+use cubenceline::config::Config;
+use cubenceline::ui::history::History;
+
+fn with_separator(separator: &str) -> Config {
+    let mut config = Config::default();
+    config.style.separator = separator.to_string();
+    config
+}
+
+fn separator(config: Option<Config>) -> String {
+    config.expect("a snapshot").style.separator
+}
+
+#[test]
+fn undo_and_redo_walk_the_snapshots() {
+    let mut history = History::new();
+    history.record(with_separator("a"));
+    history.record(with_separator("b"));
+
+    assert_eq!(separator(history.undo(with_separator("c"))), "b");
+    assert_eq!(separator(history.undo(with_separator("b"))), "a");
+    assert!(history.undo(with_separator("a")).is_none());
+
+    assert_eq!(separator(history.redo(with_separator("a"))), "b");
+    assert_eq!(separator(history.redo(with_separator("b"))), "c");
+    assert!(history.redo(with_separator("c")).is_none());
+}
+
+#[test]
+fn a_new_edit_discards_undone_snapshots() {
+    let mut history = History::new();
+    history.record(with_separator("a"));
+    assert_eq!(separator(history.undo(with_separator("b"))), "a");
+
+    history.record(with_separator("a"));
+    assert!(history.redo(with_separator("d")).is_none());
+}
+
+#[test]
+fn typing_into_one_option_is_a_single_step() {
+    let mut history = History::new();
+    history.record_in_group(with_separator(""), "2.format");
+    history.record_in_group(with_separator("a"), "2.format");
+    history.record_in_group(with_separator("ab"), "2.format");
+    history.record_in_group(with_separator("abc"), "2.icon");
+
+    assert_eq!(separator(history.undo(with_separator("abcd"))), "abc");
+    assert_eq!(separator(history.undo(with_separator("abc"))), "");
+    assert!(history.undo(with_separator("")).is_none());
+
+    // Ending the group splits the next edit off, even in the same field
+    let mut history = History::new();
+    history.record_in_group(with_separator(""), "2.format");
+    history.end_group();
+    history.record_in_group(with_separator("a"), "2.format");
+    assert_eq!(separator(history.undo(with_separator("ab"))), "a");
+}
@@ -0,0 +1,150 @@
+//! Configurable key bindings and the command palette's fuzzy search.
+#![cfg(feature = "tui")]
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use cubenceline::ui::components::command_palette::{
+    fuzzy_match, CommandPaletteComponent, PaletteCommand, PaletteEntry,
+};
+use cubenceline::ui::keymap::{join_keys, validate, Action, Binding, KeyChord, Keymap};
+use cubenceline::ui::themes::BUILTIN_THEMES;
+use std::collections::HashMap;
+
+fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+    KeyEvent::new(code, modifiers)
+}
+
+fn keymap(entries: &[(&str, &str)]) -> Keymap {
+    let overrides: HashMap<String, String> = entries
+        .iter()
+        .map(|(key, action)| (key.to_string(), action.to_string()))
+        .collect();
+    Keymap::new(&overrides)
+}
+
+#[test]
+fn defaults_match_the_documented_keys() {
+    let keymap = Keymap::default();
+    let lookup = |code, modifiers| keymap.lookup(&key(code, modifiers)).cloned();
+
+    assert_eq!(
+        lookup(KeyCode::Char('p'), KeyModifiers::CONTROL),
+        Some(Binding::Action(Action::CommandPalette))
+    );
+    assert_eq!(
+        lookup(KeyCode::Char('p'), KeyModifiers::NONE),
+        Some(Binding::Action(Action::NextTheme))
+    );
+    assert_eq!(
+        lookup(KeyCode::Up, KeyModifiers::SHIFT),
+        Some(Binding::Action(Action::MoveSegmentUp))
+    );
+    // Letters are case-insensitive
+    assert_eq!(
+        lookup(KeyCode::Char('U'), KeyModifiers::SHIFT),
+        Some(Binding::Action(Action::Undo))
+    );
+    assert_eq!(
+        lookup(KeyCode::Char('4'), KeyModifiers::NONE),
+        Some(Binding::Theme("nord".to_string()))
+    );
+    assert_eq!(lookup(KeyCode::Char('z'), KeyModifiers::NONE), None);
+
+    // Every built-in theme has a digit
+    let mut themes: Vec<String> = keymap
+        .theme_keys()
+        .into_iter()
+        .map(|(_, theme)| theme.to_string())
+        .collect();
+    themes.sort();
+    let mut builtin: Vec<String> = BUILTIN_THEMES.iter().map(|t| t.to_string()).collect();
+    builtin.sort();
+    assert_eq!(themes, builtin);
+}
+
+#[test]
+fn overrides_rebind_add_and_remove_keys() {
+    let keymap = keymap(&[
+        ("Ctrl+T", "next_theme"),
+        ("p", "none"),
+        ("9", "theme:my-theme"),
+        ("x", "not_an_action"),
+    ]);
+
+    assert_eq!(
+        keymap
+            .lookup(&key(KeyCode::Char('t'), KeyModifiers::CONTROL))
+            .cloned(),
+        Some(Binding::Action(Action::NextTheme))
+    );
+    assert_eq!(
+        keymap.lookup(&key(KeyCode::Char('p'), KeyModifiers::NONE)),
+        None
+    );
+    assert_eq!(
+        keymap.lookup(&key(KeyCode::Char('x'), KeyModifiers::NONE)),
+        None
+    );
+    assert_eq!(
+        join_keys(&keymap.keys_for(&Binding::Action(Action::NextTheme))),
+        "Ctrl+T"
+    );
+
+    let theme_keys: Vec<KeyChord> = keymap.theme_keys().into_iter().map(|(k, _)| k).collect();
+    assert_eq!(join_keys(&theme_keys), "1-9");
+    assert_eq!(
+        keymap.lookup(&key(KeyCode::Char('9'), KeyModifiers::NONE)),
+        Some(&Binding::Theme("my-theme".to_string()))
+    );
+}
+
+#[test]
+fn invalid_entries_are_reported() {
+    let overrides: HashMap<String, String> = [
+        ("hyper+x", "undo"),
+        ("f13", "undo"),
+        ("x", "explode"),
+        ("y", "theme:"),
+        ("shift+tab", "switch_panel"),
+    ]
+    .into_iter()
+    .map(|(key, action)| (key.to_string(), action.to_string()))
+    .collect();
+
+    let keys: Vec<String> = validate(&overrides)
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect();
+    assert_eq!(keys, ["f13", "hyper+x", "x", "y"]);
+}
+
+#[test]
+fn fuzzy_search_prefers_word_starts_and_runs() {
+    assert!(fuzzy_match("thm nor", "Theme: nord").is_some());
+    assert!(fuzzy_match("xyz", "Theme: nord").is_none());
+    assert_eq!(fuzzy_match("", "anything").map(|(_, p)| p), Some(vec![]));
+
+    let (_, positions) = fuzzy_match("nord", "Theme: nord").unwrap();
+    assert_eq!(positions, vec![7, 8, 9, 10]);
+
+    let entry = |category, label: &str| {
+        PaletteEntry::new(
+            category,
+            label,
+            PaletteCommand::Run(Binding::Theme(label.to_string())),
+        )
+    };
+    let mut palette = CommandPaletteComponent::new();
+    palette.open(vec![
+        entry("Action", "Reset the theme to its defaults"),
+        entry("Theme", "powerline-dark"),
+        entry("Theme", "default"),
+    ]);
+    palette.input_char('d');
+    palette.input_char('e');
+    palette.input_char('f');
+    assert_eq!(palette.results()[0], "default");
+    assert_eq!(
+        palette.selected_command(),
+        Some(PaletteCommand::Run(Binding::Theme("default".to_string())))
+    );
+}
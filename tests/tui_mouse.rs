@@ -0,0 +1,74 @@
+//! Mouse hit-testing in the configurator.
+#![cfg(feature = "tui")]
+
+use cubenceline::config::AnsiColor;
+use cubenceline::ui::components::color_picker::ColorPickerComponent;
+use cubenceline::ui::components::segment_list::{Panel, SegmentListComponent};
+use cubenceline::ui::components::theme_selector::ThemeSelectorComponent;
+use cubenceline::ui::themes::ThemePresets;
+use ratatui::{backend::TestBackend, layout::Rect, Terminal};
+
+fn terminal() -> Terminal<TestBackend> {
+    Terminal::new(TestBackend::new(100, 40)).unwrap()
+}
+
+#[test]
+fn clicks_land_on_segments_and_drags_clamp_to_the_list() {
+    let config = ThemePresets::get_default();
+    let count = config.segments.len();
+    let mut list = SegmentListComponent::new();
+    terminal()
+        .draw(|f| list.render(f, Rect::new(0, 10, 30, 20), &config, 0, &Panel::SegmentList))
+        .unwrap();
+
+    assert_eq!(list.segment_at(5, 11, count), Some(0));
+    assert_eq!(list.segment_at(5, 13, count), Some(2));
+    assert_eq!(list.segment_at(5, 10, count), None, "border");
+    assert_eq!(list.segment_at(40, 11, count), None, "outside");
+    assert_eq!(list.segment_at(5, 11 + count as u16, count), None);
+
+    assert_eq!(list.nearest_segment(0, count), 0);
+    assert_eq!(list.nearest_segment(39, count), count - 1);
+}
+
+#[test]
+fn clicks_land_on_theme_names() {
+    let config = ThemePresets::get_default();
+    let themes = ThemePresets::list_available_themes();
+    let mut selector = ThemeSelectorComponent::new();
+    terminal()
+        .draw(|f| selector.render(f, Rect::new(0, 3, 100, 6), &config))
+        .unwrap();
+
+    // "[✓] default  [ ] ..." inside the border
+    assert_eq!(selector.theme_at(1, 4), Some(themes[0].as_str()));
+    let second = 1 + format!("[ ] {}  ", themes[0]).chars().count() as u16;
+    assert_eq!(selector.theme_at(second, 4), Some(themes[1].as_str()));
+    assert_eq!(selector.theme_at(0, 4), None, "border");
+}
+
+#[test]
+fn clicking_a_color_twice_picks_it() {
+    let mut picker = ColorPickerComponent::new();
+    picker.open();
+    let mut terminal = terminal();
+    terminal.draw(|f| picker.render(f, f.area())).unwrap();
+
+    // Find the swatch of the second color by clicking across the popup
+    let (column, row) = (0..100)
+        .flat_map(|column| (0..40).map(move |row| (column, row)))
+        .find(|&(column, row)| {
+            let mut probe = picker.clone();
+            !probe.click(column, row)
+                && probe.get_selected_color() == Some(AnsiColor::Color16 { c16: 1 })
+        })
+        .expect("swatch for color 1");
+
+    assert!(!picker.click(column, row), "first click only selects");
+    assert_eq!(
+        picker.get_selected_color(),
+        Some(AnsiColor::Color16 { c16: 1 })
+    );
+    assert!(picker.click(column, row), "second click picks");
+    assert!(!picker.click(0, 0), "outside the swatches");
+}
@@ -0,0 +1,60 @@
+//! Editing segment options from the configurator.
+#![cfg(feature = "tui")]
+
+use cubenceline::config::{SegmentConfig, SegmentId};
+use cubenceline::ui::components::options_editor::OptionsEditorComponent;
+use cubenceline::ui::themes::ThemePresets;
+use serde_json::Value;
+
+fn segment(id: SegmentId) -> SegmentConfig {
+    ThemePresets::get_default()
+        .segments
+        .into_iter()
+        .find(|segment| segment.id == id)
+        .expect("default theme has the segment")
+}
+
+#[test]
+fn booleans_toggle_and_choices_cycle() {
+    let mut git = segment(SegmentId::Git);
+    let mut editor = OptionsEditorComponent::new();
+    editor.open();
+    editor.activate(&mut git);
+    assert_eq!(git.options["show_sha"], Value::Bool(true));
+
+    let mut directory = segment(SegmentId::Directory);
+    editor.open();
+    editor.adjust(1, &mut directory);
+    assert_eq!(directory.options["mode"], "full");
+    editor.adjust(-2, &mut directory);
+    assert_eq!(directory.options["mode"], "fish");
+    editor.reset_selected(&mut directory);
+    assert_eq!(directory.options["mode"], "name");
+}
+
+#[test]
+fn invalid_numbers_are_rolled_back() {
+    let mut directory = segment(SegmentId::Directory);
+    let mut editor = OptionsEditorComponent::new();
+    editor.open();
+    editor.move_selection(1, &directory);
+
+    editor.activate(&mut directory);
+    editor.backspace(&mut directory);
+    editor.input_char('x', &mut directory);
+    editor.input_char('3', &mut directory);
+    assert_eq!(directory.options["max_components"], 3);
+    assert!(editor.finish_edit(&mut directory).is_some());
+    assert!(!editor.is_editing());
+
+    editor.activate(&mut directory);
+    editor.backspace(&mut directory);
+    let message = editor.finish_edit(&mut directory).expect("an error");
+    assert!(message.contains("expected"), "{}", message);
+    assert_eq!(directory.options["max_components"], 3);
+
+    editor.activate(&mut directory);
+    editor.input_char('9', &mut directory);
+    editor.cancel_edit(&mut directory);
+    assert_eq!(directory.options["max_components"], 3);
+}
@@ -0,0 +1,89 @@
+use cubenceline::config::RawUsage;
+
+fn normalize(json: &str) -> cubenceline::config::NormalizedUsage {
+    serde_json::from_str::<RawUsage>(json).unwrap().normalize()
+}
+
+#[test]
+fn anthropic_usage_sums_input_and_cache_tokens() {
+    let usage = normalize(
+        r#"{
+            "input_tokens": 12,
+            "output_tokens": 880,
+            "cache_creation_input_tokens": 2400,
+            "cache_read_input_tokens": 45800
+        }"#,
+    );
+
+    assert_eq!(usage.input_tokens, 12);
+    assert_eq!(usage.output_tokens, 880);
+    assert_eq!(usage.cache_creation_input_tokens, 2400);
+    assert_eq!(usage.cache_read_input_tokens, 45800);
+    assert_eq!(usage.total_tokens, 12 + 880 + 2400 + 45800);
+    assert_eq!(usage.calculation_source, "total_from_components");
+    assert_eq!(usage.display_tokens(), 12 + 880 + 2400 + 45800);
+}
+
+#[test]
+fn openai_usage_prefers_total_tokens() {
+    let usage = normalize(
+        r#"{
+            "prompt_tokens": 1500,
+            "completion_tokens": 300,
+            "total_tokens": 1800
+        }"#,
+    );
+
+    assert_eq!(usage.input_tokens, 1500);
+    assert_eq!(usage.output_tokens, 300);
+    assert_eq!(usage.total_tokens, 1800);
+    assert_eq!(usage.calculation_source, "total_tokens_direct");
+    assert_eq!(usage.total_for_cost(), 1800);
+}
+
+#[test]
+fn openai_nested_cached_tokens_count_as_cache_reads() {
+    let usage = normalize(
+        r#"{
+            "prompt_tokens": 2000,
+            "completion_tokens": 100,
+            "prompt_tokens_details": { "cached_tokens": 1200, "audio_tokens": 0 }
+        }"#,
+    );
+
+    assert_eq!(usage.cache_read_input_tokens, 1200);
+    assert!(usage.raw_data_available.contains(&"cache_read".to_string()));
+}
+
+#[test]
+fn anthropic_fields_win_over_openai_fields() {
+    let usage = normalize(
+        r#"{
+            "input_tokens": 10,
+            "prompt_tokens": 99,
+            "cache_read_input_tokens": 500,
+            "cache_read_prompt_tokens": 700,
+            "cached_tokens": 900
+        }"#,
+    );
+
+    assert_eq!(usage.input_tokens, 10);
+    assert_eq!(usage.cache_read_input_tokens, 500);
+}
+
+#[test]
+fn total_only_payload_is_displayed_as_total() {
+    let usage = normalize(r#"{ "total_tokens": 4321 }"#);
+
+    assert_eq!(usage.total_tokens, 4321);
+    assert_eq!(usage.display_tokens(), 4321);
+}
+
+#[test]
+fn empty_payload_normalizes_to_zero() {
+    let usage = normalize(r#"{ "unknown_field": true }"#);
+
+    assert_eq!(usage.total_tokens, 0);
+    assert_eq!(usage.display_tokens(), 0);
+    assert!(usage.raw_data_available.is_empty());
+}
@@ -0,0 +1,35 @@
+use cubenceline::core::statusline::{strip_ansi, visible_width};
+
+#[test]
+fn ascii_text() {
+    assert_eq!(visible_width("Sonnet 4.5"), 10);
+}
+
+#[test]
+fn escape_sequences_take_no_space() {
+    assert_eq!(visible_width("\x1b[1m\x1b[38;2;255;0;0mred\x1b[0m"), 3);
+    assert_eq!(visible_width("\x1b[4:3m\x1b[58;5;1mcurly\x1b[0m"), 5);
+}
+
+#[test]
+fn hyperlinks_take_no_space() {
+    let link = "\x1b]8;;https://example.com\x1b\\main\x1b]8;;\x1b\\";
+    assert_eq!(visible_width(link), 4);
+    assert_eq!(strip_ansi(link), "main");
+
+    let bel_terminated = "\x1b]8;;file:///tmp\x07tmp\x1b]8;;\x07";
+    assert_eq!(visible_width(bel_terminated), 3);
+}
+
+#[test]
+fn wide_characters_take_two_columns() {
+    assert_eq!(visible_width("余额"), 4);
+    assert_eq!(visible_width("Cubence - 余额[$899.00]"), 23);
+    assert_eq!(visible_width("🤖 Sonnet"), 9);
+}
+
+#[test]
+fn nerd_font_glyphs_take_one_column() {
+    assert_eq!(visible_width("\u{e0b0}"), 1);
+    assert_eq!(visible_width("\u{f02a2} main"), 6);
+}